@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+use andromeda_core::{Instrumentation, LoggingInstrumentation};
+use clap::ValueEnum;
+use tracing::Level;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable with per-target log directives, e.g. `ANDROMEDA_LOG=event_loop=trace,warn`.
+pub const LOG_ENV_VAR: &str = "ANDROMEDA_LOG";
+
+/// Maximum level of the logs written to stderr.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn directive(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// Install the global logger. `--quiet` wins over `--log-level`, which wins over
+/// `ANDROMEDA_LOG`, and warnings are shown when none of them are given.
+pub fn init(quiet: bool, log_level: Option<LogLevel>) {
+    let filter = if quiet {
+        EnvFilter::new(LogLevel::Error.directive())
+    } else if let Some(log_level) = log_level {
+        EnvFilter::new(log_level.directive())
+    } else {
+        EnvFilter::try_from_env(LOG_ENV_VAR)
+            .unwrap_or_else(|_| EnvFilter::new(LogLevel::Warn.directive()))
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .without_time()
+        .init();
+}
+
+/// Log the runtime instrumentation hooks when the `instrumentation` target is traced, e.g. with
+/// `ANDROMEDA_LOG=instrumentation=trace`. Must be called after [init].
+pub fn instrumentation() -> Option<Box<dyn Instrumentation>> {
+    if tracing::enabled!(target: "instrumentation", Level::TRACE) {
+        Some(Box::new(LoggingInstrumentation))
+    } else {
+        None
+    }
+}
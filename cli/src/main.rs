@@ -1,11 +1,22 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
-use andromeda_core::{Runtime, RuntimeConfig};
+use andromeda_core::{ExtensionError, NetAddress, Permission, Permissions, Runtime, RuntimeConfig};
 use andromeda_runtime::{
-    recommended_builtins, recommended_eventloop_handler, recommended_extensions,
+    exit_with_uncaught_exception, recommended_builtins, recommended_eventloop_handler,
+    recommended_extensions, DenoExt,
 };
-use clap::{Parser as ClapParser, Subcommand};
+use clap::{CommandFactory, Parser as ClapParser, Subcommand, ValueEnum};
+use clap_complete::{generate, Generator, Shell};
+use clap_complete_nushell::Nushell;
+use std::{
+    io::{stdout, Write},
+    path::PathBuf,
+};
+
+mod logging;
+use logging::LogLevel;
+
 /// A JavaScript runtime
 #[derive(Debug, ClapParser)]
 #[command(name = "andromeda")]
@@ -16,6 +27,14 @@ use clap::{Parser as ClapParser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Command,
+
+    /// Only print errors, hiding warnings and other output from the CLI itself
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Maximum level of the logs, overriding the ANDROMEDA_LOG environment variable
+    #[arg(long, value_enum, global = true)]
+    log_level: Option<LogLevel>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -28,56 +47,199 @@ enum Command {
         #[arg(short, long)]
         no_strict: bool,
 
+        /// Expose Andromeda.internals, meant for tooling
+        #[arg(long)]
+        expose_internals: bool,
+
+        /// Expose a subset of the Deno namespace, so Deno scripts run with minimal changes
+        #[arg(long)]
+        deno_compat: bool,
+
+        /// Allow every access, like passing all the --allow-* flags
+        #[arg(short = 'A', long)]
+        allow_all: bool,
+
+        /// Allow reading the file system, or only the given comma-separated paths
+        #[arg(
+            long,
+            value_name = "PATH",
+            require_equals = true,
+            num_args = 0..=1,
+            value_delimiter = ','
+        )]
+        allow_read: Option<Vec<PathBuf>>,
+
+        /// Allow writing the file system, or only the given comma-separated paths
+        #[arg(
+            long,
+            value_name = "PATH",
+            require_equals = true,
+            num_args = 0..=1,
+            value_delimiter = ','
+        )]
+        allow_write: Option<Vec<PathBuf>>,
+
+        /// Allow network access, or only to the given comma-separated hosts (host or host:port)
+        #[arg(
+            long,
+            value_name = "HOST",
+            require_equals = true,
+            num_args = 0..=1,
+            value_delimiter = ','
+        )]
+        allow_net: Option<Vec<String>>,
+
+        /// Allow environment access, or only to the given comma-separated variables
+        #[arg(
+            long,
+            value_name = "NAME",
+            require_equals = true,
+            num_args = 0..=1,
+            value_delimiter = ','
+        )]
+        allow_env: Option<Vec<String>>,
+
         /// The files to run
         #[arg(required = true)]
         paths: Vec<String>,
     },
+
+    /// Generate shell completions or a man page
+    Completions {
+        /// The shell to generate completions for
+        #[arg(value_enum, required_unless_present = "man")]
+        shell: Option<CompletionShell>,
+
+        /// Generate a man page instead of shell completions
+        #[arg(long, conflicts_with = "shell")]
+        man: bool,
+    },
+}
+
+/// Shells supported by the `completions` command.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CompletionShell {
+    Bash,
+    Elvish,
+    Fish,
+    Nushell,
+    #[value(name = "powershell")]
+    PowerShell,
+    Zsh,
+}
+
+/// Write the shell completions or the man page for the CLI to stdout.
+fn generate_completions(shell: Option<CompletionShell>, man: bool) -> std::io::Result<()> {
+    let mut command = Cli::command();
+    let mut out = stdout();
+
+    if man {
+        clap_mangen::Man::new(command).render(&mut out)?;
+        return out.flush();
+    }
+
+    fn print<G: Generator>(generator: G, command: &mut clap::Command, out: &mut impl Write) {
+        let name = command.get_name().to_string();
+        generate(generator, command, name, out);
+    }
+
+    match shell {
+        Some(CompletionShell::Bash) => print(Shell::Bash, &mut command, &mut out),
+        Some(CompletionShell::Elvish) => print(Shell::Elvish, &mut command, &mut out),
+        Some(CompletionShell::Fish) => print(Shell::Fish, &mut command, &mut out),
+        Some(CompletionShell::Nushell) => print(Nushell, &mut command, &mut out),
+        Some(CompletionShell::PowerShell) => print(Shell::PowerShell, &mut command, &mut out),
+        Some(CompletionShell::Zsh) => print(Shell::Zsh, &mut command, &mut out),
+        None => {}
+    }
+    out.flush()
+}
+
+/// Permission of an `--allow-*` flag: everything when given without values, nothing when missing.
+fn permission<T>(allow_all: bool, allowed: Option<Vec<T>>) -> Permission<T> {
+    match allowed {
+        _ if allow_all => Permission::All,
+        Some(allowed) if allowed.is_empty() => Permission::All,
+        Some(allowed) => Permission::Only(allowed),
+        None => Permission::none(),
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Cli::parse();
+    logging::init(args.quiet, args.log_level);
 
-    let rt = tokio::runtime::Builder::new_current_thread()
-        .enable_time()
-        .build()
-        .unwrap();
-
-    // Run Nova in a secondary blocking thread so tokio tasks can still run
-    let nova_thread = rt.spawn_blocking(|| match args.command {
+    match args.command {
         Command::Run {
             verbose,
             no_strict,
+            expose_internals,
+            deno_compat,
+            allow_all,
+            allow_read,
+            allow_write,
+            allow_net,
+            allow_env,
             paths,
         } => {
-            let mut runtime = Runtime::new(RuntimeConfig {
-                no_strict,
-                paths,
-                verbose,
-                extensions: recommended_extensions(),
-                builtins: recommended_builtins(),
-                eventloop_handler: recommended_eventloop_handler,
-            });
-            let runtime_result = runtime.run();
+            let permissions = Permissions {
+                read: permission(allow_all, allow_read),
+                write: permission(allow_all, allow_write),
+                net: permission(
+                    allow_all,
+                    allow_net
+                        .map(|hosts| hosts.iter().map(|host| NetAddress::parse(host)).collect()),
+                ),
+                env: permission(allow_all, allow_env),
+            }
+            .resolve_paths();
+
+            let rt = tokio::runtime::Builder::new_current_thread()
+                // The IO driver also delivers the signals of `Andromeda.shutdown`.
+                .enable_io()
+                .enable_time()
+                .build()
+                .unwrap();
+
+            // Run Nova in a secondary blocking thread so tokio tasks can still run
+            let quiet = args.quiet;
+            let nova_thread = rt.spawn_blocking(move || {
+                let mut extensions = recommended_extensions();
+                if deno_compat {
+                    extensions.push(DenoExt::new_extension());
+                }
+                let mut runtime = Runtime::new(RuntimeConfig {
+                    no_strict,
+                    paths,
+                    verbose,
+                    extensions,
+                    builtins: recommended_builtins(),
+                    eventloop_handler: recommended_eventloop_handler,
+                    expose_internals,
+                    instrumentation: logging::instrumentation(),
+                    permissions,
+                })?;
+                let runtime_result = runtime.run();
 
-            match runtime_result {
-                Ok(result) => {
-                    if verbose {
-                        println!("{:?}", result);
+                match runtime_result {
+                    Ok(result) => {
+                        if verbose && !quiet {
+                            println!("{:?}", result);
+                        }
                     }
+                    Err(error) => runtime.agent.run_in_realm(&runtime.realm_root, |agent| {
+                        exit_with_uncaught_exception(agent, error)
+                    }),
                 }
-                Err(error) => runtime.agent.run_in_realm(&runtime.realm_root, |agent| {
-                    eprintln!(
-                        "Uncaught exception: {}",
-                        error.value().string_repr(agent).as_str(agent)
-                    );
-                    std::process::exit(1);
-                }),
-            }
-        }
-    });
+                Ok::<_, ExtensionError>(())
+            });
 
-    rt.block_on(nova_thread)
-        .expect("oh no! Something went wrong when running Andromeda.");
+            rt.block_on(nova_thread)
+                .expect("oh no! Something went wrong when running Andromeda.")?;
+        }
+        // Completions don't need the JavaScript runtime at all
+        Command::Completions { shell, man } => generate_completions(shell, man)?,
+    }
 
     Ok(())
 }
@@ -0,0 +1,91 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use tokio::{sync::Semaphore, task::JoinError};
+
+/// Default maximum number of jobs a [BlockingPool] runs at the same time.
+pub const DEFAULT_BLOCKING_POOL_LIMIT: usize = 32;
+
+#[derive(Default)]
+struct Counters {
+    queued: AtomicU64,
+    running: AtomicU64,
+    completed: AtomicU64,
+}
+
+/// Snapshot of the activity of a [BlockingPool].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BlockingPoolStats {
+    /// Jobs waiting for a free slot.
+    pub queued: u64,
+    /// Jobs running right now.
+    pub running: u64,
+    /// Jobs that finished, including the ones that panicked.
+    pub completed: u64,
+}
+
+/// Pool running blocking work, such as file system calls or password hashing, on the Tokio
+/// blocking threads so the JavaScript thread never waits for it.
+/// At most `limit` jobs run at the same time, the others wait in a queue.
+#[derive(Clone)]
+pub struct BlockingPool {
+    permits: Arc<Semaphore>,
+    counters: Arc<Counters>,
+}
+
+impl BlockingPool {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(limit.max(1))),
+            counters: Arc::default(),
+        }
+    }
+
+    /// Queue a job and get a future resolving to its result. The future must be awaited in the
+    /// Tokio runtime, usually in a [HostData::spawn_macro_task](crate::HostData::spawn_macro_task).
+    pub fn spawn<F, R>(&self, job: F) -> impl Future<Output = Result<R, JoinError>> + Send + 'static
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let permits = self.permits.clone();
+        let counters = self.counters.clone();
+        counters.queued.fetch_add(1, Ordering::Relaxed);
+
+        async move {
+            // The semaphore is never closed.
+            let permit = permits.acquire_owned().await.unwrap();
+            counters.queued.fetch_sub(1, Ordering::Relaxed);
+            counters.running.fetch_add(1, Ordering::Relaxed);
+
+            let result = tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                job()
+            })
+            .await;
+
+            counters.running.fetch_sub(1, Ordering::Relaxed);
+            counters.completed.fetch_add(1, Ordering::Relaxed);
+            result
+        }
+    }
+
+    pub fn stats(&self) -> BlockingPoolStats {
+        BlockingPoolStats {
+            queued: self.counters.queued.load(Ordering::Relaxed),
+            running: self.counters.running.load(Ordering::Relaxed),
+            completed: self.counters.completed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for BlockingPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_BLOCKING_POOL_LIMIT)
+    }
+}
@@ -0,0 +1,258 @@
+use std::io;
+
+use nova_vm::ecmascript::{
+    execution::{
+        agent::{ExceptionType, JsError},
+        Agent,
+    },
+    types::{Function, Global, Value},
+};
+
+use crate::HostData;
+
+/// Classes of errors thrown by the ops. Each one is exposed to JavaScript as a subclass of `Error`
+/// under `Andromeda.errors`, so user code can tell them apart with `instanceof`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    NotFound,
+    PermissionDenied,
+    ConnectionRefused,
+    ConnectionReset,
+    ConnectionAborted,
+    NotConnected,
+    AddrInUse,
+    AddrNotAvailable,
+    BrokenPipe,
+    AlreadyExists,
+    InvalidData,
+    TimedOut,
+    Interrupted,
+    WriteZero,
+    UnexpectedEof,
+    Busy,
+    NotSupported,
+    /// The given resource ID does not point to a live resource.
+    BadResource,
+    /// A web platform `DOMException` with the given name.
+    DomException(DomExceptionName),
+    /// A plain `Error`, for everything that doesn't fit in the other classes.
+    Error,
+}
+
+impl ErrorClass {
+    /// Name of the JavaScript class.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NotFound => "NotFound",
+            Self::PermissionDenied => "PermissionDenied",
+            Self::ConnectionRefused => "ConnectionRefused",
+            Self::ConnectionReset => "ConnectionReset",
+            Self::ConnectionAborted => "ConnectionAborted",
+            Self::NotConnected => "NotConnected",
+            Self::AddrInUse => "AddrInUse",
+            Self::AddrNotAvailable => "AddrNotAvailable",
+            Self::BrokenPipe => "BrokenPipe",
+            Self::AlreadyExists => "AlreadyExists",
+            Self::InvalidData => "InvalidData",
+            Self::TimedOut => "TimedOut",
+            Self::Interrupted => "Interrupted",
+            Self::WriteZero => "WriteZero",
+            Self::UnexpectedEof => "UnexpectedEof",
+            Self::Busy => "Busy",
+            Self::NotSupported => "NotSupported",
+            Self::BadResource => "BadResource",
+            Self::DomException(_) => "DOMException",
+            Self::Error => "Error",
+        }
+    }
+}
+
+impl From<io::ErrorKind> for ErrorClass {
+    fn from(kind: io::ErrorKind) -> Self {
+        match kind {
+            io::ErrorKind::NotFound => Self::NotFound,
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            io::ErrorKind::ConnectionRefused => Self::ConnectionRefused,
+            io::ErrorKind::ConnectionReset => Self::ConnectionReset,
+            io::ErrorKind::ConnectionAborted => Self::ConnectionAborted,
+            io::ErrorKind::NotConnected => Self::NotConnected,
+            io::ErrorKind::AddrInUse => Self::AddrInUse,
+            io::ErrorKind::AddrNotAvailable => Self::AddrNotAvailable,
+            io::ErrorKind::BrokenPipe => Self::BrokenPipe,
+            io::ErrorKind::AlreadyExists => Self::AlreadyExists,
+            io::ErrorKind::InvalidData => Self::InvalidData,
+            io::ErrorKind::TimedOut => Self::TimedOut,
+            io::ErrorKind::Interrupted => Self::Interrupted,
+            io::ErrorKind::WriteZero => Self::WriteZero,
+            io::ErrorKind::UnexpectedEof => Self::UnexpectedEof,
+            io::ErrorKind::WouldBlock => Self::Busy,
+            io::ErrorKind::Unsupported => Self::NotSupported,
+            _ => Self::Error,
+        }
+    }
+}
+
+/// Names of the `DOMException`s that web APIs throw, as listed in the WebIDL specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomExceptionName {
+    IndexSizeError,
+    HierarchyRequestError,
+    WrongDocumentError,
+    InvalidCharacterError,
+    NoModificationAllowedError,
+    NotFoundError,
+    NotSupportedError,
+    InvalidStateError,
+    SyntaxError,
+    InvalidModificationError,
+    NamespaceError,
+    InvalidAccessError,
+    TypeMismatchError,
+    SecurityError,
+    NetworkError,
+    AbortError,
+    URLMismatchError,
+    QuotaExceededError,
+    TimeoutError,
+    InvalidNodeTypeError,
+    DataCloneError,
+    EncodingError,
+    NotReadableError,
+    UnknownError,
+    ConstraintError,
+    DataError,
+    TransactionInactiveError,
+    ReadOnlyError,
+    VersionError,
+    OperationError,
+    NotAllowedError,
+}
+
+impl DomExceptionName {
+    /// Value of the `name` property of the `DOMException`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::IndexSizeError => "IndexSizeError",
+            Self::HierarchyRequestError => "HierarchyRequestError",
+            Self::WrongDocumentError => "WrongDocumentError",
+            Self::InvalidCharacterError => "InvalidCharacterError",
+            Self::NoModificationAllowedError => "NoModificationAllowedError",
+            Self::NotFoundError => "NotFoundError",
+            Self::NotSupportedError => "NotSupportedError",
+            Self::InvalidStateError => "InvalidStateError",
+            Self::SyntaxError => "SyntaxError",
+            Self::InvalidModificationError => "InvalidModificationError",
+            Self::NamespaceError => "NamespaceError",
+            Self::InvalidAccessError => "InvalidAccessError",
+            Self::TypeMismatchError => "TypeMismatchError",
+            Self::SecurityError => "SecurityError",
+            Self::NetworkError => "NetworkError",
+            Self::AbortError => "AbortError",
+            Self::URLMismatchError => "URLMismatchError",
+            Self::QuotaExceededError => "QuotaExceededError",
+            Self::TimeoutError => "TimeoutError",
+            Self::InvalidNodeTypeError => "InvalidNodeTypeError",
+            Self::DataCloneError => "DataCloneError",
+            Self::EncodingError => "EncodingError",
+            Self::NotReadableError => "NotReadableError",
+            Self::UnknownError => "UnknownError",
+            Self::ConstraintError => "ConstraintError",
+            Self::DataError => "DataError",
+            Self::TransactionInactiveError => "TransactionInactiveError",
+            Self::ReadOnlyError => "ReadOnlyError",
+            Self::VersionError => "VersionError",
+            Self::OperationError => "OperationError",
+            Self::NotAllowedError => "NotAllowedError",
+        }
+    }
+}
+
+/// Error produced by an op, thrown to JavaScript as an instance of its [ErrorClass].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpError {
+    pub class: ErrorClass,
+    pub message: String,
+}
+
+impl OpError {
+    pub fn new(class: ErrorClass, message: impl Into<String>) -> Self {
+        Self {
+            class,
+            message: message.into(),
+        }
+    }
+
+    /// Error thrown to JavaScript as a `DOMException` of the given name.
+    ///
+    /// ```ignore
+    /// return Err(OpError::dom_exception(DomExceptionName::InvalidStateError, "The canvas is detached.")
+    ///     .throw::<RuntimeMacroTask>(agent));
+    /// ```
+    pub fn dom_exception(name: DomExceptionName, message: impl Into<String>) -> Self {
+        Self::new(ErrorClass::DomException(name), message)
+    }
+
+    /// Error for a resource ID that doesn't point to a live resource.
+    pub fn bad_resource() -> Self {
+        Self::new(ErrorClass::BadResource, "Bad resource ID")
+    }
+
+    /// Create the JavaScript error. Use it as `Err(error.throw::<UserMacroTask>(agent))` in ops.
+    pub fn throw<UserMacroTask: 'static>(self, agent: &mut Agent) -> JsError {
+        // The thrower always throws, so an `Ok` means it was never registered or is broken.
+        if let Some(Ok(thrower)) = ErrorThrower::get::<UserMacroTask>(agent).map(Function::try_from)
+        {
+            let class = Value::from_string(agent, self.class.name().to_string());
+            let message = Value::from_string(agent, self.message.clone());
+            let dom_exception_name = match self.class {
+                ErrorClass::DomException(name) => {
+                    Value::from_string(agent, name.name().to_string())
+                }
+                _ => Value::Undefined,
+            };
+            if let Err(error) = thrower.call(
+                agent,
+                Value::Undefined,
+                &[class, message, dom_exception_name],
+            ) {
+                return error;
+            }
+        }
+
+        agent.throw_exception(ExceptionType::Error, self.message)
+    }
+
+    /// Create the JavaScript error as a value, e.g. to reject a Promise with it.
+    pub fn into_value<UserMacroTask: 'static>(self, agent: &mut Agent) -> Value {
+        self.throw::<UserMacroTask>(agent).value()
+    }
+}
+
+impl From<io::Error> for OpError {
+    fn from(error: io::Error) -> Self {
+        Self::new(error.kind().into(), error.to_string())
+    }
+}
+
+impl std::fmt::Display for OpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.class {
+            ErrorClass::DomException(name) => write!(f, "{}: {}", name.name(), self.message),
+            class => write!(f, "{}: {}", class.name(), self.message),
+        }
+    }
+}
+
+/// JavaScript function `(className, message, domExceptionName) => never` that throws an instance of the error class,
+/// registered in the [OpsStorage](crate::OpsStorage) by the runtime.
+pub struct ErrorThrower(pub Global<Value>);
+
+impl ErrorThrower {
+    /// Get the registered thrower function, if any.
+    fn get<UserMacroTask: 'static>(agent: &mut Agent) -> Option<Value> {
+        let thrower = HostData::<UserMacroTask>::from_agent(agent).remove_storage::<Self>()?;
+        let value = thrower.0.get(agent);
+        HostData::<UserMacroTask>::from_agent(agent).insert_storage(thrower);
+        Some(value)
+    }
+}
@@ -1,10 +1,18 @@
 use nova_vm::ecmascript::types::{Global, Value};
 
+use crate::OpError;
+
 /// Collection of tasks dispatched and handled by the Runtime.
 #[derive(Debug)]
 pub enum MacroTask<UserMacroTask> {
     /// Resolve a promise.
     ResolvePromise(Global<Value>),
+    /// Resolve a promise with a string.
+    ResolvePromiseWithString(Global<Value>, String),
+    /// Resolve a promise with a boolean.
+    ResolvePromiseWithBoolean(Global<Value>, bool),
+    /// Reject a promise with the JavaScript error of an [OpError].
+    RejectPromise(Global<Value>, OpError),
     /// User-defined macro task.
     User(UserMacroTask),
 }
@@ -1,10 +1,17 @@
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+};
+
 use nova_vm::ecmascript::{
-    builtins::{create_builtin_function, Behaviour, BuiltinFunctionArgs, RegularFn},
-    execution::Agent,
+    builtins::{create_builtin_function, ArgumentsList, Behaviour, BuiltinFunctionArgs, RegularFn},
+    execution::{agent::ExceptionType, Agent, JsResult},
     scripts_and_modules::script::{parse_script, script_evaluation},
-    types::{InternalMethods, IntoValue, Object, PropertyDescriptor, PropertyKey},
+    types::{InternalMethods, IntoValue, Object, PropertyDescriptor, PropertyKey, Value},
 };
 
+use tracing::{debug, error};
+
 use crate::{exit_with_parse_errors, HostData, OpsStorage};
 
 pub type ExtensionStorageInit = Box<dyn FnOnce(&mut OpsStorage)>;
@@ -13,17 +20,160 @@ pub type ExtensionStorageInit = Box<dyn FnOnce(&mut OpsStorage)>;
 pub struct ExtensionOp {
     pub name: &'static str,
     pub args: u32,
+    /// Function bound to the global, see [extension_op!].
     pub function: RegularFn,
+    /// Whether the op returns a Promise.
+    pub is_async: bool,
 }
 
 impl ExtensionOp {
+    /// Op calling `function`. Prefer [extension_op!], whose function also records the op as
+    /// running, see [current_op].
     pub fn new(name: &'static str, function: RegularFn, args: u32) -> Self {
         Self {
             name,
             args,
             function,
+            is_async: false,
         }
     }
+
+    /// Op that returns a Promise settled by a macro task.
+    pub fn new_async(name: &'static str, function: RegularFn, args: u32) -> Self {
+        Self {
+            is_async: true,
+            ..Self::new(name, function, args)
+        }
+    }
+}
+
+/// Create an [ExtensionOp] whose function records the op as running while it calls the given
+/// function, see [current_op]. Prefix the name with `async` for ops that return a Promise.
+///
+/// ```ignore
+/// extension_op!("internal_open_file", Self::internal_open_file, 2)
+/// extension_op!(async "internal_chmod", Self::internal_chmod, 2)
+/// ```
+#[macro_export]
+macro_rules! extension_op {
+    (async $name:literal, $function:expr, $args:expr) => {
+        $crate::ExtensionOp::new_async(
+            $name,
+            |agent, this, args| $crate::run_op($name, $function, agent, this, args),
+            $args,
+        )
+    };
+    ($name:literal, $function:expr, $args:expr) => {
+        $crate::ExtensionOp::new(
+            $name,
+            |agent, this, args| $crate::run_op($name, $function, agent, this, args),
+            $args,
+        )
+    };
+}
+
+thread_local! {
+    /// Name of the op running on this thread, see [current_op].
+    static CURRENT_OP: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Name of the op running on this thread, e.g. `internal_open_file`, to name it in diagnostics.
+pub fn current_op() -> Option<&'static str> {
+    CURRENT_OP.get()
+}
+
+/// Records an op as running until it's dropped, when the previous one is restored.
+struct CurrentOpGuard(Option<&'static str>);
+
+impl CurrentOpGuard {
+    fn enter(name: &'static str) -> Self {
+        Self(CURRENT_OP.replace(Some(name)))
+    }
+}
+
+impl Drop for CurrentOpGuard {
+    fn drop(&mut self) {
+        CURRENT_OP.set(self.0);
+    }
+}
+
+/// Call the function of the op `name`, recording it as the running op meanwhile. Used by
+/// [extension_op!].
+/// A panic in the op is thrown as an `Error` rather than unwinding through the engine.
+#[doc(hidden)]
+pub fn run_op(
+    name: &'static str,
+    function: RegularFn,
+    agent: &mut Agent,
+    this: Value,
+    args: ArgumentsList,
+) -> JsResult<Value> {
+    let _guard = CurrentOpGuard::enter(name);
+    panic::catch_unwind(AssertUnwindSafe(|| function(agent, this, args))).unwrap_or_else(
+        |payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            error!(target: "extensions", "The {name} op panicked: {message}");
+            Err(agent.throw_exception(
+                ExceptionType::Error,
+                format!("The {name} op panicked: {message}"),
+            ))
+        },
+    )
+}
+
+/// Metadata of a registered [ExtensionOp], for tooling such as completions and docs generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpInfo {
+    pub name: &'static str,
+    pub args: u32,
+    pub is_async: bool,
+    /// Name of the [Extension] that registered the op.
+    pub extension: &'static str,
+}
+
+/// Every op registered by the loaded extensions, in registration order, kept in the [OpsStorage].
+#[derive(Debug, Default)]
+pub struct OpRegistry {
+    ops: Vec<OpInfo>,
+}
+
+impl OpRegistry {
+    /// Register the ops of the extensions, in order. Fails if two ops share a name, as the later
+    /// one would silently replace the global of the first.
+    pub fn new(extensions: &[Extension]) -> Result<Self, ExtensionError> {
+        let mut registry = Self::default();
+        for extension in extensions {
+            for op in &extension.ops {
+                if let Some(registered) = registry.get(op.name) {
+                    return Err(ExtensionError::DuplicateOp {
+                        op: op.name,
+                        first: registered.extension,
+                        second: extension.name,
+                    });
+                }
+                registry.ops.push(OpInfo {
+                    name: op.name,
+                    args: op.args,
+                    is_async: op.is_async,
+                    extension: extension.name,
+                });
+            }
+        }
+        Ok(registry)
+    }
+
+    pub fn ops(&self) -> &[OpInfo] {
+        &self.ops
+    }
+
+    /// Get the op bound to the global of that name.
+    pub fn get(&self, name: &str) -> Option<&OpInfo> {
+        self.ops.iter().find(|op| op.name == name)
+    }
 }
 
 /// Group of global functions. Usually every extension has it's own topic, e.g: fs, network, ffi, etc.
@@ -38,6 +188,111 @@ pub struct Extension {
 
     // JavaScript or Typescript files that are loaded by this extension.
     pub files: Vec<&'static str>,
+    /// Names of the extensions that must be loaded before this one.
+    pub deps: &'static [&'static str],
+    /// Predicate deciding whether the extension is registered at all, e.g. to leave out
+    /// platform specific extensions. Always registered when `None`.
+    pub cfg: Option<fn() -> bool>,
+}
+
+/// Error found while ordering the extensions by their dependencies or registering their ops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtensionError {
+    /// An extension depends on one that isn't registered or was left out by its `cfg`.
+    MissingDependency {
+        extension: &'static str,
+        dependency: &'static str,
+    },
+    /// The extensions depend on each other in a cycle, listed from and back to the same name.
+    Cycle(Vec<&'static str>),
+    /// Two extensions, or the same one twice, register an op of the same name.
+    DuplicateOp {
+        op: &'static str,
+        first: &'static str,
+        second: &'static str,
+    },
+}
+
+impl std::fmt::Display for ExtensionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingDependency {
+                extension,
+                dependency,
+            } => write!(
+                f,
+                "The {extension} extension depends on {dependency}, which is not registered"
+            ),
+            Self::Cycle(cycle) => write!(
+                f,
+                "Extensions depend on each other in a cycle: {}",
+                cycle.join(" -> ")
+            ),
+            Self::DuplicateOp { op, first, second } => write!(
+                f,
+                "The {second} extension registers the {op} op, which the {first} extension already registers"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExtensionError {}
+
+/// Drop the extensions whose `cfg` is false and sort the rest so every extension comes after its
+/// dependencies. Extensions that don't depend on each other keep their relative order.
+pub fn order_extensions(extensions: Vec<Extension>) -> Result<Vec<Extension>, ExtensionError> {
+    let mut pending: Vec<Extension> = extensions
+        .into_iter()
+        .filter(|extension| extension.cfg.map_or(true, |cfg| cfg()))
+        .collect();
+
+    for extension in &pending {
+        for dependency in extension.deps {
+            if !pending.iter().any(|other| other.name == *dependency) {
+                return Err(ExtensionError::MissingDependency {
+                    extension: extension.name,
+                    dependency,
+                });
+            }
+        }
+    }
+
+    let mut ordered: Vec<Extension> = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|extension| {
+            extension
+                .deps
+                .iter()
+                .all(|dependency| ordered.iter().any(|other| other.name == *dependency))
+        });
+        match ready {
+            Some(index) => ordered.push(pending.remove(index)),
+            None => return Err(ExtensionError::Cycle(find_cycle(&pending))),
+        }
+    }
+
+    Ok(ordered)
+}
+
+/// Find a dependency cycle among extensions that all wait on another pending extension.
+fn find_cycle(pending: &[Extension]) -> Vec<&'static str> {
+    let mut path = vec![pending[0].name];
+    loop {
+        let current = path[path.len() - 1];
+        let extension = pending.iter().find(|e| e.name == current).unwrap();
+        let next = extension
+            .deps
+            .iter()
+            .copied()
+            .find(|dependency| pending.iter().any(|e| e.name == *dependency))
+            .unwrap();
+        if let Some(start) = path.iter().position(|name| *name == next) {
+            let mut cycle = path.split_off(start);
+            cycle.push(next);
+            return cycle;
+        }
+        path.push(next);
+    }
 }
 
 impl Extension {
@@ -46,18 +301,18 @@ impl Extension {
         agent: &mut Agent,
         global_object: Object,
     ) {
-        for file in &self.files {
-            let source_text = nova_vm::ecmascript::types::String::from_str(agent, file);
-            let script =
-                match parse_script(agent, source_text, agent.current_realm_id(), true, None) {
-                    Ok(script) => script,
-                    Err(diagnostics) => exit_with_parse_errors(diagnostics, "<runtime>", file),
-                };
-            match script_evaluation(agent, script) {
-                Ok(_) => (),
-                Err(_) => println!("Error in runtime"),
-            }
+        debug!(
+            target: "extensions",
+            "Loading the {} extension ({} ops, {} files)",
+            self.name,
+            self.ops.len(),
+            self.files.len()
+        );
+
+        if let Some(storage_hook) = self.storage.take() {
+            HostData::<UserMacroTask>::from_agent(agent).with_ops_storage(storage_hook)
         }
+
         for op in &self.ops {
             let function = create_builtin_function(
                 agent,
@@ -77,11 +332,21 @@ impl Extension {
                 .unwrap();
         }
 
-        if let Some(storage_hook) = self.storage.take() {
-            let host_data = agent.get_host_data();
-            let host_data: &HostData<UserMacroTask> = host_data.downcast_ref().unwrap();
-            let mut storage = host_data.storage.borrow_mut();
-            (storage_hook)(&mut storage)
+        for file in &self.files {
+            let source_text = nova_vm::ecmascript::types::String::from_str(agent, file);
+            let script =
+                match parse_script(agent, source_text, agent.current_realm_id(), true, None) {
+                    Ok(script) => script,
+                    Err(diagnostics) => exit_with_parse_errors(diagnostics, "<runtime>", file),
+                };
+            match script_evaluation(agent, script) {
+                Ok(_) => (),
+                Err(_) => error!(
+                    target: "extensions",
+                    "Error in runtime: a file of the {} extension threw",
+                    self.name
+                ),
+            }
         }
     }
 }
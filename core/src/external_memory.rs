@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Bytes of external memory that must be allocated since the last garbage collection before
+/// the [Runtime](crate::Runtime) runs another one, when little external memory survived it.
+pub const EXTERNAL_MEMORY_GC_THRESHOLD: usize = 64 * 1024 * 1024;
+
+/// Accounting of the memory held on the Rust side on behalf of JavaScript objects, such as the
+/// buffers of the resources, which the garbage collector can't see.
+///
+/// Extensions report their allocations so the [Runtime](crate::Runtime) collects garbage when
+/// they grow, and the total is exposed to scripts as `Andromeda.memoryUsage().external`.
+///
+/// ```ignore
+/// let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+/// host_data.external_memory.allocate(image.len());
+/// // Once the resource is closed.
+/// host_data.external_memory.free(image.len());
+/// ```
+#[derive(Debug, Default)]
+pub struct ExternalMemory {
+    /// Bytes currently allocated.
+    total: AtomicUsize,
+    /// Bytes allocated since the last garbage collection.
+    since_gc: AtomicUsize,
+    /// Bytes still allocated right after the last garbage collection.
+    after_gc: AtomicUsize,
+}
+
+impl ExternalMemory {
+    /// Report `bytes` of newly allocated external memory.
+    pub fn allocate(&self, bytes: usize) {
+        self.total.fetch_add(bytes, Ordering::Relaxed);
+        self.since_gc.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Report `bytes` of external memory previously reported with [ExternalMemory::allocate] as freed.
+    pub fn free(&self, bytes: usize) {
+        let _ = self
+            .total
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+                Some(total.saturating_sub(bytes))
+            });
+    }
+
+    /// Bytes of external memory currently allocated.
+    pub fn total(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    /// Whether enough external memory was allocated since the last garbage collection to run
+    /// another one: [EXTERNAL_MEMORY_GC_THRESHOLD] bytes, or as many bytes as survived the
+    /// last one if that's more, so the collections get rarer as the live memory grows.
+    pub fn needs_gc(&self) -> bool {
+        let threshold = self
+            .after_gc
+            .load(Ordering::Relaxed)
+            .max(EXTERNAL_MEMORY_GC_THRESHOLD);
+        self.since_gc.load(Ordering::Relaxed) >= threshold
+    }
+
+    /// Start counting again after a garbage collection.
+    pub(crate) fn reset_after_gc(&self) {
+        self.since_gc.store(0, Ordering::Relaxed);
+        self.after_gc.store(self.total(), Ordering::Relaxed);
+    }
+}
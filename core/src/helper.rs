@@ -1,3 +1,4 @@
+use miette::{GraphicalReportHandler, GraphicalTheme, NamedSource};
 use oxc_diagnostics::OxcDiagnostic;
 
 /// Exit the program with parse errors.
@@ -15,7 +16,7 @@ pub fn exit_with_parse_errors(errors: Vec<OxcDiagnostic>, source_path: &str, sou
     // SAFETY: This function never returns, so `source`'s lifetime must last for
     // the duration of the program.
     let source: &'static str = unsafe { std::mem::transmute(source) };
-    let named_source = miette::NamedSource::new(source_path, source);
+    let named_source = NamedSource::new(source_path, source);
 
     for error in errors {
         let report = error.with_source_code(named_source.clone());
@@ -25,3 +26,19 @@ pub fn exit_with_parse_errors(errors: Vec<OxcDiagnostic>, source_path: &str, sou
 
     std::process::exit(1);
 }
+
+/// Render parse errors with the lines of the source they point at, like
+/// [exit_with_parse_errors] prints them but without colors, e.g. to return them from a test.
+pub fn format_parse_errors(errors: Vec<OxcDiagnostic>, source_path: &str, source: &str) -> String {
+    let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+    let named_source = NamedSource::new(source_path, source.to_string());
+
+    let mut rendered = String::new();
+    for error in errors {
+        let report = error.with_source_code(named_source.clone());
+        handler
+            .render_report(&mut rendered, &*report)
+            .expect("Writing to a String can't fail");
+    }
+    rendered
+}
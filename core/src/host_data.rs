@@ -1,7 +1,9 @@
 use std::{
-    cell::RefCell,
-    collections::HashMap,
+    any::type_name,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
     future::Future,
+    panic::Location,
     sync::{
         atomic::{AtomicU32, Ordering},
         mpsc::{Receiver, Sender},
@@ -10,9 +12,10 @@ use std::{
 };
 
 use anymap::AnyMap;
+use nova_vm::ecmascript::execution::Agent;
 use tokio::task::JoinHandle;
 
-use crate::{MacroTask, TaskId};
+use crate::{current_op, BlockingPool, ExternalMemory, MacroTask, TaskId};
 
 pub type OpsStorage = AnyMap;
 
@@ -28,8 +31,19 @@ pub struct HostData<UserMacroTask> {
     pub macro_task_count: Arc<AtomicU32>,
     /// Registry of async tasks.
     pub tasks: RefCell<HashMap<TaskId, JoinHandle<()>>>,
+    /// Tasks of [HostData::spawn_unref_macro_task], which the macro tasks counter leaves out.
+    unref_tasks: RefCell<HashSet<TaskId>>,
     /// Counter of accumulative created async tasks. Used for ID generation.
     pub task_count: Arc<AtomicU32>,
+    /// Macro tasks of the immediates phase, see [HostData::queue_immediate].
+    pub immediates: RefCell<VecDeque<UserMacroTask>>,
+    /// Pool for the blocking work of the ops.
+    pub blocking_pool: BlockingPool,
+    /// Memory held by the ops outside of the JavaScript heap.
+    pub external_memory: ExternalMemory,
+    /// Where the storage was last borrowed through [HostData::with_storage] and the like, to
+    /// explain conflicting borrows in debug builds.
+    storage_borrowed_at: Cell<Option<&'static Location<'static>>>,
 }
 
 impl<UserMacroTask> HostData<UserMacroTask> {
@@ -41,12 +55,140 @@ impl<UserMacroTask> HostData<UserMacroTask> {
                 macro_task_tx,
                 macro_task_count: Arc::new(AtomicU32::new(0)),
                 tasks: RefCell::default(),
+                unref_tasks: RefCell::default(),
                 task_count: Arc::default(),
+                immediates: RefCell::default(),
+                blocking_pool: BlockingPool::default(),
+                external_memory: ExternalMemory::default(),
+                storage_borrowed_at: Cell::new(None),
             },
             rx,
         )
     }
 
+    /// Get the [HostData] of the runtime the agent belongs to.
+    ///
+    /// # Panics
+    ///
+    /// If the agent was created by a runtime with another `UserMacroTask` type.
+    #[track_caller]
+    pub fn from_agent(agent: &Agent) -> &Self
+    where
+        UserMacroTask: 'static,
+    {
+        match agent.get_host_data().downcast_ref() {
+            Some(host_data) => host_data,
+            None => panic!(
+                "The host data of the agent is not a HostData<{}>",
+                type_name::<UserMacroTask>()
+            ),
+        }
+    }
+
+    /// Run `f` with the `T` kept in the storage, usually the resources of an extension.
+    ///
+    /// ```ignore
+    /// let rid = HostData::<RuntimeMacroTask>::from_agent(agent)
+    ///     .with_storage(|resources: &RegexExtResources| resources.patterns.push(regex));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If no `T` was inserted, e.g. because its extension isn't loaded, or if the storage is
+    /// already mutably borrowed. The message names the type, the running op and, in debug
+    /// builds, where the conflicting borrow was taken.
+    #[track_caller]
+    pub fn with_storage<T: 'static, R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        match self.try_with_storage(f) {
+            Some(result) => result,
+            None => storage_missing::<T>(),
+        }
+    }
+
+    /// Run `f` with the `T` kept in the storage if there is one, e.g. for the optional
+    /// configuration of an extension. See [HostData::with_storage].
+    #[track_caller]
+    pub fn try_with_storage<T: 'static, R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let storage = match self.storage.try_borrow() {
+            Ok(storage) => storage,
+            Err(_) => self.storage_borrow_conflict::<T>(),
+        };
+        let value = storage.get::<T>()?;
+        let previous = self.track_storage_borrow();
+        let result = f(value);
+        self.storage_borrowed_at.set(previous);
+        Some(result)
+    }
+
+    /// Run `f` with the `T` kept in the storage, mutably. See [HostData::with_storage].
+    #[track_caller]
+    pub fn with_storage_mut<T: 'static, R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut storage = match self.storage.try_borrow_mut() {
+            Ok(storage) => storage,
+            Err(_) => self.storage_borrow_conflict::<T>(),
+        };
+        let value = match storage.get_mut::<T>() {
+            Some(value) => value,
+            None => storage_missing::<T>(),
+        };
+        let previous = self.track_storage_borrow();
+        let result = f(value);
+        self.storage_borrowed_at.set(previous);
+        result
+    }
+
+    /// Insert `value` in the storage, returning the `T` it replaces. See [HostData::with_storage].
+    #[track_caller]
+    pub fn insert_storage<T: 'static>(&self, value: T) -> Option<T> {
+        self.with_ops_storage(|storage| storage.insert(value))
+    }
+
+    /// Remove the `T` kept in the storage, if any. See [HostData::with_storage].
+    #[track_caller]
+    pub fn remove_storage<T: 'static>(&self) -> Option<T> {
+        self.with_ops_storage(|storage| storage.remove::<T>())
+    }
+
+    /// Run `f` with the whole storage, mutably, e.g. for the storage hook of an extension.
+    /// See [HostData::with_storage].
+    #[track_caller]
+    pub fn with_ops_storage<R>(&self, f: impl FnOnce(&mut OpsStorage) -> R) -> R {
+        let mut storage = match self.storage.try_borrow_mut() {
+            Ok(storage) => storage,
+            Err(_) => self.storage_borrow_conflict::<OpsStorage>(),
+        };
+        let previous = self.track_storage_borrow();
+        let result = f(&mut storage);
+        self.storage_borrowed_at.set(previous);
+        result
+    }
+
+    /// Remember where the storage is being borrowed, returning the previous location to restore.
+    #[track_caller]
+    fn track_storage_borrow(&self) -> Option<&'static Location<'static>> {
+        if cfg!(debug_assertions) {
+            self.storage_borrowed_at.replace(Some(Location::caller()))
+        } else {
+            None
+        }
+    }
+
+    #[track_caller]
+    fn storage_borrow_conflict<T>(&self) -> ! {
+        match self.storage_borrowed_at.get() {
+            Some(location) => panic!(
+                "Cannot borrow {}{} from the ops storage, it is already borrowed at {location}",
+                type_name::<T>(),
+                in_current_op()
+            ),
+            None => panic!(
+                "Cannot borrow {}{} from the ops storage, it is already borrowed",
+                type_name::<T>(),
+                in_current_op()
+            ),
+        }
+    }
+
     /// Get an owned senderto the macro tasks event loop.
     pub fn macro_task_tx(&self) -> Sender<MacroTask<UserMacroTask>> {
         self.macro_task_tx.clone()
@@ -73,6 +215,37 @@ impl<UserMacroTask> HostData<UserMacroTask> {
         task_id
     }
 
+    /// Spawn an async task like [HostData::spawn_macro_task], except it doesn't keep the event
+    /// loop alive, e.g. the timer of `AbortSignal.timeout`.
+    pub fn spawn_unref_macro_task<F>(&self, future: F) -> TaskId
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let task_handle = tokio::spawn(async move {
+            future.await;
+        });
+
+        let task_id = TaskId::from_index(self.task_count.fetch_add(1, Ordering::Relaxed));
+        self.tasks.borrow_mut().insert(task_id, task_handle);
+        self.unref_tasks.borrow_mut().insert(task_id);
+
+        task_id
+    }
+
+    /// Queue a user macro task for the immediates phase of the event loop. Immediates run in
+    /// order after the microtasks checkpoint, before any other macro task such as timers or I/O.
+    /// Only the immediates queued before the phase started run in it, so an immediate queuing
+    /// another one can't starve the rest of the event loop.
+    pub fn queue_immediate(&self, macro_task: UserMacroTask) {
+        self.immediates.borrow_mut().push_back(macro_task);
+    }
+
+    /// Whether there are immediates waiting for the next immediates phase.
+    pub fn any_pending_immediates(&self) -> bool {
+        !self.immediates.borrow().is_empty()
+    }
+
     /// Abort a MacroTask execution given it's [TaskId].
     pub fn abort_macro_task(&self, task_id: TaskId) {
         let tasks = self.tasks.borrow();
@@ -80,11 +253,31 @@ impl<UserMacroTask> HostData<UserMacroTask> {
         task.abort();
 
         // Manualy decrease the macro tasks counter as the task was aborted.
-        self.macro_task_count.fetch_sub(1, Ordering::Relaxed);
+        if !self.unref_tasks.borrow_mut().remove(&task_id) {
+            self.macro_task_count.fetch_sub(1, Ordering::Relaxed);
+        }
     }
 
     /// Clear a MacroTask given it's [TaskId].
     pub fn clear_macro_task(&self, task_id: TaskId) {
         self.tasks.borrow_mut().remove(&task_id).unwrap();
+        self.unref_tasks.borrow_mut().remove(&task_id);
+    }
+}
+
+#[track_caller]
+fn storage_missing<T>() -> ! {
+    panic!(
+        "{} is not in the ops storage{}, is the extension that provides it loaded?",
+        type_name::<T>(),
+        in_current_op()
+    )
+}
+
+/// Name the op running when the storage was accessed, if any, for the panic messages.
+fn in_current_op() -> String {
+    match current_op() {
+        Some(op) => format!(" (accessed by the {op} op)"),
+        None => String::new(),
     }
 }
@@ -0,0 +1,208 @@
+use std::{future::Future, pin::Pin, rc::Rc};
+
+use nova_vm::ecmascript::{
+    builtins::{
+        create_builtin_function,
+        promise_objects::promise_abstract_operations::promise_capability_records::PromiseCapability,
+        ArgumentsList, Behaviour, BuiltinFunctionArgs, RegularFn,
+    },
+    execution::{Agent, JsResult},
+    scripts_and_modules::script::{parse_script, script_evaluation},
+    types::{self, Function, Global, IntoValue, Value},
+};
+use serde_json::Value as JsonValue;
+
+use crate::{exit_with_parse_errors, ErrorClass, HostData, MacroTask, OpError, Runtime};
+
+type SyncHostFn = Rc<dyn Fn(Vec<JsonValue>) -> Result<JsonValue, OpError>>;
+
+type HostFnFuture = Pin<Box<dyn Future<Output = Result<JsonValue, OpError>> + Send>>;
+
+type AsyncHostFn = Rc<dyn Fn(Vec<JsonValue>) -> HostFnFuture>;
+
+enum HostFn {
+    Sync(SyncHostFn),
+    Async(AsyncHostFn),
+}
+
+/// Functions registered with [Runtime::register_fn] and [Runtime::register_async_fn], kept in
+/// the ops storage and indexed by the id their JavaScript wrapper calls them with.
+#[derive(Default)]
+pub struct HostFunctions {
+    functions: Vec<HostFn>,
+}
+
+impl HostFunctions {
+    fn push(&mut self, function: HostFn) -> u32 {
+        self.functions.push(function);
+        self.functions.len() as u32 - 1
+    }
+}
+
+/// Define the wrapper of a host function at its dotted path from `globalThis`, creating the
+/// missing objects on the way. Arguments and return values cross the boundary as JSON.
+const HOST_FN_INSTALLER: &str = r#"(function (dispatch, id, name, isAsync) {
+  const path = name.split(".");
+  const key = path.pop();
+  let target = globalThis;
+  for (const segment of path) {
+    if (target[segment] === undefined) {
+      target[segment] = {};
+    }
+    target = target[segment];
+  }
+  target[key] = isAsync
+    ? (...args) => dispatch(id, JSON.stringify(args)).then(JSON.parse)
+    : (...args) => JSON.parse(dispatch(id, JSON.stringify(args)));
+})"#;
+
+impl<UserMacroTask> Runtime<UserMacroTask> {
+    /// Expose a Rust closure to the scripts as a function at `name`, a dotted path from
+    /// `globalThis` whose missing objects are created. The arguments are given as JSON values and
+    /// the returned value is converted back, while an [OpError] is thrown as its error class.
+    /// Register the functions before running the scripts that use them.
+    ///
+    /// ```ignore
+    /// runtime.register_fn("myApp.log", |args| {
+    ///     println!("{args:?}");
+    ///     Ok(serde_json::Value::Null)
+    /// })?;
+    /// ```
+    pub fn register_fn<F>(&mut self, name: &str, function: F) -> JsResult<()>
+    where
+        F: Fn(Vec<JsonValue>) -> Result<JsonValue, OpError> + 'static,
+    {
+        let id = self.push_host_fn(HostFn::Sync(Rc::new(function)));
+        self.install_host_fn(name, id, call_host_fn::<UserMacroTask>, false)
+    }
+
+    fn push_host_fn(&self, function: HostFn) -> u32 {
+        self.host_hooks.host_data.with_ops_storage(|storage| {
+            if storage.get::<HostFunctions>().is_none() {
+                storage.insert(HostFunctions::default());
+            }
+            let host_functions: &mut HostFunctions = storage.get_mut().unwrap();
+            host_functions.push(function)
+        })
+    }
+
+    fn install_host_fn(
+        &mut self,
+        name: &str,
+        id: u32,
+        dispatch: RegularFn,
+        is_async: bool,
+    ) -> JsResult<()> {
+        self.agent.run_in_realm(&self.realm_root, |agent| {
+            let source_text = types::String::from_str(agent, HOST_FN_INSTALLER);
+            let script =
+                match parse_script(agent, source_text, agent.current_realm_id(), true, None) {
+                    Ok(script) => script,
+                    Err(diagnostics) => {
+                        exit_with_parse_errors(diagnostics, "<runtime>", HOST_FN_INSTALLER)
+                    }
+                };
+            let installer: Function = script_evaluation(agent, script)?.try_into().unwrap();
+            let dispatch = create_builtin_function(
+                agent,
+                Behaviour::Regular(dispatch),
+                BuiltinFunctionArgs::new(2, "dispatch", agent.current_realm_id()),
+            );
+            let id = Value::from_f64(agent, id as f64);
+            let name = Value::from_string(agent, name.to_string());
+            installer.call(
+                agent,
+                Value::Undefined,
+                &[dispatch.into_value(), id, name, Value::Boolean(is_async)],
+            )?;
+            Ok(())
+        })
+    }
+}
+
+impl<UserMacroTask: Send> Runtime<UserMacroTask> {
+    /// Same as [Runtime::register_fn] for a closure returning a future. The function returns a
+    /// Promise settled through the macro task queue once the future, spawned on the Tokio
+    /// runtime, completes.
+    ///
+    /// ```ignore
+    /// runtime.register_async_fn("myApp.fetchUser", |args| async move {
+    ///     let user = users::fetch(args[0].as_u64().unwrap_or_default()).await?;
+    ///     Ok(serde_json::to_value(user).unwrap())
+    /// })?;
+    /// ```
+    pub fn register_async_fn<F, Fut>(&mut self, name: &str, function: F) -> JsResult<()>
+    where
+        F: Fn(Vec<JsonValue>) -> Fut + 'static,
+        Fut: Future<Output = Result<JsonValue, OpError>> + Send + 'static,
+    {
+        let function: AsyncHostFn = Rc::new(move |args| Box::pin(function(args)));
+        let id = self.push_host_fn(HostFn::Async(function));
+        self.install_host_fn(name, id, call_async_host_fn::<UserMacroTask>, true)
+    }
+}
+
+/// Get the host function and the arguments a wrapper was called with.
+fn host_fn_call<UserMacroTask: 'static>(
+    agent: &mut Agent,
+    args: &ArgumentsList,
+) -> JsResult<(u32, Vec<JsonValue>)> {
+    let id = args.get(0).to_uint32(agent)?;
+    let arguments = args.get(1).to_string(agent)?;
+    match serde_json::from_str(arguments.as_str(agent)) {
+        Ok(arguments) => Ok((id, arguments)),
+        Err(error) => {
+            Err(OpError::new(ErrorClass::InvalidData, error.to_string())
+                .throw::<UserMacroTask>(agent))
+        }
+    }
+}
+
+fn call_host_fn<UserMacroTask: 'static>(
+    agent: &mut Agent,
+    _this: Value,
+    args: ArgumentsList,
+) -> JsResult<Value> {
+    let (id, arguments) = host_fn_call::<UserMacroTask>(agent, &args)?;
+    let function = HostData::<UserMacroTask>::from_agent(agent).with_storage(
+        |host_functions: &HostFunctions| match &host_functions.functions[id as usize] {
+            HostFn::Sync(function) => function.clone(),
+            HostFn::Async(_) => unreachable!("The wrapper of an async host function is async"),
+        },
+    );
+
+    match function(arguments) {
+        Ok(value) => Ok(Value::from_string(agent, value.to_string())),
+        Err(error) => Err(error.throw::<UserMacroTask>(agent)),
+    }
+}
+
+fn call_async_host_fn<UserMacroTask: Send + 'static>(
+    agent: &mut Agent,
+    _this: Value,
+    args: ArgumentsList,
+) -> JsResult<Value> {
+    let (id, arguments) = host_fn_call::<UserMacroTask>(agent, &args)?;
+    let function = HostData::<UserMacroTask>::from_agent(agent).with_storage(
+        |host_functions: &HostFunctions| match &host_functions.functions[id as usize] {
+            HostFn::Async(function) => function.clone(),
+            HostFn::Sync(_) => unreachable!("The wrapper of a sync host function is sync"),
+        },
+    );
+    let future = function(arguments);
+
+    let promise_capability = PromiseCapability::new(agent);
+    let root_value = Global::new(agent, promise_capability.promise().into_value());
+    let host_data = HostData::<UserMacroTask>::from_agent(agent);
+    let macro_task_tx = host_data.macro_task_tx();
+
+    host_data.spawn_macro_task(async move {
+        let macro_task = match future.await {
+            Ok(value) => MacroTask::ResolvePromiseWithString(root_value, value.to_string()),
+            Err(error) => MacroTask::RejectPromise(root_value, error),
+        };
+        macro_task_tx.send(macro_task).unwrap();
+    });
+
+    Ok(Value::Promise(promise_capability.promise()))
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use tracing::trace;
+
+use crate::MacroTask;
+
+/// Kind of a macro task handled by the event loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroTaskKind {
+    ResolvePromise,
+    RejectPromise,
+    /// A macro task of the user runtime, e.g. a timer.
+    User,
+}
+
+impl<UserMacroTask> From<&MacroTask<UserMacroTask>> for MacroTaskKind {
+    fn from(macro_task: &MacroTask<UserMacroTask>) -> Self {
+        match macro_task {
+            MacroTask::ResolvePromise(_)
+            | MacroTask::ResolvePromiseWithString(..)
+            | MacroTask::ResolvePromiseWithBoolean(..) => Self::ResolvePromise,
+            MacroTask::RejectPromise(..) => Self::RejectPromise,
+            MacroTask::User(_) => Self::User,
+        }
+    }
+}
+
+/// Hooks fired by the [Runtime](crate::Runtime) around the work it does, so embedders can build
+/// profilers, deadlines or audit logs without patching it. Every hook does nothing by default.
+///
+/// ```ignore
+/// struct SlowJobs;
+///
+/// impl Instrumentation for SlowJobs {
+///     fn after_job(&self, elapsed: Duration) {
+///         if elapsed > Duration::from_millis(50) {
+///             eprintln!("A microtask took {elapsed:?}");
+///         }
+///     }
+/// }
+/// ```
+pub trait Instrumentation {
+    /// Before evaluating a script given its path.
+    fn before_script(&self, _path: &str) {}
+    /// After evaluating a script, whether it threw or not.
+    fn after_script(&self, _path: &str, _elapsed: Duration) {}
+    /// Before running a microtask (promise job).
+    fn before_job(&self) {}
+    /// After running a microtask.
+    fn after_job(&self, _elapsed: Duration) {}
+    /// Before handling a macro task received by the event loop.
+    fn before_macro_task(&self, _kind: MacroTaskKind) {}
+    /// After handling a macro task.
+    fn after_macro_task(&self, _kind: MacroTaskKind, _elapsed: Duration) {}
+    /// Before a garbage collection started with [Runtime::gc](crate::Runtime::gc).
+    fn before_gc(&self) {}
+    /// After a garbage collection.
+    fn after_gc(&self, _elapsed: Duration) {}
+}
+
+/// [Instrumentation] logging every hook at the trace level with the `instrumentation` target.
+pub struct LoggingInstrumentation;
+
+impl Instrumentation for LoggingInstrumentation {
+    fn before_script(&self, path: &str) {
+        trace!(target: "instrumentation", "Evaluating {path}");
+    }
+
+    fn after_script(&self, path: &str, elapsed: Duration) {
+        trace!(target: "instrumentation", "Evaluated {path} in {elapsed:?}");
+    }
+
+    fn after_job(&self, elapsed: Duration) {
+        trace!(target: "instrumentation", "Ran a microtask in {elapsed:?}");
+    }
+
+    fn after_macro_task(&self, kind: MacroTaskKind, elapsed: Duration) {
+        trace!(target: "instrumentation", "Handled a {kind:?} macro task in {elapsed:?}");
+    }
+
+    fn after_gc(&self, elapsed: Duration) {
+        trace!(target: "instrumentation", "Collected garbage in {elapsed:?}");
+    }
+}
@@ -1,15 +1,30 @@
+mod blocking_pool;
+mod error;
 mod event_loop;
 mod extension;
+mod external_memory;
 mod helper;
 mod host_data;
+mod host_fn;
+mod instrumentation;
+mod permissions;
 mod resource_table;
 mod runtime;
 mod task;
+mod test_runtime;
+pub mod webidl;
 
+pub use blocking_pool::*;
+pub use error::*;
 pub use event_loop::*;
 pub use extension::*;
+pub use external_memory::*;
 pub use helper::*;
 pub use host_data::*;
+pub use host_fn::*;
+pub use instrumentation::*;
+pub use permissions::*;
 pub use resource_table::*;
 pub use runtime::*;
 pub use task::*;
+pub use test_runtime::*;
@@ -0,0 +1,211 @@
+use std::path::{Component, Path, PathBuf};
+
+use nova_vm::ecmascript::execution::{Agent, JsResult};
+
+use crate::{ErrorClass, HostData, OpError};
+
+/// What a [Permissions] kind allows: everything, or only the listed entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Permission<T> {
+    All,
+    /// Only the listed entries, nothing when empty.
+    Only(Vec<T>),
+}
+
+impl<T> Permission<T> {
+    pub fn none() -> Self {
+        Self::Only(Vec::new())
+    }
+
+    fn allows(&self, allowed: impl Fn(&T) -> bool) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(entries) => entries.iter().any(allowed),
+        }
+    }
+}
+
+/// Network address a script is allowed to connect to. Without a port every port of the host is allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetAddress {
+    pub host: String,
+    pub port: Option<u16>,
+}
+
+impl NetAddress {
+    /// Parse a `host` or `host:port` entry, like the ones given to `--allow-net`.
+    pub fn parse(address: &str) -> Self {
+        match address.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && !host.ends_with(':') => match port.parse() {
+                Ok(port) => Self {
+                    host: host.to_ascii_lowercase(),
+                    port: Some(port),
+                },
+                Err(_) => Self::host(address),
+            },
+            _ => Self::host(address),
+        }
+    }
+
+    fn host(host: &str) -> Self {
+        Self {
+            host: host.to_ascii_lowercase(),
+            port: None,
+        }
+    }
+}
+
+/// Resources the scripts of a [Runtime](crate::Runtime) may access, kept in the ops storage and
+/// checked by the ops before touching the file system, the network or the environment.
+/// Denied accesses throw `Andromeda.errors.PermissionDenied`.
+///
+/// ```ignore
+/// Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+///     permissions.check_read(Path::new(&path))
+/// })?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permissions {
+    /// Files and directories that can be read, including their content.
+    pub read: Permission<PathBuf>,
+    /// Files and directories that can be written, including their content.
+    pub write: Permission<PathBuf>,
+    /// Hosts that can be connected to.
+    pub net: Permission<NetAddress>,
+    /// Environment variables that can be read and changed.
+    pub env: Permission<String>,
+}
+
+impl Permissions {
+    /// Allow everything, like the runtime did before permissions existed.
+    pub fn allow_all() -> Self {
+        Self {
+            read: Permission::All,
+            write: Permission::All,
+            net: Permission::All,
+            env: Permission::All,
+        }
+    }
+
+    /// Deny everything.
+    pub fn deny_all() -> Self {
+        Self {
+            read: Permission::none(),
+            write: Permission::none(),
+            net: Permission::none(),
+            env: Permission::none(),
+        }
+    }
+
+    /// Resolve the allowed paths like the checked ones, against the current directory and with
+    /// their symbolic links followed. Call it once when creating the permissions, as the checks
+    /// compare the paths as they are. Empty paths, e.g. from `--allow-read=`, allow nothing
+    /// rather than the current directory.
+    pub fn resolve_paths(mut self) -> Self {
+        for permission in [&mut self.read, &mut self.write] {
+            if let Permission::Only(paths) = permission {
+                paths.retain(|path| !path.as_os_str().is_empty());
+                for path in paths.iter_mut() {
+                    *path = resolve_path(path);
+                }
+            }
+        }
+        self
+    }
+
+    /// Run `check` against the permissions of the runtime the agent belongs to, throwing the
+    /// error of a denied access.
+    pub fn check<UserMacroTask: 'static>(
+        agent: &mut Agent,
+        check: impl FnOnce(&Self) -> Result<(), OpError>,
+    ) -> JsResult<()> {
+        HostData::<UserMacroTask>::from_agent(agent)
+            .with_storage(check)
+            .map_err(|error| error.throw::<UserMacroTask>(agent))
+    }
+
+    pub fn check_read(&self, path: &Path) -> Result<(), OpError> {
+        let path = resolve_path(path);
+        if self.read.allows(|allowed| path.starts_with(allowed)) {
+            return Ok(());
+        }
+        Err(denied(
+            format!("read access to \"{}\"", path.display()),
+            "--allow-read",
+        ))
+    }
+
+    pub fn check_write(&self, path: &Path) -> Result<(), OpError> {
+        let path = resolve_path(path);
+        if self.write.allows(|allowed| path.starts_with(allowed)) {
+            return Ok(());
+        }
+        Err(denied(
+            format!("write access to \"{}\"", path.display()),
+            "--allow-write",
+        ))
+    }
+
+    pub fn check_net(&self, host: &str, port: u16) -> Result<(), OpError> {
+        let host = host.to_ascii_lowercase();
+        if self.net.allows(|allowed| {
+            allowed.host == host && allowed.port.map_or(true, |allowed| allowed == port)
+        }) {
+            return Ok(());
+        }
+        Err(denied(
+            format!("net access to \"{host}:{port}\""),
+            "--allow-net",
+        ))
+    }
+
+    pub fn check_env(&self, key: &str) -> Result<(), OpError> {
+        if self.env.allows(|allowed| allowed == key) {
+            return Ok(());
+        }
+        Err(denied(format!("env access to \"{key}\""), "--allow-env"))
+    }
+
+    /// Check the access to every environment variable, e.g. to list them.
+    pub fn check_env_all(&self) -> Result<(), OpError> {
+        match self.env {
+            Permission::All => Ok(()),
+            Permission::Only(_) => Err(denied("env access".to_string(), "--allow-env")),
+        }
+    }
+}
+
+fn denied(access: String, flag: &str) -> OpError {
+    OpError::new(
+        ErrorClass::PermissionDenied,
+        format!("Requires {access}, run again with the {flag} flag"),
+    )
+}
+
+/// Resolve the path against the current directory, following the symbolic links of its longest
+/// existing ancestor so a link can't lead out of an allowed directory. The `.` and `..`
+/// components of the rest are resolved without touching the file system, so paths that don't
+/// exist yet can be checked too.
+fn resolve_path(path: &Path) -> PathBuf {
+    let path = match std::env::current_dir() {
+        Ok(current_dir) if path.is_relative() => current_dir.join(path),
+        _ => path.to_path_buf(),
+    };
+    let (mut resolved, rest) = path
+        .ancestors()
+        .find_map(|ancestor| {
+            let canonical = std::fs::canonicalize(ancestor).ok()?;
+            Some((canonical, path.strip_prefix(ancestor).ok()?))
+        })
+        .unwrap_or((PathBuf::new(), path.as_path()));
+    for component in rest.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+    resolved
+}
@@ -1,4 +1,8 @@
-use std::{cell::RefCell, collections::HashMap, hash::Hash};
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+    hash::Hash,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
 pub struct Rid(u32);
@@ -7,6 +11,10 @@ impl Rid {
     pub fn index(&self) -> u32 {
         self.0
     }
+
+    pub fn from_index(index: u32) -> Self {
+        Self(index)
+    }
 }
 
 pub struct ResourceTable<T> {
@@ -37,4 +45,19 @@ impl<T> ResourceTable<T> {
 
         rid
     }
+
+    /// Get a reference to the resource behind the given [Rid], if any.
+    pub fn get(&self, rid: Rid) -> Option<Ref<'_, T>> {
+        Ref::filter_map(self.table.borrow(), |table| table.get(&rid)).ok()
+    }
+
+    /// Get a mutable reference to the resource behind the given [Rid], if any.
+    pub fn get_mut(&self, rid: Rid) -> Option<RefMut<'_, T>> {
+        RefMut::filter_map(self.table.borrow_mut(), |table| table.get_mut(&rid)).ok()
+    }
+
+    /// Remove the resource behind the given [Rid] and return it, if any.
+    pub fn remove(&self, rid: Rid) -> Option<T> {
+        self.table.borrow_mut().remove(&rid)
+    }
 }
@@ -2,7 +2,10 @@ use std::{
     any::Any,
     cell::RefCell,
     collections::VecDeque,
+    convert::Infallible,
+    path::{Path, PathBuf},
     sync::{atomic::Ordering, mpsc::Receiver},
+    time::Instant,
 };
 
 use nova_vm::ecmascript::{
@@ -15,7 +18,13 @@ use nova_vm::ecmascript::{
     types::{self, Object, Value},
 };
 
-use crate::{exit_with_parse_errors, Extension, HostData, MacroTask};
+use oxc_diagnostics::OxcDiagnostic;
+use tracing::{debug, error, trace};
+
+use crate::{
+    exit_with_parse_errors, format_parse_errors, order_extensions, Extension, ExtensionError,
+    HostData, Instrumentation, MacroTask, MacroTaskKind, OpInfo, OpRegistry, Permissions,
+};
 
 pub struct RuntimeHostHooks<UserMacroTask> {
     pub(crate) promise_job_queue: RefCell<VecDeque<Job>>,
@@ -55,12 +64,14 @@ impl<UserMacroTask: 'static> HostHooks for RuntimeHostHooks<UserMacroTask> {
     }
 }
 
+/// Handler of the user macro tasks. Exceptions thrown by the JavaScript it runs, e.g. by a timer
+/// callback, are returned so the embedder decides what to do with them, see [Runtime::run_event_loop].
 pub type EventLoopHandler<UserMacroTask> = fn(
     macro_task: UserMacroTask,
     agent: &mut GcAgent,
     realm_root: &RealmRoot,
     host_data: &HostData<UserMacroTask>,
-);
+) -> JsResult<()>;
 
 pub struct RuntimeConfig<UserMacroTask: 'static> {
     /// Disable or not strict mode.
@@ -69,12 +80,36 @@ pub struct RuntimeConfig<UserMacroTask: 'static> {
     pub paths: Vec<String>,
     /// Enable or not verbose outputs.
     pub verbose: bool,
-    /// Collection of Rust Extensions
+    /// Collection of Rust Extensions, loaded after their dependencies
     pub extensions: Vec<Extension>,
     /// Collection of builtin js sources
     pub builtins: Vec<&'static str>,
     /// User event loop handler.
     pub eventloop_handler: EventLoopHandler<UserMacroTask>,
+    /// Expose introspection APIs meant for tooling, such as the list of ops, to the scripts.
+    pub expose_internals: bool,
+    /// Hooks fired around scripts, jobs, macro tasks and garbage collections.
+    pub instrumentation: Option<Box<dyn Instrumentation>>,
+    /// Resources the scripts may access, checked by the ops.
+    pub permissions: Permissions,
+}
+
+/// Present in the ops storage when [RuntimeConfig::expose_internals] is enabled.
+pub struct ExposeInternals;
+
+/// Absolute path of the first script run by the [Runtime], kept in the ops storage.
+pub struct MainModule(pub PathBuf);
+
+impl MainModule {
+    fn new(path: &str) -> Self {
+        let path = Path::new(path);
+        let path = std::fs::canonicalize(path).unwrap_or_else(|_| {
+            std::env::current_dir()
+                .map(|current_dir| current_dir.join(path))
+                .unwrap_or_else(|_| path.to_path_buf())
+        });
+        Self(path)
+    }
 }
 
 pub struct Runtime<UserMacroTask: 'static> {
@@ -87,8 +122,20 @@ pub struct Runtime<UserMacroTask: 'static> {
 
 impl<UserMacroTask> Runtime<UserMacroTask> {
     /// Create a new [Runtime] given a [RuntimeConfig]. Use [Runtime::run] to run it.
-    pub fn new(mut config: RuntimeConfig<UserMacroTask>) -> Self {
+    /// Fails if the extensions have missing or cyclic dependencies, see [order_extensions], or if
+    /// two of their ops share a name.
+    pub fn new(mut config: RuntimeConfig<UserMacroTask>) -> Result<Self, ExtensionError> {
+        config.extensions = order_extensions(std::mem::take(&mut config.extensions))?;
+        let op_registry = OpRegistry::new(&config.extensions)?;
         let (host_data, macro_task_rx) = HostData::new();
+        host_data.insert_storage(op_registry);
+        if let Some(path) = config.paths.first() {
+            host_data.insert_storage(MainModule::new(path));
+        }
+        if config.expose_internals {
+            host_data.insert_storage(ExposeInternals);
+        }
+        host_data.insert_storage(config.permissions.clone());
         let host_hooks = RuntimeHostHooks::new(host_data);
 
         let host_hooks: &RuntimeHostHooks<UserMacroTask> = &*Box::leak(Box::new(host_hooks));
@@ -111,18 +158,42 @@ impl<UserMacroTask> Runtime<UserMacroTask> {
             }),
         );
 
-        Self {
+        Ok(Self {
             config,
             agent,
             realm_root,
             host_hooks,
             macro_task_rx,
-        }
+        })
+    }
+
+    /// Metadata of every op registered by the extensions.
+    pub fn ops(&self) -> Vec<OpInfo> {
+        self.host_hooks
+            .host_data
+            .try_with_storage(|registry: &OpRegistry| registry.ops().to_vec())
+            .unwrap_or_default()
     }
 
     /// Run the Runtime with the specified configuration.
     pub fn run(&mut self) -> JsResult<Value> {
-        // Load the builtins js sources
+        self.load_builtins();
+
+        let mut final_result = Value::Null;
+
+        // Fetch the runtime mod.ts file using a macro and add it to the paths
+        for path in self.config.paths.clone() {
+            let file = std::fs::read_to_string(&path).unwrap();
+            final_result = self.eval_script(&path, file)?;
+        }
+
+        self.run_event_loop()?;
+
+        Ok(final_result)
+    }
+
+    /// Load the builtins js sources.
+    pub fn load_builtins(&mut self) {
         self.agent.run_in_realm(&self.realm_root, |agent| {
             let realm = agent.current_realm_id();
 
@@ -137,54 +208,184 @@ impl<UserMacroTask> Runtime<UserMacroTask> {
                     };
                 match script_evaluation(agent, script) {
                     Ok(_) => (),
-                    Err(_) => println!("Error in runtime"),
+                    Err(_) => error!(target: "runtime", "Error in runtime: a builtin threw"),
                 }
             }
         });
+    }
 
-        let mut final_result = Value::Null;
-
-        // Fetch the runtime mod.ts file using a macro and add it to the paths
-        for path in &self.config.paths {
-            let file = std::fs::read_to_string(path).unwrap();
+    /// Parse and evaluate a script, returning its completion value.
+    /// Exits the process if the script has parse errors.
+    pub fn eval_script(&mut self, path: &str, source: String) -> JsResult<Value> {
+        let result = self.eval_script_with(path, source, |errors, source_text| -> Infallible {
+            exit_with_parse_errors(errors, path, source_text)
+        });
+        match result {
+            Ok(result) => result,
+            Err(never) => match never {},
+        }
+    }
 
-            final_result = self.agent.run_in_realm(&self.realm_root, |agent| {
-                let source_text = types::String::from_string(agent, file);
-                let realm = agent.current_realm_id();
+    /// Parse and evaluate a script like [Runtime::eval_script], but return its parse errors
+    /// rendered by [format_parse_errors](crate::format_parse_errors) instead of exiting.
+    pub fn try_eval_script(
+        &mut self,
+        path: &str,
+        source: String,
+    ) -> Result<JsResult<Value>, String> {
+        self.eval_script_with(path, source, |errors, source_text| {
+            format_parse_errors(errors, path, source_text)
+        })
+    }
 
-                let script =
-                    match parse_script(agent, source_text, realm, !self.config.no_strict, None) {
-                        Ok(script) => script,
-                        Err(errors) => {
-                            exit_with_parse_errors(errors, path, source_text.as_str(agent))
-                        }
-                    };
+    fn eval_script_with<E>(
+        &mut self,
+        path: &str,
+        source: String,
+        on_parse_errors: impl FnOnce(Vec<OxcDiagnostic>, &str) -> E,
+    ) -> Result<JsResult<Value>, E> {
+        debug!(target: "runtime", "Evaluating {path}");
+        if let Some(instrumentation) = &self.config.instrumentation {
+            instrumentation.before_script(path);
+        }
+        let start = Instant::now();
+        let result = self.agent.run_in_realm(&self.realm_root, |agent| {
+            let source_text = types::String::from_string(agent, source);
+            let realm = agent.current_realm_id();
 
-                script_evaluation(agent, script)
-            })?;
+            match parse_script(agent, source_text, realm, !self.config.no_strict, None) {
+                Ok(script) => Ok(script_evaluation(agent, script)),
+                Err(errors) => Err(on_parse_errors(errors, source_text.as_str(agent))),
+            }
+        });
+        if let Some(instrumentation) = &self.config.instrumentation {
+            instrumentation.after_script(path, start.elapsed());
         }
+        result
+    }
 
-        loop {
-            while let Some(job) = self.host_hooks.pop_promise_job() {
-                self.agent
-                    .run_in_realm(&self.realm_root, |agent| job.run(agent))?;
+    /// Run every job of the microtasks (promise jobs) queue.
+    pub fn run_microtasks(&mut self) -> JsResult<()> {
+        while let Some(job) = self.host_hooks.pop_promise_job() {
+            if let Some(instrumentation) = &self.config.instrumentation {
+                instrumentation.before_job();
+            }
+            let start = Instant::now();
+            let result = self
+                .agent
+                .run_in_realm(&self.realm_root, |agent| job.run(agent));
+            if let Some(instrumentation) = &self.config.instrumentation {
+                instrumentation.after_job(start.elapsed());
             }
+            result?;
+        }
+        Ok(())
+    }
 
-            // If both the microtasks and macrotasks queues are empty we can end the event loop
-            if !self.host_hooks.any_pending_macro_tasks() {
-                break;
+    /// Whether there are macro tasks that haven't been handled yet.
+    pub fn any_pending_macro_tasks(&self) -> bool {
+        self.host_hooks.any_pending_macro_tasks()
+    }
+
+    /// Whether there are macro tasks or immediates that haven't been handled yet.
+    pub fn any_pending_tasks(&self) -> bool {
+        self.any_pending_macro_tasks() || self.host_hooks.host_data.any_pending_immediates()
+    }
+
+    /// Run the event loop until the microtasks, immediates and macrotasks queues are empty.
+    ///
+    /// Every turn of the loop runs, in order:
+    /// 1. the microtasks (promise jobs),
+    /// 2. the immediates queued so far, each followed by the microtasks it queued,
+    /// 3. a single macro task, e.g. a timer or an I/O completion.
+    ///
+    /// The loop only blocks waiting for a macro task when no immediates are pending. It stops at
+    /// the first uncaught exception, whether thrown by a microtask or by a macro task.
+    pub fn run_event_loop(&mut self) -> JsResult<()> {
+        while self.run_event_loop_turn()? {}
+
+        Ok(())
+    }
+
+    /// Run a single turn of the event loop, see [Runtime::run_event_loop].
+    /// Returns `Ok(false)` without blocking when there is nothing left to run.
+    pub fn run_event_loop_turn(&mut self) -> JsResult<bool> {
+        self.run_microtasks()?;
+        self.run_immediates()?;
+
+        // If the microtasks, immediates and macrotasks queues are empty we can end the event loop
+        if !self.any_pending_tasks() {
+            return Ok(false);
+        }
+
+        // The garbage collector can't see the memory held by the ops, so collect when it grows.
+        if self.host_hooks.host_data.external_memory.needs_gc() {
+            self.gc();
+        }
+
+        if self.host_hooks.host_data.any_pending_immediates() {
+            // Don't wait for macro tasks while immediates are ready to run.
+            if let Ok(macro_task) = self.macro_task_rx.try_recv() {
+                self.run_macro_task(macro_task)?;
             }
+        } else {
+            self.handle_macro_task()?;
+        }
+
+        Ok(true)
+    }
 
-            self.handle_macro_task();
+    /// Run the immediates phase: every immediate queued before it started, followed by a
+    /// microtasks checkpoint each.
+    pub fn run_immediates(&mut self) -> JsResult<()> {
+        let count = self.host_hooks.host_data.immediates.borrow().len();
+        for _ in 0..count {
+            let Some(macro_task) = self
+                .host_hooks
+                .host_data
+                .immediates
+                .borrow_mut()
+                .pop_front()
+            else {
+                break;
+            };
+            self.run_macro_task(MacroTask::User(macro_task))?;
+            self.run_microtasks()?;
         }
+        Ok(())
+    }
 
-        Ok(final_result)
+    /// Run a garbage collection, firing the instrumentation hooks around it.
+    pub fn gc(&mut self) {
+        if let Some(instrumentation) = &self.config.instrumentation {
+            instrumentation.before_gc();
+        }
+        let start = Instant::now();
+        self.agent.gc();
+        self.host_hooks.host_data.external_memory.reset_after_gc();
+        if let Some(instrumentation) = &self.config.instrumentation {
+            instrumentation.after_gc(start.elapsed());
+        }
     }
 
     // Listen for pending macro tasks and resolve one by one
-    pub fn handle_macro_task(&mut self) {
-        match self.macro_task_rx.recv() {
-            Ok(MacroTask::ResolvePromise(root_value)) => {
+    pub fn handle_macro_task(&mut self) -> JsResult<()> {
+        let Ok(macro_task) = self.macro_task_rx.recv() else {
+            return Ok(());
+        };
+        self.run_macro_task(macro_task)
+    }
+
+    fn run_macro_task(&mut self, macro_task: MacroTask<UserMacroTask>) -> JsResult<()> {
+        let kind = MacroTaskKind::from(&macro_task);
+        if let Some(instrumentation) = &self.config.instrumentation {
+            instrumentation.before_macro_task(kind);
+        }
+        let start = Instant::now();
+
+        let result = match macro_task {
+            MacroTask::ResolvePromise(root_value) => {
+                trace!(target: "event_loop", "Resolving a promise");
                 self.agent.run_in_realm(&self.realm_root, |agent| {
                     let value = root_value.take(agent);
                     if let Value::Promise(promise) = value {
@@ -194,17 +395,64 @@ impl<UserMacroTask> Runtime<UserMacroTask> {
                         panic!("Attempted to resolve a non-promise value");
                     }
                 });
+                Ok(())
+            }
+            MacroTask::ResolvePromiseWithString(root_value, string) => {
+                trace!(target: "event_loop", "Resolving a promise with a string");
+                self.agent.run_in_realm(&self.realm_root, |agent| {
+                    let value = root_value.take(agent);
+                    if let Value::Promise(promise) = value {
+                        let promise_capability = PromiseCapability::from_promise(promise, false);
+                        let string = Value::from_string(agent, string);
+                        promise_capability.resolve(agent, string);
+                    } else {
+                        panic!("Attempted to resolve a non-promise value");
+                    }
+                });
+                Ok(())
+            }
+            MacroTask::ResolvePromiseWithBoolean(root_value, boolean) => {
+                trace!(target: "event_loop", "Resolving a promise with {boolean}");
+                self.agent.run_in_realm(&self.realm_root, |agent| {
+                    let value = root_value.take(agent);
+                    if let Value::Promise(promise) = value {
+                        let promise_capability = PromiseCapability::from_promise(promise, false);
+                        promise_capability.resolve(agent, Value::Boolean(boolean));
+                    } else {
+                        panic!("Attempted to resolve a non-promise value");
+                    }
+                });
+                Ok(())
+            }
+            MacroTask::RejectPromise(root_value, error) => {
+                trace!(target: "event_loop", "Rejecting a promise with {error}");
+                self.agent.run_in_realm(&self.realm_root, |agent| {
+                    let value = root_value.take(agent);
+                    if let Value::Promise(promise) = value {
+                        let promise_capability = PromiseCapability::from_promise(promise, false);
+                        let error = error.into_value::<UserMacroTask>(agent);
+                        promise_capability.reject(agent, error);
+                    } else {
+                        panic!("Attempted to reject a non-promise value");
+                    }
+                });
+                Ok(())
             }
             // Let the user runtime handle its macro tasks
-            Ok(MacroTask::User(e)) => {
+            MacroTask::User(e) => {
+                trace!(target: "event_loop", "Handling a user macro task");
                 (self.config.eventloop_handler)(
                     e,
                     &mut self.agent,
                     &self.realm_root,
                     &self.host_hooks.host_data,
-                );
+                )
             }
-            _ => {}
+        };
+
+        if let Some(instrumentation) = &self.config.instrumentation {
+            instrumentation.after_macro_task(kind, start.elapsed());
         }
+        result
     }
 }
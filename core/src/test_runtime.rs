@@ -0,0 +1,124 @@
+use std::sync::atomic::Ordering;
+
+use nova_vm::ecmascript::{execution::agent::JsError, types::Value};
+
+use crate::{EventLoopHandler, Extension, HostData, Permissions, Runtime, RuntimeConfig};
+
+/// Harness to test extensions: evaluate JavaScript snippets, pump the event loop
+/// deterministically one macro task at a time and assert on the results and resources.
+///
+/// It owns the Tokio runtime the macro tasks are spawned in, so it can be used from
+/// a plain `#[test]` function.
+pub struct TestRuntime<UserMacroTask: 'static> {
+    pub runtime: Runtime<UserMacroTask>,
+    tokio_runtime: tokio::runtime::Runtime,
+}
+
+impl<UserMacroTask> TestRuntime<UserMacroTask> {
+    /// Create a [TestRuntime] with the given extensions and builtins already loaded.
+    ///
+    /// # Panics
+    ///
+    /// If the extensions have missing or cyclic dependencies, see [order_extensions](crate::order_extensions).
+    pub fn new(
+        extensions: Vec<Extension>,
+        builtins: Vec<&'static str>,
+        eventloop_handler: EventLoopHandler<UserMacroTask>,
+    ) -> Self {
+        Self::with_permissions(
+            extensions,
+            builtins,
+            eventloop_handler,
+            Permissions::allow_all(),
+        )
+    }
+
+    /// Like [TestRuntime::new], but the scripts only get the given permissions instead of
+    /// every access.
+    ///
+    /// # Panics
+    ///
+    /// If the extensions have missing or cyclic dependencies, see [order_extensions](crate::order_extensions).
+    pub fn with_permissions(
+        extensions: Vec<Extension>,
+        builtins: Vec<&'static str>,
+        eventloop_handler: EventLoopHandler<UserMacroTask>,
+        permissions: Permissions,
+    ) -> Self {
+        // Macro tasks run in a worker thread while the test thread blocks on the event loop.
+        let tokio_runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_io()
+            .enable_time()
+            .build()
+            .unwrap();
+        let _guard = tokio_runtime.enter();
+
+        let mut runtime = Runtime::new(RuntimeConfig {
+            no_strict: false,
+            paths: vec![],
+            verbose: false,
+            extensions,
+            builtins,
+            eventloop_handler,
+            expose_internals: true,
+            instrumentation: None,
+            permissions,
+        })
+        .unwrap_or_else(|error| panic!("{error}"));
+        runtime.load_builtins();
+
+        Self {
+            runtime,
+            tokio_runtime,
+        }
+    }
+
+    /// Evaluate a snippet and return the string representation of its completion value,
+    /// or of the thrown value as the error. A snippet with syntax errors isn't run and its
+    /// rendered parse errors are returned instead. The event loop is not pumped.
+    pub fn eval(&mut self, source: &str) -> Result<String, String> {
+        let _guard = self.tokio_runtime.enter();
+        match self.runtime.try_eval_script("<test>", source.to_string())? {
+            Ok(value) => Ok(self.value_to_string(value)),
+            Err(error) => Err(self.error_to_string(error)),
+        }
+    }
+
+    /// Run every pending microtask and immediate, then wait for and handle a single macro task.
+    /// Returns `Ok(false)` without blocking when there are no more tasks pending.
+    pub fn tick(&mut self) -> Result<bool, String> {
+        let _guard = self.tokio_runtime.enter();
+        self.runtime
+            .run_event_loop_turn()
+            .map_err(|error| self.error_to_string(error))
+    }
+
+    /// Pump the event loop until both the microtasks and macrotasks queues are empty.
+    pub fn run_event_loop(&mut self) -> Result<(), String> {
+        while self.tick()? {}
+        Ok(())
+    }
+
+    /// Number of macro tasks that haven't been handled yet.
+    pub fn pending_macro_tasks(&self) -> u32 {
+        self.host_data().macro_task_count.load(Ordering::Relaxed)
+    }
+
+    /// The [HostData] of the runtime, to inspect the extensions' storage and resources.
+    pub fn host_data(&self) -> &HostData<UserMacroTask> {
+        &self.runtime.host_hooks.host_data
+    }
+
+    fn value_to_string(&mut self, value: Value) -> String {
+        self.runtime
+            .agent
+            .run_in_realm(&self.runtime.realm_root, |agent| {
+                value.string_repr(agent).as_str(agent).to_string()
+            })
+    }
+
+    fn error_to_string(&mut self, error: JsError) -> String {
+        self.value_to_string(error.value())
+    }
+}
@@ -0,0 +1,335 @@
+//! Conversions of the arguments of ops, following the WebIDL rules for JavaScript values.
+//! See <https://webidl.spec.whatwg.org/#js-type-mapping>.
+//!
+//! Every conversion takes a `context`, such as `"Argument 2 of setTimeout"`, used in the
+//! messages of the thrown `TypeError`s.
+
+use nova_vm::ecmascript::{
+    execution::{
+        agent::{ExceptionType, JsError},
+        Agent, JsResult,
+    },
+    types::{Function, InternalMethods, IntoValue, Object, PropertyKey, Value},
+};
+
+/// Largest integer a double represents exactly, the range of the 64-bit WebIDL types.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// Extended attribute changing how out of range values are converted to integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntegerConversion {
+    /// No attribute: NaN and infinities become 0, other values wrap around.
+    #[default]
+    Modulo,
+    /// `[EnforceRange]`: NaN, infinities and out of range values throw a `TypeError`.
+    EnforceRange,
+    /// `[Clamp]`: NaN becomes 0, other values are clamped to the range and rounded.
+    Clamp,
+}
+
+/// Convert to a WebIDL `long`.
+pub fn to_long(
+    agent: &mut Agent,
+    value: Value,
+    conversion: IntegerConversion,
+    context: &str,
+) -> JsResult<i32> {
+    convert_to_int(agent, value, 32, true, conversion, context).map(|x| x as i32)
+}
+
+/// Convert to a WebIDL `unsigned long`.
+pub fn to_unsigned_long(
+    agent: &mut Agent,
+    value: Value,
+    conversion: IntegerConversion,
+    context: &str,
+) -> JsResult<u32> {
+    convert_to_int(agent, value, 32, false, conversion, context).map(|x| x as u32)
+}
+
+/// Convert to a WebIDL `long long`.
+pub fn to_long_long(
+    agent: &mut Agent,
+    value: Value,
+    conversion: IntegerConversion,
+    context: &str,
+) -> JsResult<i64> {
+    convert_to_int(agent, value, 64, true, conversion, context).map(|x| x as i64)
+}
+
+/// Convert to a WebIDL `unsigned long long`.
+pub fn to_unsigned_long_long(
+    agent: &mut Agent,
+    value: Value,
+    conversion: IntegerConversion,
+    context: &str,
+) -> JsResult<u64> {
+    convert_to_int(agent, value, 64, false, conversion, context).map(|x| x as u64)
+}
+
+/// Convert to a WebIDL `double`, which unlike `unrestricted double` throws for NaN and infinities.
+pub fn to_double(agent: &mut Agent, value: Value, context: &str) -> JsResult<f64> {
+    let x = value.to_number(agent)?.into_f64(agent);
+    if !x.is_finite() {
+        return Err(type_error(
+            agent,
+            format!("{context} is not a finite number"),
+        ));
+    }
+    Ok(x)
+}
+
+/// Convert to one of the `values` of a WebIDL enumeration.
+pub fn to_enum<'a>(
+    agent: &mut Agent,
+    value: Value,
+    values: &[&'a str],
+    context: &str,
+) -> JsResult<&'a str> {
+    let string = value.to_string(agent)?;
+    let string = string.as_str(agent);
+    match values.iter().find(|value| **value == string) {
+        Some(value) => Ok(value),
+        None => {
+            let message = format!(
+                "{context} is not one of {}, got \"{string}\"",
+                values
+                    .iter()
+                    .map(|value| format!("\"{value}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            Err(type_error(agent, message))
+        }
+    }
+}
+
+/// Convert to a WebIDL callback function, throwing if the value is not callable.
+pub fn to_callback_function(agent: &mut Agent, value: Value, context: &str) -> JsResult<Function> {
+    Function::try_from(value).map_err(|_| type_error(agent, format!("{context} is not a function")))
+}
+
+/// WebIDL dictionary passed to an op, e.g. its options.
+pub struct Dictionary(Option<Object>);
+
+impl Dictionary {
+    /// `undefined` and `null` convert to an empty dictionary, other non-objects throw a `TypeError`.
+    pub fn new(agent: &mut Agent, value: Value, context: &str) -> JsResult<Self> {
+        match value {
+            Value::Undefined | Value::Null => Ok(Self(None)),
+            value => match Object::try_from(value) {
+                Ok(object) => Ok(Self(Some(object))),
+                Err(_) => Err(type_error(agent, format!("{context} is not an object"))),
+            },
+        }
+    }
+
+    /// Get the value of a member, `undefined` if it isn't present.
+    pub fn get(&self, agent: &mut Agent, member: &'static str) -> JsResult<Value> {
+        match self.0 {
+            Some(object) => {
+                let property_key = PropertyKey::from_static_str(agent, member);
+                object.internal_get(agent, property_key, object.into_value())
+            }
+            None => Ok(Value::Undefined),
+        }
+    }
+}
+
+/// ConvertToInt, see <https://webidl.spec.whatwg.org/#abstract-opdef-converttoint>.
+fn convert_to_int(
+    agent: &mut Agent,
+    value: Value,
+    bit_length: i32,
+    signed: bool,
+    conversion: IntegerConversion,
+    context: &str,
+) -> JsResult<f64> {
+    let x = value.to_number(agent)?.into_f64(agent);
+    number_to_int(x, bit_length, signed, conversion).map_err(|error| {
+        let message = match error {
+            IntegerError::NotFinite => format!("{context} is not a finite number"),
+            IntegerError::OutOfRange { lower, upper } => {
+                format!("{context} is outside of the range {lower} to {upper}")
+            }
+        };
+        type_error(agent, message)
+    })
+}
+
+/// Why [IntegerConversion::EnforceRange] rejected a number.
+#[derive(Debug, PartialEq)]
+enum IntegerError {
+    NotFinite,
+    OutOfRange { lower: f64, upper: f64 },
+}
+
+/// The steps of [convert_to_int] after the conversion to a number.
+fn number_to_int(
+    x: f64,
+    bit_length: i32,
+    signed: bool,
+    conversion: IntegerConversion,
+) -> Result<f64, IntegerError> {
+    let (lower, upper) = match (bit_length, signed) {
+        (64, true) => (-MAX_SAFE_INTEGER, MAX_SAFE_INTEGER),
+        (64, false) => (0.0, MAX_SAFE_INTEGER),
+        (_, true) => (
+            -(2f64.powi(bit_length - 1)),
+            2f64.powi(bit_length - 1) - 1.0,
+        ),
+        (_, false) => (0.0, 2f64.powi(bit_length) - 1.0),
+    };
+
+    // Adding 0 turns -0 into +0.
+    let x = x + 0.0;
+
+    match conversion {
+        IntegerConversion::EnforceRange => {
+            if !x.is_finite() {
+                return Err(IntegerError::NotFinite);
+            }
+            let x = x.trunc() + 0.0;
+            if x < lower || x > upper {
+                return Err(IntegerError::OutOfRange { lower, upper });
+            }
+            Ok(x)
+        }
+        IntegerConversion::Clamp => {
+            if x.is_nan() {
+                return Ok(0.0);
+            }
+            Ok(x.clamp(lower, upper).round_ties_even() + 0.0)
+        }
+        IntegerConversion::Modulo => {
+            if !x.is_finite() {
+                return Ok(0.0);
+            }
+            let x = x.trunc() + 0.0;
+            if lower <= x && x <= upper {
+                return Ok(x);
+            }
+            let modulus = 2f64.powi(bit_length);
+            // The remainder of a negative multiple of the modulus is -0.
+            let x = x.rem_euclid(modulus) + 0.0;
+            if signed && x >= modulus / 2.0 {
+                Ok(x - modulus)
+            } else {
+                Ok(x)
+            }
+        }
+    }
+}
+
+fn type_error(agent: &mut Agent, message: String) -> JsError {
+    agent.throw_exception(ExceptionType::TypeError, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{number_to_int, IntegerConversion::*, IntegerError, MAX_SAFE_INTEGER};
+
+    /// Whether the result is exactly `expected`, telling +0 and -0 apart.
+    fn assert_int(actual: Result<f64, IntegerError>, expected: f64) {
+        let actual = actual.unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(actual.is_sign_negative(), expected.is_sign_negative());
+    }
+
+    #[test]
+    fn negative_zero_becomes_positive_zero() {
+        for conversion in [Modulo, Clamp, EnforceRange] {
+            assert_int(number_to_int(-0.0, 32, true, conversion), 0.0);
+            assert_int(number_to_int(-0.4, 8, false, conversion), 0.0);
+        }
+    }
+
+    #[test]
+    fn nan_and_infinities() {
+        assert_int(number_to_int(f64::NAN, 32, true, Modulo), 0.0);
+        assert_int(number_to_int(f64::INFINITY, 32, true, Modulo), 0.0);
+        assert_int(number_to_int(f64::NEG_INFINITY, 32, true, Modulo), 0.0);
+
+        assert_int(number_to_int(f64::NAN, 8, false, Clamp), 0.0);
+        assert_int(number_to_int(f64::INFINITY, 8, false, Clamp), 255.0);
+        assert_int(number_to_int(f64::NEG_INFINITY, 8, true, Clamp), -128.0);
+
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                number_to_int(x, 64, true, EnforceRange),
+                Err(IntegerError::NotFinite)
+            );
+        }
+    }
+
+    #[test]
+    fn modulo_wraps_around() {
+        assert_int(number_to_int(256.0, 8, false, Modulo), 0.0);
+        assert_int(number_to_int(257.0, 8, false, Modulo), 1.0);
+        assert_int(number_to_int(-1.0, 8, false, Modulo), 255.0);
+        assert_int(number_to_int(-1.0, 32, false, Modulo), 4294967295.0);
+
+        assert_int(number_to_int(128.0, 8, true, Modulo), -128.0);
+        assert_int(number_to_int(255.0, 8, true, Modulo), -1.0);
+        assert_int(number_to_int(-129.0, 8, true, Modulo), 127.0);
+        assert_int(
+            number_to_int(2f64.powi(31), 32, true, Modulo),
+            -(2f64.powi(31)),
+        );
+
+        assert_int(number_to_int(2.9, 32, true, Modulo), 2.0);
+        assert_int(number_to_int(-2.9, 32, true, Modulo), -2.0);
+        assert_int(number_to_int(-256.0, 8, false, Modulo), 0.0);
+    }
+
+    #[test]
+    fn clamp_rounds_halves_to_even() {
+        assert_int(number_to_int(0.5, 32, true, Clamp), 0.0);
+        assert_int(number_to_int(1.5, 32, true, Clamp), 2.0);
+        assert_int(number_to_int(2.5, 32, true, Clamp), 2.0);
+        assert_int(number_to_int(-2.5, 32, true, Clamp), -2.0);
+        assert_int(number_to_int(-0.5, 32, true, Clamp), 0.0);
+        assert_int(number_to_int(2.6, 32, true, Clamp), 3.0);
+
+        assert_int(number_to_int(300.0, 8, false, Clamp), 255.0);
+        assert_int(number_to_int(-5.0, 8, false, Clamp), 0.0);
+        assert_int(number_to_int(254.5, 8, false, Clamp), 254.0);
+    }
+
+    #[test]
+    fn enforce_range_bounds_of_long_long() {
+        assert_int(
+            number_to_int(MAX_SAFE_INTEGER, 64, true, EnforceRange),
+            MAX_SAFE_INTEGER,
+        );
+        assert_int(
+            number_to_int(-MAX_SAFE_INTEGER, 64, true, EnforceRange),
+            -MAX_SAFE_INTEGER,
+        );
+        assert_int(number_to_int(1.9, 64, true, EnforceRange), 1.0);
+        assert_int(number_to_int(-1.9, 64, true, EnforceRange), -1.0);
+
+        let out_of_range = Err(IntegerError::OutOfRange {
+            lower: -MAX_SAFE_INTEGER,
+            upper: MAX_SAFE_INTEGER,
+        });
+        assert_eq!(
+            number_to_int(MAX_SAFE_INTEGER + 1.0, 64, true, EnforceRange),
+            out_of_range
+        );
+        assert_eq!(
+            number_to_int(-MAX_SAFE_INTEGER - 1.0, 64, true, EnforceRange),
+            out_of_range
+        );
+
+        assert_int(number_to_int(0.0, 64, false, EnforceRange), 0.0);
+        assert_int(number_to_int(-0.9, 64, false, EnforceRange), 0.0);
+        assert_eq!(
+            number_to_int(-1.0, 64, false, EnforceRange),
+            Err(IntegerError::OutOfRange {
+                lower: 0.0,
+                upper: MAX_SAFE_INTEGER,
+            })
+        );
+    }
+}
@@ -0,0 +1,17 @@
+/// <reference path="../types/global.d.ts" />
+
+const controller = new AbortController();
+controller.signal.addEventListener("abort", () => {
+  console.log("aborted:", controller.signal.reason);
+});
+
+Andromeda.sleep(5000, { signal: controller.signal })
+  .catch((reason) => console.log(`sleep rejected: ${reason}`));
+controller.abort("no longer needed");
+
+const signal = AbortSignal.any([
+  new AbortController().signal,
+  AbortSignal.timeout(500),
+]);
+Andromeda.sleep(2000, { signal })
+  .catch((error) => console.log(`${error.name}: ${error.message}`));
@@ -0,0 +1,21 @@
+/// <reference path="../types/global.d.ts" />
+
+let start: number | undefined;
+
+function step(timestamp: number) {
+  if (start === undefined) {
+    start = timestamp;
+  }
+  const elapsed = timestamp - start;
+  const x = Math.min(Math.floor(elapsed / 10), 40);
+  console.log(`${" ".repeat(x)}*`);
+  if (x < 40) {
+    requestAnimationFrame(step);
+  }
+}
+
+requestAnimationFrame(step);
+
+cancelAnimationFrame(requestAnimationFrame(() => {
+  console.log("I'll never run :)");
+}));
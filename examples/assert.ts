@@ -0,0 +1,21 @@
+/// <reference path="../types/global.d.ts" />
+
+assert(1 + 1 === 2);
+assertEquals(
+  { name: "andromeda", tags: new Set(["js", "ts"]) },
+  { name: "andromeda", tags: new Set(["ts", "js"]) },
+);
+assertNotEquals([1, 2, 3], [1, 2]);
+assertMatch("andromeda v0.1.0", /v\d+\.\d+\.\d+/);
+
+const error = assertThrows(() => JSON.parse("{"), SyntaxError);
+console.log(`caught ${error.name}`);
+
+assertRejects(() => Andromeda.readLink("missing"), Andromeda.errors.NotFound)
+  .then(() => console.log("readLink rejected with NotFound"));
+
+try {
+  assertEquals({ a: 1, b: [1, 2] }, { a: 1, b: [1, 3] });
+} catch (error) {
+  console.log((error as Error).message);
+}
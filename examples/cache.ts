@@ -0,0 +1,26 @@
+/// <reference path="../types/global.d.ts" />
+
+let lookups = 0;
+const slowSquare = (n: number) =>
+  new Promise<number>((resolve) => {
+    lookups++;
+    setTimeout(() => resolve(n * n), 50);
+  });
+
+const square = Andromeda.memoize(slowSquare, { capacity: 100, ttl: 1_000 });
+
+async function main() {
+  // The three concurrent calls share one computation.
+  const results = await Promise.all([square(12), square(12), square(12)]);
+  console.log(results, `computed ${lookups} time(s)`);
+
+  const sessions = Andromeda.caches.memory<string>({ capacity: 2 });
+  sessions.set("a", "ada");
+  sessions.set("b", "grace");
+  sessions.get("a");
+  sessions.set("c", "linus");
+  console.log(sessions.has("a"), sessions.has("b"), sessions.size);
+  sessions.close();
+}
+
+main();
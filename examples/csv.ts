@@ -0,0 +1,16 @@
+/// <reference path="../types/global.d.ts" />
+
+const text = `name,language,year
+Ada,"Analytical Engine, notes",1843
+Grace,COBOL,1959`;
+
+const people = Andromeda.csv.parse(text, { header: true, cast: true }) as {
+  name: string;
+  language: string;
+  year: number;
+}[];
+for (const person of people) {
+  console.log(`${person.name} (${person.year}): ${person.language}`);
+}
+
+console.log(Andromeda.csv.stringify(people, { delimiter: "\t" }));
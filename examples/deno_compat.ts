@@ -0,0 +1,17 @@
+/// <reference path="../types/global.d.ts" />
+/// <reference path="../types/deno.d.ts" />
+
+// Run with `andromeda run --deno-compat examples/deno_compat.ts`.
+
+console.log(`args: ${JSON.stringify(Deno.args)}`);
+console.log(`HOME is set: ${Deno.env.has("HOME")}`);
+
+Deno.writeTextFile("deno_compat.txt", "Hello from Deno code!")
+  .then(() => Deno.readTextFile("deno_compat.txt"))
+  .then((text) => console.log(text))
+  .then(() => Deno.readTextFile("missing.txt"))
+  .catch((error) => {
+    if (error instanceof Deno.errors.NotFound) {
+      console.log("missing.txt does not exist");
+    }
+  });
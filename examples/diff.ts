@@ -0,0 +1,18 @@
+/// <reference path="../types/global.d.ts" />
+
+const before = "one\ntwo\nthree\nfour\n";
+const after = "one\n2\nthree\nfour\nfive\n";
+
+for (const change of Andromeda.diff.lines(before, after)) {
+  const prefix = { equal: " ", delete: "-", insert: "+" }[change.type];
+  console.log(`${prefix} ${JSON.stringify(change.value)}`);
+}
+
+const patch = Andromeda.diff.unified(before, after, {
+  oldName: "numbers.txt",
+  newName: "numbers.txt",
+});
+console.log(patch);
+assert(Andromeda.diff.apply(before, patch) === after, "The patch applies");
+
+console.log(JSON.stringify(Andromeda.diff.chars("kitten", "sitting")));
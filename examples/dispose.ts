@@ -0,0 +1,17 @@
+/// <reference path="../types/global.d.ts" />
+
+function countWords(text: string): number {
+  const stack = new DisposableStack();
+  try {
+    const words = stack.use(Andromeda.regex.compile("\\w+"));
+    const spinner = stack.use(Andromeda.prompts.spinner("Counting words"));
+    const count = words.findAll(text).length;
+    spinner.stop(`Counted ${count} words`);
+    return count;
+  } finally {
+    // Closes the pattern, and would stop the spinner if counting had thrown.
+    stack.dispose();
+  }
+}
+
+console.log(countWords("The quick brown fox jumps over the lazy dog"));
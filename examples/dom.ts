@@ -0,0 +1,36 @@
+/// <reference path="../types/global.d.ts" />
+
+const page = new DOMParser().parseFromString(
+  `<!DOCTYPE html>
+<html>
+  <head><title>Release notes</title></head>
+  <body>
+    <ul id="changes">
+      <li class="feature">Iterator helpers</li>
+      <li class="fix">Timers no longer panic</li>
+      <li class="feature">DOMParser</li>
+    </ul>
+    <a href="https://example.com/changelog">Full changelog</a>
+  </body>
+</html>`,
+  "text/html",
+);
+
+console.log(page.title);
+for (const item of page.querySelectorAll("#changes > li.feature")) {
+  console.log(`- ${item.textContent}`);
+}
+console.log(page.querySelector("a[href^='https']")?.getAttribute("href"));
+
+const feed = new DOMParser().parseFromString(
+  `<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <entry><title>First post</title></entry>
+  <entry><title>Second post</title></entry>
+</feed>`,
+  "application/xml",
+);
+
+for (const title of feed.getElementsByTagName("title")) {
+  console.log(title.textContent);
+}
@@ -0,0 +1,15 @@
+/// <reference path="../types/global.d.ts" />
+
+try {
+  Andromeda.readTextFileSync("this-file-does-not-exist.txt");
+} catch (error) {
+  if (error instanceof Andromeda.errors.NotFound) {
+    console.log("NotFound:", error.message);
+  } else {
+    throw error;
+  }
+}
+
+Andromeda.realPath("this-file-does-not-exist.txt").catch((error) => {
+  console.log(error instanceof Andromeda.errors.NotFound);
+});
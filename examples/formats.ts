@@ -0,0 +1,16 @@
+/// <reference path="../types/global.d.ts" />
+
+const config = Andromeda.toml.parse(`
+title = "andromeda"
+
+[server]
+port = 8080
+started = 2024-09-01T12:00:00Z
+`);
+console.log(JSON.stringify(config));
+
+const yaml = Andromeda.yaml.stringify(config);
+console.log(yaml);
+console.log(JSON.stringify(Andromeda.yaml.parse(yaml)));
+
+console.log(Andromeda.toml.stringify({ dependencies: { nova: "main" } }));
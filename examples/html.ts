@@ -0,0 +1,21 @@
+/// <reference path="../types/global.d.ts" />
+
+const { escape, sanitize, raw, template: html, stream } = Andromeda.html;
+
+console.log(escape(`<a href="/?q=1&r=2">Tom & Jerry</a>`));
+
+const comment = `<p onclick="steal()">Nice <b>post</b>! <a href="javascript:alert(1)">me</a></p><script>steal()</script>`;
+console.log(sanitize(comment, { tags: ["p", "b", "a"], attributes: { a: ["href"] } }));
+
+const fruits = ["<apple>", "pear", "fig"];
+const list = html`<ul>${fruits.map((fruit) => html`<li>${fruit}</li>`)}</ul>`;
+console.log(String(html`<h1>${"Fruits & more"}</h1>${list}${raw("<hr>")}`));
+
+async function main() {
+  const delayed = new Promise((resolve) => setTimeout(() => resolve("<later>"), 10));
+  for await (const chunk of stream`<p>${delayed}</p>`) {
+    console.log(chunk);
+  }
+}
+
+main();
@@ -0,0 +1,17 @@
+/// <reference path="../types/global.d.ts" />
+
+setTimeout(() => console.log("timeout, once the timer fired"), 0);
+
+setImmediate(() => {
+  console.log("3. immediate");
+  Promise.resolve().then(() => console.log("4. microtask queued by the immediate"));
+  setImmediate(() => console.log("immediate queued by an immediate, in the next turn"));
+});
+
+Promise.resolve().then(() => console.log("2. microtask"));
+
+clearImmediate(setImmediate(() => {
+  console.log("I'll never run :)");
+}));
+
+console.log("1. script");
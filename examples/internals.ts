@@ -0,0 +1,19 @@
+/// <reference path="../types/global.d.ts" />
+
+// Run with `andromeda run --expose-internals examples/internals.ts`
+const ops = Andromeda.internals.ops();
+const extensions = new Map<string, string[]>();
+for (const op of ops) {
+  const names = extensions.get(op.extension) ?? [];
+  names.push(`${op.name}/${op.args}${op.async ? " (async)" : ""}`);
+  extensions.set(op.extension, names);
+}
+
+for (const [extension, names] of extensions) {
+  console.log(`${extension}: ${names.join(", ")}`);
+}
+
+const stats = Andromeda.internals.blockingPool();
+console.log(
+  `Blocking pool: ${stats.running} running, ${stats.queued} queued, ${stats.completed} completed`,
+);
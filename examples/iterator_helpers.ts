@@ -0,0 +1,32 @@
+/// <reference path="../types/global.d.ts" />
+
+const squares = [1, 2, 3, 4, 5, 6].values()
+  .filter((n) => n % 2 === 0)
+  .map((n) => n * n)
+  .toArray();
+console.log(squares);
+
+Andromeda.writeTextFileSync(
+  "events.ndjson",
+  [
+    JSON.stringify({ level: "info", message: "server started" }),
+    JSON.stringify({ level: "warn", message: "slow request" }),
+    JSON.stringify({ level: "warn", message: "retrying" }),
+  ].join("\n"),
+);
+
+async function main() {
+  const warnings = await Andromeda.readJsonLines("events.ndjson")
+    .filter((event: any) => event.level === "warn")
+    .map((event: any) => event.message)
+    .toArray();
+  console.log(warnings);
+
+  const doubled = await Array.fromAsync(
+    [Promise.resolve(1), 2, Promise.resolve(3)],
+    (n: number) => n * 2,
+  );
+  console.log(doubled);
+}
+
+main();
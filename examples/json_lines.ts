@@ -0,0 +1,19 @@
+/// <reference path="../types/global.d.ts" />
+
+Andromeda.writeTextFileSync(
+  "events.ndjson",
+  [
+    JSON.stringify({ level: "info", message: "server started" }),
+    "",
+    JSON.stringify({ level: "warn", message: "slow request" }),
+  ].join("\n"),
+);
+
+async function main() {
+  for await (const event of Andromeda.readJsonLines("events.ndjson")) {
+    const { level, message } = event as { level: string; message: string };
+    console.log(`[${level}] ${message}`);
+  }
+}
+
+main();
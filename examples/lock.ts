@@ -0,0 +1,30 @@
+/// <reference path="../types/global.d.ts" />
+
+Andromeda.writeTextFileSync("counter.txt", "0");
+const rid = Andromeda.openFileSync("counter.txt", { write: true });
+
+// Whole-file locks survive reading the file through another resource.
+if (Andromeda.tryLockSync(rid, true)) {
+  const count = Number(Andromeda.readTextFileSync("counter.txt"));
+  Andromeda.writeTextFileSync("counter.txt", String(count + 1));
+  Andromeda.unlockSync(rid);
+} else {
+  console.log("Another process is updating the counter");
+}
+
+async function main() {
+  // Exclusive range locks need the file opened for writing. Outside of Linux, closing any
+  // resource of the file releases them, so don't read it with `readTextFileSync` meanwhile.
+  await Andromeda.lockRange(rid, 0, 1, true);
+  console.log("Locked the first byte of the counter");
+  Andromeda.unlockRangeSync(rid, 0, 1);
+
+  if (Andromeda.tryLockRangeSync(rid, 1, 0)) {
+    console.log("Locked the rest of the counter without waiting");
+    Andromeda.unlockRangeSync(rid, 1, 0);
+  }
+
+  Andromeda.closeSync(rid);
+}
+
+main();
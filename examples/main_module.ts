@@ -0,0 +1,6 @@
+/// <reference path="../types/global.d.ts" />
+
+console.log(`Running ${Andromeda.mainModule}`);
+
+const sibling = new URL("./fizzbuzz.ts", Andromeda.mainModule);
+console.log(`A sibling script lives at ${sibling.href}`);
@@ -0,0 +1,23 @@
+/// <reference path="../types/global.d.ts" />
+
+const { values, positionals } = Andromeda.parseArgs({
+  args: ["-vv", "--port=8080", "--no-color", "--tag", "a", "--tag", "b", "src"],
+  options: {
+    verbose: { type: "boolean", short: "v", multiple: true },
+    port: { type: "number", default: 3000 },
+    color: { type: "boolean", default: true },
+    tag: { type: "string", multiple: true },
+    host: { type: "string", default: "localhost" },
+  },
+  allowPositionals: true,
+  allowNegative: true,
+});
+
+console.log(JSON.stringify(values));
+console.log(JSON.stringify(positionals));
+
+try {
+  Andromeda.parseArgs({ args: ["--unknown"] });
+} catch (error) {
+  console.log((error as Error).message);
+}
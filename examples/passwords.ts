@@ -0,0 +1,18 @@
+/// <reference path="../types/global.d.ts" />
+
+async function main() {
+  const hash = await Andromeda.passwords.hash("correct horse battery staple");
+  console.log(hash);
+
+  console.log(
+    await Andromeda.passwords.verify("correct horse battery staple", hash),
+  );
+  console.log(await Andromeda.passwords.verify("Tr0ub4dor&3", hash));
+
+  const bcryptHash = await Andromeda.passwords.hash("hunter2", {
+    algorithm: "bcrypt",
+  });
+  console.log(await Andromeda.passwords.verify("hunter2", bcryptHash));
+}
+
+main();
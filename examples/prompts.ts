@@ -0,0 +1,25 @@
+/// <reference path="../types/global.d.ts" />
+
+const name = Andromeda.prompts.select("Project template", [
+  { value: "cli", label: "Command line tool" },
+  { value: "server", label: "HTTP server", hint: "needs network access" },
+  { value: "library", label: "Library" },
+]);
+
+const features = Andromeda.prompts.multiselect(
+  "Features",
+  ["formatting", "linting", "tests"],
+  { initial: ["tests"] },
+);
+
+const token = Andromeda.prompts.password("Registry token");
+
+if (Andromeda.prompts.confirm(`Create a ${name} project?`)) {
+  const spinner = Andromeda.prompts.spinner("Creating the project");
+  setTimeout(() => {
+    spinner.stop(
+      `Created a ${name} project with ${features.join(", ") || "no features"}` +
+        (token ? " and a registry token" : ""),
+    );
+  }, 1000);
+}
@@ -0,0 +1,24 @@
+/// <reference path="../types/global.d.ts" />
+
+const log = [
+  "2024-09-01 12:00:01 INFO  GET /index.html 200",
+  "2024-09-01 12:00:02 WARN  GET /slow 200",
+  "2024-09-01 12:00:03 ERROR POST /api/users 500",
+].join("\n");
+
+const line = Andromeda.regex.compile(
+  "^(?P<date>\\S+) (?P<time>\\S+) (?P<level>\\w+)\\s+(?P<method>\\w+) (?P<path>\\S+) (?P<status>\\d+)$",
+  "m",
+);
+
+for (const match of line.findAll(log)) {
+  const { level, path, status } = match.groups;
+  console.log(`${level}: ${path} -> ${status}`);
+}
+
+console.log(line.replaceAll(log, "$level $path"));
+line.close();
+
+const separators = Andromeda.regex.compile("\\s*[,;]\\s*");
+console.log(separators.split("a, b;c ,d", 3));
+separators.close();
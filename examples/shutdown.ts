@@ -0,0 +1,12 @@
+/// <reference path="../types/global.d.ts" />
+
+// Stands in for a server, press Ctrl+C to shut it down.
+let requests = 0;
+const server = setInterval(() => console.log(`Handled request ${++requests}`), 500);
+
+Andromeda.shutdown.on(async (signal) => {
+  console.log(`Received ${signal}, no longer accepting requests`);
+  clearInterval(server);
+  await Andromeda.sleep(1000);
+  console.log(`Shut down after ${requests} requests`);
+});
@@ -0,0 +1,23 @@
+/// <reference path="../types/global.d.ts" />
+
+async function main() {
+  const response = await Andromeda.smtp.send(
+    {
+      hostname: Andromeda.env.get("SMTP_HOSTNAME"),
+      tls: "starttls",
+      username: Andromeda.env.get("SMTP_USERNAME"),
+      password: Andromeda.env.get("SMTP_PASSWORD"),
+    },
+    {
+      from: "Andromeda <alerts@example.com>",
+      to: ["ops@example.com"],
+      subject: "Nightly backup finished",
+      text: "The nightly backup finished without errors.",
+      html: "<p>The nightly backup finished <b>without errors</b>.</p>",
+      attachments: [{ filename: "backup.log", content: "backup: 42 files copied\n" }],
+    },
+  );
+  console.log(response);
+}
+
+main();
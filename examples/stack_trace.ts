@@ -0,0 +1,17 @@
+/// <reference path="../types/global.d.ts" />
+
+class HttpError extends Error {
+  status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "HttpError";
+    this.status = status;
+    Error.captureStackTrace(this, HttpError);
+  }
+}
+
+console.log(new HttpError(404, "Not found").stack);
+
+Error.prepareStackTrace = (error, frames) => `${error.name} (${frames.length} frames): ${error.message}`;
+console.log(new Error("custom formatting").stack);
@@ -0,0 +1,16 @@
+/// <reference path="../types/global.d.ts" />
+
+console.table([
+  { city: "東京", country: "日本", population: 37_400_000 },
+  { city: "Paris", country: "France", population: 11_000_000 },
+  { city: "서울", country: "대한민국", population: 9_700_000 },
+  { city: "São Paulo 🇧🇷", country: "Brasil", population: 12_300_000 },
+]);
+
+console.table({ apples: 3, bananas: 12, "🍒": 40 });
+
+console.log(Andromeda.text.width("日本語"));
+console.log(Andromeda.text.truncate("こんにちは世界", 9));
+for (const line of Andromeda.text.wrap("Wide characters like 漢字 and emoji like 🎉 take two columns.", 20)) {
+  console.log(`|${line}|`);
+}
@@ -1,64 +1,1176 @@
-// deno-lint-ignore-file no-unused-vars
+// deno-lint-ignore-file no-unused-vars no-explicit-any
+
+// Explicit Resource Management: the well-known symbols, `SuppressedError`, `DisposableStack` and
+// `AsyncDisposableStack`, for as long as the engine doesn't provide them.
+if (typeof Symbol.dispose !== "symbol") {
+  Object.defineProperty(Symbol, "dispose", { value: Symbol("Symbol.dispose") });
+}
+if (typeof Symbol.asyncDispose !== "symbol") {
+  Object.defineProperty(Symbol, "asyncDispose", {
+    value: Symbol("Symbol.asyncDispose"),
+  });
+}
+
+if (typeof (globalThis as any).SuppressedError !== "function") {
+  /**
+   * The `SuppressedError` class is thrown when disposing a resource throws while another error
+   * is already being thrown.
+   */
+  class SuppressedError extends Error {
+    error: unknown;
+    suppressed: unknown;
+
+    constructor(error: unknown, suppressed: unknown, message?: string) {
+      super(message);
+      this.name = "SuppressedError";
+      this.error = error;
+      this.suppressed = suppressed;
+    }
+  }
+  (globalThis as any).SuppressedError = SuppressedError;
+}
+
+const stackDisposers = Symbol("[[disposers]]");
+const stackDisposed = Symbol("[[disposed]]");
+
+/**
+ * Take the disposers out of a stack, throwing if it is already disposed.
+ */
+function takeDisposers(
+  stack: { [stackDisposers]: (() => unknown)[]; [stackDisposed]: boolean },
+  name: string,
+): (() => unknown)[] {
+  if (stack[stackDisposed]) {
+    throw new ReferenceError(`The ${name} is already disposed`);
+  }
+  const disposers = stack[stackDisposers];
+  stack[stackDisposers] = [];
+  stack[stackDisposed] = true;
+  return disposers;
+}
+
+/**
+ * Get the dispose method of a resource for a stack, `undefined` for `null` and `undefined`.
+ */
+function disposeMethod(
+  value: unknown,
+  symbols: symbol[],
+): (() => unknown) | undefined {
+  if (value === null || value === undefined) return undefined;
+  for (const symbol of symbols) {
+    const dispose = (value as any)[symbol];
+    if (typeof dispose === "function") return () => dispose.call(value);
+  }
+  throw new TypeError("The value is not disposable");
+}
+
+if (typeof (globalThis as any).DisposableStack !== "function") {
+  /**
+   * The `DisposableStack` class collects resources and disposes them all, in reverse order,
+   * when it is disposed itself.
+   *
+   * @example
+   * ```ts
+   * const stack = new DisposableStack();
+   * const words = stack.use(Andromeda.regex.compile("\\w+"));
+   * stack.defer(() => console.log("done"));
+   * stack.dispose();
+   * ```
+   */
+  class DisposableStack {
+    [stackDisposers]: (() => unknown)[] = [];
+    [stackDisposed] = false;
+
+    get disposed(): boolean {
+      return this[stackDisposed];
+    }
+
+    use<T extends Disposable | null | undefined>(value: T): T {
+      const dispose = disposeMethod(value, [Symbol.dispose]);
+      this.defer(dispose ?? (() => {}));
+      return value;
+    }
+
+    adopt<T>(value: T, onDispose: (value: T) => void): T {
+      this.defer(() => onDispose(value));
+      return value;
+    }
+
+    defer(onDispose: () => void): void {
+      if (this[stackDisposed]) {
+        throw new ReferenceError("The DisposableStack is already disposed");
+      }
+      this[stackDisposers].push(onDispose);
+    }
+
+    move(): DisposableStack {
+      const stack = new DisposableStack();
+      stack[stackDisposers] = takeDisposers(this, "DisposableStack");
+      return stack;
+    }
+
+    dispose(): void {
+      if (this[stackDisposed]) return;
+      const disposers = takeDisposers(this, "DisposableStack");
+      let thrown = false;
+      let error: unknown;
+      while (disposers.length > 0) {
+        try {
+          disposers.pop()!();
+        } catch (e) {
+          error = thrown ? new (globalThis as any).SuppressedError(e, error) : e;
+          thrown = true;
+        }
+      }
+      if (thrown) throw error;
+    }
+
+    [Symbol.dispose](): void {
+      this.dispose();
+    }
+  }
+  (globalThis as any).DisposableStack = DisposableStack;
+}
+
+if (typeof (globalThis as any).AsyncDisposableStack !== "function") {
+  /**
+   * The `AsyncDisposableStack` class collects sync and async resources and disposes them all,
+   * in reverse order and one after the other, when it is disposed itself.
+   *
+   * @example
+   * ```ts
+   * const stack = new AsyncDisposableStack();
+   * const entries = stack.use(Andromeda.readJsonLines("logs.ndjson"));
+   * stack.disposeAsync().then(() => console.log("closed"));
+   * ```
+   */
+  class AsyncDisposableStack {
+    [stackDisposers]: (() => unknown)[] = [];
+    [stackDisposed] = false;
+
+    get disposed(): boolean {
+      return this[stackDisposed];
+    }
+
+    use<T extends AsyncDisposable | Disposable | null | undefined>(value: T): T {
+      const dispose = disposeMethod(value, [
+        Symbol.asyncDispose,
+        Symbol.dispose,
+      ]);
+      this.defer(dispose ?? (() => {}));
+      return value;
+    }
+
+    adopt<T>(value: T, onDisposeAsync: (value: T) => unknown): T {
+      this.defer(() => onDisposeAsync(value));
+      return value;
+    }
+
+    defer(onDisposeAsync: () => unknown): void {
+      if (this[stackDisposed]) {
+        throw new ReferenceError("The AsyncDisposableStack is already disposed");
+      }
+      this[stackDisposers].push(onDisposeAsync);
+    }
+
+    move(): AsyncDisposableStack {
+      const stack = new AsyncDisposableStack();
+      stack[stackDisposers] = takeDisposers(this, "AsyncDisposableStack");
+      return stack;
+    }
+
+    async disposeAsync(): Promise<void> {
+      if (this[stackDisposed]) return;
+      const disposers = takeDisposers(this, "AsyncDisposableStack");
+      let thrown = false;
+      let error: unknown;
+      while (disposers.length > 0) {
+        try {
+          await disposers.pop()!();
+        } catch (e) {
+          error = thrown ? new (globalThis as any).SuppressedError(e, error) : e;
+          thrown = true;
+        }
+      }
+      if (thrown) throw error;
+    }
+
+    [Symbol.asyncDispose](): Promise<void> {
+      return this.disposeAsync();
+    }
+  }
+  (globalThis as any).AsyncDisposableStack = AsyncDisposableStack;
+}
+
+// Iterator helpers, Async Iterator helpers and `Array.fromAsync`, for as long as the engine
+// doesn't provide them. They are written without generators so they only rely on plain objects.
+const iteratorPrototype = Object.getPrototypeOf(
+  Object.getPrototypeOf([][Symbol.iterator]()),
+);
+const asyncIteratorPrototype = (() => {
+  try {
+    // Parsed at runtime so engines without async generators can still load this file.
+    const asyncGenerator = new Function("return async function* () {}")();
+    return Object.getPrototypeOf(
+      Object.getPrototypeOf(asyncGenerator.prototype),
+    );
+  } catch {
+    return {
+      [Symbol.asyncIterator]() {
+        return this;
+      },
+    };
+  }
+})();
+
+/**
+ * Throw a `TypeError` unless the value is a function.
+ */
+function requireCallable(value: unknown, name: string): void {
+  if (typeof value !== "function") {
+    throw new TypeError(`${name} is not a function`);
+  }
+}
+
+/**
+ * Convert the limit of `take` and `drop`, throwing a `RangeError` for negative values and NaN.
+ */
+function toLimit(limit: number): number {
+  const number = Number(limit);
+  if (Number.isNaN(number)) throw new RangeError("The limit is NaN");
+  const integer = Math.trunc(number);
+  if (integer < 0) throw new RangeError("The limit must be positive");
+  return integer;
+}
+
+/**
+ * Create an iterator inheriting the helpers, pulling values with `step` until it returns `done`.
+ * Closing it closes the source iterator.
+ */
+function createIteratorHelper(
+  source: Iterator<unknown>,
+  step: (close: () => void) => IteratorResult<unknown>,
+): any {
+  let done = false;
+  const close = () => {
+    if (!done) {
+      done = true;
+      source.return?.();
+    }
+  };
+  const helper = Object.create(iteratorPrototype);
+  helper.next = () => {
+    if (done) return { value: undefined, done: true };
+    const result = step(close);
+    if (result.done) done = true;
+    return result;
+  };
+  helper.return = () => {
+    close();
+    return { value: undefined, done: true };
+  };
+  return helper;
+}
+
+/**
+ * Call a helper callback, closing the source iterator if it throws.
+ */
+function callOrClose<T>(close: () => void, callback: () => T): T {
+  try {
+    return callback();
+  } catch (error) {
+    close();
+    throw error;
+  }
+}
+
+if (typeof (globalThis as any).Iterator !== "function") {
+  /**
+   * The `Iterator` class is the base of the built-in iterators, holding the iterator helpers.
+   */
+  const Iterator = function Iterator(this: unknown) {
+    if (new.target === undefined || new.target === Iterator) {
+      throw new TypeError("Iterator is abstract");
+    }
+  } as any;
+  Iterator.prototype = iteratorPrototype;
+  Object.defineProperty(iteratorPrototype, "constructor", {
+    value: Iterator,
+    writable: true,
+    configurable: true,
+  });
+  (globalThis as any).Iterator = Iterator;
+}
+
+if (typeof (globalThis as any).Iterator.from !== "function") {
+  (globalThis as any).Iterator.from = function from(value: any) {
+    const iterator = typeof value === "string" || value?.[Symbol.iterator]
+      ? value[Symbol.iterator]()
+      : value;
+    if (iteratorPrototype.isPrototypeOf(iterator)) return iterator;
+    const wrapper = Object.create(iteratorPrototype);
+    wrapper.next = () => iterator.next();
+    wrapper.return = () => iterator.return?.() ?? { value: undefined, done: true };
+    return wrapper;
+  };
+}
+
+const iteratorHelpers: Record<string, (this: Iterator<any>, ...args: any[]) => any> = {
+  map(mapper: (value: unknown, index: number) => unknown) {
+    requireCallable(mapper, "The mapper");
+    let index = 0;
+    return createIteratorHelper(this, (close) => {
+      const result = this.next();
+      if (result.done) return result;
+      return {
+        value: callOrClose(close, () => mapper(result.value, index++)),
+        done: false,
+      };
+    });
+  },
+
+  filter(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    return createIteratorHelper(this, (close) => {
+      while (true) {
+        const result = this.next();
+        if (result.done) return result;
+        if (callOrClose(close, () => predicate(result.value, index++))) {
+          return result;
+        }
+      }
+    });
+  },
+
+  take(limit: number) {
+    let remaining = toLimit(limit);
+    return createIteratorHelper(this, (close) => {
+      if (remaining === 0) {
+        close();
+        return { value: undefined, done: true };
+      }
+      remaining--;
+      return this.next();
+    });
+  },
+
+  drop(limit: number) {
+    let remaining = toLimit(limit);
+    return createIteratorHelper(this, () => {
+      while (remaining > 0) {
+        remaining--;
+        if (this.next().done) return { value: undefined, done: true };
+      }
+      return this.next();
+    });
+  },
+
+  flatMap(mapper: (value: unknown, index: number) => Iterable<unknown>) {
+    requireCallable(mapper, "The mapper");
+    let index = 0;
+    let inner: Iterator<unknown> | undefined;
+    return createIteratorHelper(this, (close) => {
+      while (true) {
+        if (inner) {
+          const result = callOrClose(close, () => inner!.next());
+          if (!result.done) return result;
+          inner = undefined;
+        }
+        const result = this.next();
+        if (result.done) return result;
+        inner = callOrClose(
+          close,
+          () => (Iterator as any).from(mapper(result.value, index++)),
+        );
+      }
+    });
+  },
+
+  reduce(reducer: (acc: unknown, value: unknown, index: number) => unknown, ...initial: unknown[]) {
+    requireCallable(reducer, "The reducer");
+    let index = 0;
+    let accumulator: unknown;
+    if (initial.length > 0) {
+      accumulator = initial[0];
+    } else {
+      const first = this.next();
+      if (first.done) {
+        throw new TypeError("Reduce of an empty iterator with no initial value");
+      }
+      accumulator = first.value;
+      index = 1;
+    }
+    for (let result = this.next(); !result.done; result = this.next()) {
+      const value = result.value;
+      accumulator = callOrClose(
+        () => this.return?.(),
+        () => reducer(accumulator, value, index++),
+      );
+    }
+    return accumulator;
+  },
+
+  toArray() {
+    const values = [];
+    for (let result = this.next(); !result.done; result = this.next()) {
+      values.push(result.value);
+    }
+    return values;
+  },
+
+  forEach(callback: (value: unknown, index: number) => void) {
+    requireCallable(callback, "The callback");
+    let index = 0;
+    for (let result = this.next(); !result.done; result = this.next()) {
+      const value = result.value;
+      callOrClose(() => this.return?.(), () => callback(value, index++));
+    }
+  },
+
+  some(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    for (let result = this.next(); !result.done; result = this.next()) {
+      const value = result.value;
+      if (callOrClose(() => this.return?.(), () => predicate(value, index++))) {
+        this.return?.();
+        return true;
+      }
+    }
+    return false;
+  },
+
+  every(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    for (let result = this.next(); !result.done; result = this.next()) {
+      const value = result.value;
+      if (!callOrClose(() => this.return?.(), () => predicate(value, index++))) {
+        this.return?.();
+        return false;
+      }
+    }
+    return true;
+  },
+
+  find(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    for (let result = this.next(); !result.done; result = this.next()) {
+      const value = result.value;
+      if (callOrClose(() => this.return?.(), () => predicate(value, index++))) {
+        this.return?.();
+        return value;
+      }
+    }
+    return undefined;
+  },
+};
+
+/**
+ * Create an async iterator inheriting the helpers, pulling values with `step`.
+ * Closing it closes the source iterator.
+ */
+function createAsyncIteratorHelper(
+  source: AsyncIterator<unknown>,
+  step: (close: () => Promise<void>) => Promise<IteratorResult<unknown>>,
+): any {
+  let done = false;
+  const close = async () => {
+    if (!done) {
+      done = true;
+      await source.return?.();
+    }
+  };
+  const helper = Object.create(asyncIteratorPrototype);
+  helper.next = async () => {
+    if (done) return { value: undefined, done: true };
+    const result = await step(close);
+    if (result.done) done = true;
+    return result;
+  };
+  helper.return = async () => {
+    await close();
+    return { value: undefined, done: true };
+  };
+  return helper;
+}
+
+/**
+ * Await a helper callback, closing the source iterator if it throws or rejects.
+ */
+async function awaitOrClose<T>(
+  close: () => Promise<unknown>,
+  callback: () => T | Promise<T>,
+): Promise<T> {
+  try {
+    return await callback();
+  } catch (error) {
+    await close();
+    throw error;
+  }
+}
+
+const asyncIteratorHelpers: Record<
+  string,
+  (this: AsyncIterator<any>, ...args: any[]) => any
+> = {
+  map(mapper: (value: unknown, index: number) => unknown) {
+    requireCallable(mapper, "The mapper");
+    let index = 0;
+    return createAsyncIteratorHelper(this, async (close) => {
+      const result = await this.next();
+      if (result.done) return result;
+      return {
+        value: await awaitOrClose(close, () => mapper(result.value, index++)),
+        done: false,
+      };
+    });
+  },
+
+  filter(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    return createAsyncIteratorHelper(this, async (close) => {
+      while (true) {
+        const result = await this.next();
+        if (result.done) return result;
+        if (await awaitOrClose(close, () => predicate(result.value, index++))) {
+          return result;
+        }
+      }
+    });
+  },
+
+  take(limit: number) {
+    let remaining = toLimit(limit);
+    return createAsyncIteratorHelper(this, async (close) => {
+      if (remaining === 0) {
+        await close();
+        return { value: undefined, done: true };
+      }
+      remaining--;
+      return this.next();
+    });
+  },
+
+  drop(limit: number) {
+    let remaining = toLimit(limit);
+    return createAsyncIteratorHelper(this, async () => {
+      while (remaining > 0) {
+        remaining--;
+        if ((await this.next()).done) return { value: undefined, done: true };
+      }
+      return this.next();
+    });
+  },
+
+  async reduce(
+    reducer: (acc: unknown, value: unknown, index: number) => unknown,
+    ...initial: unknown[]
+  ) {
+    requireCallable(reducer, "The reducer");
+    let index = 0;
+    let accumulator: unknown;
+    if (initial.length > 0) {
+      accumulator = initial[0];
+    } else {
+      const first = await this.next();
+      if (first.done) {
+        throw new TypeError("Reduce of an empty iterator with no initial value");
+      }
+      accumulator = first.value;
+      index = 1;
+    }
+    for (let result = await this.next(); !result.done; result = await this.next()) {
+      const value = result.value;
+      accumulator = await awaitOrClose(
+        async () => await this.return?.(),
+        () => reducer(accumulator, value, index++),
+      );
+    }
+    return accumulator;
+  },
+
+  async toArray() {
+    const values = [];
+    for (let result = await this.next(); !result.done; result = await this.next()) {
+      values.push(result.value);
+    }
+    return values;
+  },
+
+  async forEach(callback: (value: unknown, index: number) => unknown) {
+    requireCallable(callback, "The callback");
+    let index = 0;
+    for (let result = await this.next(); !result.done; result = await this.next()) {
+      const value = result.value;
+      await awaitOrClose(
+        async () => await this.return?.(),
+        () => callback(value, index++),
+      );
+    }
+  },
+
+  async some(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    for (let result = await this.next(); !result.done; result = await this.next()) {
+      const value = result.value;
+      const close = async () => await this.return?.();
+      if (await awaitOrClose(close, () => predicate(value, index++))) {
+        await close();
+        return true;
+      }
+    }
+    return false;
+  },
+
+  async every(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    for (let result = await this.next(); !result.done; result = await this.next()) {
+      const value = result.value;
+      const close = async () => await this.return?.();
+      if (!(await awaitOrClose(close, () => predicate(value, index++)))) {
+        await close();
+        return false;
+      }
+    }
+    return true;
+  },
+
+  async find(predicate: (value: unknown, index: number) => unknown) {
+    requireCallable(predicate, "The predicate");
+    let index = 0;
+    for (let result = await this.next(); !result.done; result = await this.next()) {
+      const value = result.value;
+      const close = async () => await this.return?.();
+      if (await awaitOrClose(close, () => predicate(value, index++))) {
+        await close();
+        return value;
+      }
+    }
+    return undefined;
+  },
+};
+
+for (
+  const [prototype, helpers] of [
+    [iteratorPrototype, iteratorHelpers],
+    [asyncIteratorPrototype, asyncIteratorHelpers],
+  ]
+) {
+  for (const [name, helper] of Object.entries(helpers)) {
+    if (typeof prototype[name] !== "function") {
+      Object.defineProperty(prototype, name, {
+        value: helper,
+        writable: true,
+        configurable: true,
+      });
+    }
+  }
+}
+
+if (typeof (globalThis as any).AsyncIterator !== "function") {
+  /**
+   * The `AsyncIterator` class is the base of the built-in async iterators, holding the async
+   * iterator helpers.
+   */
+  const AsyncIterator = function AsyncIterator(this: unknown) {
+    if (new.target === undefined || new.target === AsyncIterator) {
+      throw new TypeError("AsyncIterator is abstract");
+    }
+  } as any;
+  AsyncIterator.prototype = asyncIteratorPrototype;
+  (globalThis as any).AsyncIterator = AsyncIterator;
+}
+
+if (typeof (Array as any).fromAsync !== "function") {
+  /**
+   * The `Array.fromAsync` function collects the values of an async iterable, or of a sync
+   * iterable or array-like of promises, into an array.
+   */
+  (Array as any).fromAsync = async function fromAsync(
+    items: any,
+    mapper?: (value: unknown, index: number) => unknown,
+    thisArg?: unknown,
+  ): Promise<unknown[]> {
+    if (mapper !== undefined) requireCallable(mapper, "The mapper");
+    const values: unknown[] = [];
+    const push = async (value: unknown) => {
+      const awaited = await value;
+      values.push(
+        mapper === undefined
+          ? awaited
+          : await mapper.call(thisArg, awaited, values.length),
+      );
+    };
+
+    if (items?.[Symbol.asyncIterator]) {
+      const iterator = items[Symbol.asyncIterator]();
+      for (let result = await iterator.next(); !result.done; result = await iterator.next()) {
+        await awaitOrClose(
+          async () => await iterator.return?.(),
+          () => push(result.value),
+        );
+      }
+    } else if (items?.[Symbol.iterator]) {
+      const iterator = items[Symbol.iterator]();
+      for (let result = iterator.next(); !result.done; result = iterator.next()) {
+        await awaitOrClose(async () => iterator.return?.(), () => push(result.value));
+      }
+    } else {
+      const arrayLike = Object(items);
+      for (let index = 0; index < arrayLike.length; index++) {
+        await push(arrayLike[index]);
+      }
+    }
+    return values;
+  };
+}
+
+/**
+ * HTML that is trusted as is, either written by hand or already escaped or sanitized.
+ */
+class SafeHtml {
+  readonly value: string;
+
+  constructor(value: string) {
+    this.value = value;
+  }
+
+  toString(): string {
+    return this.value;
+  }
+}
+
+/**
+ * Convert an interpolated value of an HTML template: safe HTML is kept, arrays are joined, `null`,
+ * `undefined` and `false` are left out and anything else is escaped.
+ */
+function htmlChunk(value: unknown): string {
+  if (value instanceof SafeHtml) return value.value;
+  if (value === null || value === undefined || value === false) return "";
+  if (Array.isArray(value)) return value.map(htmlChunk).join("");
+  return internal_html_escape(String(value));
+}
+
+/**
+ * Normalize the options of a select or multiselect prompt.
+ */
+function promptChoices<T>(
+  options: (T | { value: T; label?: string; hint?: string })[],
+): { value: T; label: string; hint: string }[] {
+  return options.map((option) => {
+    if (typeof option === "object" && option !== null && "value" in option) {
+      return {
+        value: option.value,
+        label: option.label ?? String(option.value),
+        hint: option.hint ?? "",
+      };
+    }
+    return { value: option as T, label: String(option), hint: "" };
+  });
+}
+
+/**
+ * The `AssertionError` class is the error thrown by the assertion functions when an assertion fails.
+ */
+class AssertionError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "AssertionError";
+  }
+}
+
+/**
+ * Helpers shared by the assertion functions.
+ */
+const assertionInternals = {
+  /**
+   * Deep equality of two values. Objects are equal when they have the same prototype and equal own
+   * enumerable properties; Dates, RegExps, Maps, Sets, typed arrays and Errors compare their contents.
+   */
+  equal(a: unknown, b: unknown, seen = new Map<object, object>()): boolean {
+    if (Object.is(a, b)) return true;
+    if (
+      typeof a !== "object" || typeof b !== "object" || a === null ||
+      b === null
+    ) {
+      return false;
+    }
+    if (Object.getPrototypeOf(a) !== Object.getPrototypeOf(b)) return false;
+    // Cyclic structures are equal if their cycles line up.
+    if (seen.get(a) === b) return true;
+    seen.set(a, b);
+
+    const equal = (x: unknown, y: unknown) =>
+      assertionInternals.equal(x, y, seen);
+    if (a instanceof Date) {
+      return Object.is(a.getTime(), (b as Date).getTime());
+    }
+    if (a instanceof RegExp) return String(a) === String(b);
+    if (a instanceof Error) {
+      const other = b as Error;
+      if (a.name !== other.name || a.message !== other.message) return false;
+    }
+    if (a instanceof Map) {
+      const other = b as Map<unknown, unknown>;
+      if (a.size !== other.size) return false;
+      for (const [key, value] of a) {
+        if (other.has(key)) {
+          if (!equal(value, other.get(key))) return false;
+        } else if (
+          ![...other].some(([otherKey, otherValue]) =>
+            equal(key, otherKey) && equal(value, otherValue)
+          )
+        ) {
+          return false;
+        }
+      }
+      return true;
+    }
+    if (a instanceof Set) {
+      const other = b as Set<unknown>;
+      if (a.size !== other.size) return false;
+      for (const value of a) {
+        if (
+          !other.has(value) &&
+          ![...other].some((otherValue) => equal(value, otherValue))
+        ) {
+          return false;
+        }
+      }
+      return true;
+    }
+    if (ArrayBuffer.isView(a)) {
+      const x = a as unknown as ArrayLike<unknown>;
+      const y = b as unknown as ArrayLike<unknown>;
+      if (x.length !== y.length) return false;
+      for (let i = 0; i < x.length; i++) {
+        if (!Object.is(x[i], y[i])) return false;
+      }
+      return true;
+    }
+
+    const keys = (value: object) => [
+      ...Object.keys(value),
+      ...Object.getOwnPropertySymbols(value).filter((symbol) =>
+        Object.prototype.propertyIsEnumerable.call(value, symbol)
+      ),
+    ];
+    const aKeys = keys(a);
+    const bKeys = keys(b);
+    if (aKeys.length !== bKeys.length) return false;
+    return aKeys.every((key) =>
+      Object.prototype.hasOwnProperty.call(b, key) &&
+      equal(
+        (a as Record<PropertyKey, unknown>)[key],
+        (b as Record<PropertyKey, unknown>)[key],
+      )
+    );
+  },
+
+  /**
+   * Format a value over multiple lines, one entry per line, so that values can be compared line by line.
+   */
+  format(value: unknown, indent = "", seen = new Set<object>()): string {
+    switch (typeof value) {
+      case "string":
+        return JSON.stringify(value);
+      case "bigint":
+        return `${value}n`;
+      case "number":
+        return Object.is(value, -0) ? "-0" : String(value);
+      case "function":
+        return `[Function: ${value.name || "(anonymous)"}]`;
+      case "object":
+        break;
+      default:
+        return String(value);
+    }
+    if (value === null) return "null";
+    if (seen.has(value)) return "[Circular]";
+    if (value instanceof Date) {
+      return isNaN(value.getTime())
+        ? "Invalid Date"
+        : `Date(${value.toISOString()})`;
+    }
+    if (value instanceof RegExp) return String(value);
+    if (value instanceof Error) return `${value.name}: ${value.message}`;
+
+    seen.add(value);
+    const inner = indent + "  ";
+    const format = (item: unknown) =>
+      assertionInternals.format(item, inner, seen);
+    const constructorName = value.constructor?.name ?? "Object";
+    let open = "{";
+    let close = "}";
+    let prefix = constructorName === "Object" ? "" : `${constructorName} `;
+    let entries: string[];
+    if (Array.isArray(value) || ArrayBuffer.isView(value)) {
+      open = "[";
+      close = "]";
+      if (Array.isArray(value)) prefix = "";
+      entries = Array.from(value as unknown as ArrayLike<unknown>, format);
+    } else if (value instanceof Map) {
+      entries = [...value].map(([key, item]) =>
+        `${format(key)} => ${format(item)}`
+      );
+    } else if (value instanceof Set) {
+      entries = [...value].map(format);
+    } else {
+      entries = [
+        ...Object.keys(value).map((key) =>
+          `${/^[A-Za-z_$][\w$]*$/.test(key) ? key : JSON.stringify(key)}: ${
+            format((value as Record<string, unknown>)[key])
+          }`
+        ),
+        ...Object.getOwnPropertySymbols(value).map((symbol) =>
+          `[${String(symbol)}]: ${
+            format((value as Record<symbol, unknown>)[symbol])
+          }`
+        ),
+      ];
+    }
+    seen.delete(value);
+
+    if (entries.length === 0) return `${prefix}${open}${close}`;
+    return `${prefix}${open}\n${
+      entries.map((entry) => `${inner}${entry},`).join("\n")
+    }\n${indent}${close}`;
+  },
+
+  /**
+   * Line diff of the formatted values, with removed lines from `actual` and added lines from `expected`.
+   */
+  diff(actual: unknown, expected: unknown): string {
+    const colors = !internal_no_color();
+    const paint = (code: number, text: string) =>
+      colors ? `\x1b[${code}m${text}\x1b[0m` : text;
+    const changes = Andromeda.diff.lines(
+      assertionInternals.format(actual) + "\n",
+      assertionInternals.format(expected) + "\n",
+    );
+    const lines = [
+      `    ${paint(31, "- Actual")} / ${paint(32, "+ Expected")}`,
+      "",
+    ];
+    for (const change of changes) {
+      for (const line of change.value.slice(0, -1).split("\n")) {
+        if (change.type === "delete") lines.push(paint(31, `-   ${line}`));
+        else if (change.type === "insert") lines.push(paint(32, `+   ${line}`));
+        else lines.push(`    ${line}`);
+      }
+    }
+    return lines.join("\n");
+  },
+
+  /**
+   * Throw an `AssertionError` with the user message, if any, followed by the details.
+   */
+  fail(message: string | undefined, details: string): never {
+    throw new AssertionError(message ? `${message}\n${details}` : details);
+  },
+
+  /**
+   * Check that a thrown value matches the expected error class and message, returning it as an `Error`.
+   */
+  checkError(
+    error: unknown,
+    ErrorClass: (new (...args: any[]) => Error) | undefined,
+    messageIncludes: string | undefined,
+    message: string | undefined,
+  ): Error {
+    if (ErrorClass && !(error instanceof ErrorClass)) {
+      assertionInternals.fail(
+        message,
+        `Expected error to be an instance of "${ErrorClass.name}", but was "${
+          (error as Error)?.constructor?.name ?? typeof error
+        }".`,
+      );
+    }
+    if (
+      messageIncludes !== undefined &&
+      !String((error as Error)?.message).includes(messageIncludes)
+    ) {
+      assertionInternals.fail(
+        message,
+        `Expected error message to include ${
+          JSON.stringify(messageIncludes)
+        }, but got ${JSON.stringify((error as Error)?.message)}.`,
+      );
+    }
+    return error as Error;
+  },
+};
 
 /**
- * The `assert` function tests if a condition is `true`. If the condition is `false`, an error is thrown with an optional message.
+ * The `assert` function tests if a condition is truthy, throwing an `AssertionError` otherwise.
  *
  * @example
  * ```ts
  * assert(1 === 1, "The condition is true!");
  * ```
  */
-function assert(condition: boolean, message: string) {
+function assert(condition: unknown, message?: string): asserts condition {
   if (!condition) {
-    throw new Error(message);
+    throw new AssertionError(message ?? "Assertion failed.");
   }
 }
 
 /**
- * The `assertEquals` function tests if two values are equal.
+ * The `assertEquals` function tests if two values are deeply equal, throwing an `AssertionError`
+ * with a diff of the values otherwise.
  *
  * @example
  * ```ts
- * assertEquals(1, 1, "The values are equal!");
+ * assertEquals({ a: [1, 2] }, { a: [1, 2] }, "The values are equal!");
  * ```
  */
-function assertEquals<A>(value1: A, value2: A, message: string) {
-  if (value1 !== value2) {
-    console.error(message);
+function assertEquals<A>(actual: A, expected: A, message?: string) {
+  if (!assertionInternals.equal(actual, expected)) {
+    assertionInternals.fail(
+      message,
+      `Values are not equal:\n\n${assertionInternals.diff(actual, expected)}`,
+    );
   }
 }
 
 /**
- * The `assertNotEquals` function tests if two values are not equal.
+ * The `assertNotEquals` function tests if two values are not deeply equal.
  *
  * @example
  * ```ts
  * assertNotEquals(1, 2, "The values are not equal!");
  * ```
  */
-function assertNotEquals<A>(value1: A, value2: A, message: string) {
-  if (value1 === value2) {
-    console.error(message);
+function assertNotEquals<A>(actual: A, expected: A, message?: string) {
+  if (assertionInternals.equal(actual, expected)) {
+    assertionInternals.fail(
+      message,
+      `Expected actual: ${
+        assertionInternals.format(actual)
+      } not to be equal to the expected value.`,
+    );
+  }
+}
+
+/**
+ * The `assertMatch` function tests if a string matches a regular expression.
+ *
+ * @example
+ * ```ts
+ * assertMatch("Hello, World!", /world/i);
+ * ```
+ */
+function assertMatch(actual: string, expected: RegExp, message?: string) {
+  if (!expected.test(actual)) {
+    assertionInternals.fail(
+      message,
+      `Expected actual: ${JSON.stringify(actual)} to match: ${expected}.`,
+    );
   }
 }
 
 /**
- * The `assertThrows` function tests if a function throws an error.
+ * The `assertThrows` function tests if a function throws an error, optionally of the given class and
+ * with a message including the given text. It returns the thrown error.
  *
  * @example
  * ```ts
  * assertThrows(() => {
- *  throw new Error("Hello, World!");
- * }, "An error occurred!");
+ *  throw new TypeError("Hello, World!");
+ * }, TypeError, "Hello");
+ * ```
  */
-function assertThrows(fn: () => void, message: string) {
+function assertThrows(
+  fn: () => unknown,
+  ErrorClassOrMessage?: (new (...args: any[]) => Error) | string,
+  messageIncludes?: string,
+  message?: string,
+): Error {
+  // `assertThrows(fn, message)` only sets the assertion message.
+  if (typeof ErrorClassOrMessage === "string") {
+    message = ErrorClassOrMessage;
+    ErrorClassOrMessage = undefined;
+  }
   try {
     fn();
   } catch (error) {
-    return;
+    return assertionInternals.checkError(
+      error,
+      ErrorClassOrMessage,
+      messageIncludes,
+      message,
+    );
+  }
+  return assertionInternals.fail(
+    message,
+    "Expected function to throw.",
+  );
+}
+
+/**
+ * The `assertRejects` function tests if an async function returns a Promise that rejects, optionally
+ * with an error of the given class and with a message including the given text. It resolves to the error.
+ *
+ * @example
+ * ```ts
+ * await assertRejects(() => Andromeda.readLink("missing"), Andromeda.errors.NotFound);
+ * ```
+ */
+async function assertRejects(
+  fn: () => PromiseLike<unknown>,
+  ErrorClassOrMessage?: (new (...args: any[]) => Error) | string,
+  messageIncludes?: string,
+  message?: string,
+): Promise<Error> {
+  if (typeof ErrorClassOrMessage === "string") {
+    message = ErrorClassOrMessage;
+    ErrorClassOrMessage = undefined;
+  }
+  try {
+    await fn();
+  } catch (error) {
+    return assertionInternals.checkError(
+      error,
+      ErrorClassOrMessage,
+      messageIncludes,
+      message,
+    );
   }
+  return assertionInternals.fail(
+    message,
+    "Expected function to reject.",
+  );
+}
 
-  console.error(message);
+/**
+ * Hooks registered with `Andromeda.shutdown.on`.
+ */
+const shutdownHooks: ((signal: string) => unknown)[] = [];
+
+/**
+ * Run the shutdown hooks concurrently, then exit with the code of the signal once they settled
+ * or `Andromeda.shutdown.timeout` passed.
+ */
+function runShutdownHooks(signal: string, exitCode: number): void {
+  const hooks = shutdownHooks.map((hook) =>
+    Promise.resolve()
+      .then(() => hook(signal))
+      .catch((error) => console.error("Uncaught error in a shutdown hook:", error))
+  );
+  let timer: number | undefined;
+  const timeout = new Promise<void>((resolve) => {
+    timer = setTimeout(resolve, Andromeda.shutdown.timeout);
+  });
+  Promise.race([Promise.all(hooks), timeout]).then(() => {
+    clearTimeout(timer);
+    internal_exit(exitCode);
+  });
 }
 
 /**
@@ -69,6 +1181,62 @@ const Andromeda = {
    * The `args` property contains the command-line arguments passed to the program.
    */
   args: internal_get_cli_args(),
+  /**
+   * The `mainModule` property contains the `file:` URL of the script the runtime was started with,
+   * so scripts can locate files relative to themselves.
+   *
+   * @example
+   * ```ts
+   * const data = new URL("./data.json", Andromeda.mainModule);
+   * ```
+   */
+  mainModule: internal_main_module() as string,
+  /**
+   * internals namespace for introspecting the runtime, meant for tooling. Its functions throw
+   * `Andromeda.errors.PermissionDenied` unless the runtime runs with `--expose-internals`.
+   */
+  internals: {
+    /**
+     * The `ops` function lists the ops registered by the runtime extensions, with their arity,
+     * whether they return a Promise and the extension that owns them.
+     *
+     * @example
+     * ```ts
+     * const asyncOps = Andromeda.internals.ops().filter((op) => op.async);
+     * ```
+     */
+    ops(): Andromeda.internals.OpInfo[] {
+      return JSON.parse(internal_ops());
+    },
+
+    /**
+     * The `blockingPool` function reports the activity of the thread pool running the blocking
+     * work of async ops, such as file system calls and password hashing.
+     *
+     * @example
+     * ```ts
+     * const { queued, running } = Andromeda.internals.blockingPool();
+     * ```
+     */
+    blockingPool(): Andromeda.internals.BlockingPoolStats {
+      return JSON.parse(internal_blocking_pool_stats());
+    },
+  },
+  /**
+   * The `errors` property contains the error classes thrown by the runtime, so they can be caught by type.
+   *
+   * @example
+   * ```ts
+   * try {
+   *   Andromeda.readTextFileSync("missing.txt");
+   * } catch (error) {
+   *   if (error instanceof Andromeda.errors.NotFound) {
+   *     console.log("The file does not exist");
+   *   }
+   * }
+   * ```
+   */
+  errors: AndromedaErrors,
   /**
    * the readFileSync function reads a file from the filesystem.
    *
@@ -131,7 +1299,500 @@ const Andromeda = {
   },
 
   /**
-   * The `exit` function exits the program with an optional exit code.
+   * The `openFileSync` function opens a file in the file system and returns its resource ID.
+   * The file is opened for reading, and also for writing with `{ write: true }`, which
+   * exclusive range locks need.
+   *
+   * @example
+   * ```ts
+   * const rid = Andromeda.openFileSync("hello.txt");
+   * const writable = Andromeda.openFileSync("hello.txt", { write: true });
+   * ```
+   */
+  openFileSync(path: string, options?: { write?: boolean }): number {
+    return internal_open_file(path, options?.write === true);
+  },
+
+  /**
+   * The `closeSync` function closes a file given its resource ID.
+   *
+   * @example
+   * ```ts
+   * Andromeda.closeSync(rid);
+   * ```
+   */
+  closeSync(rid: number): void {
+    internal_close_file(rid);
+  },
+
+  /**
+   * The `lockSync` function acquires an advisory lock on the whole file, waiting until it is available.
+   * The lock is shared unless `exclusive` is `true`.
+   *
+   * @example
+   * ```ts
+   * const rid = Andromeda.openFileSync("hello.txt");
+   * Andromeda.lockSync(rid, true);
+   * ```
+   */
+  lockSync(rid: number, exclusive = false): void {
+    internal_lock_file(rid, exclusive === true);
+  },
+
+  /**
+   * The `lock` function acquires an advisory lock on the whole file without blocking the event loop.
+   * The lock is shared unless `exclusive` is `true`.
+   *
+   * @example
+   * ```ts
+   * const rid = Andromeda.openFileSync("hello.txt");
+   * await Andromeda.lock(rid, true);
+   * ```
+   */
+  lock(rid: number, exclusive = false): Promise<void> {
+    return internal_lock_file_async(rid, exclusive === true);
+  },
+
+  /**
+   * The `tryLockSync` function tries to acquire an advisory lock on the whole file without waiting.
+   * It returns `false` if the lock is held by somebody else.
+   *
+   * @example
+   * ```ts
+   * if (!Andromeda.tryLockSync(rid, true)) {
+   *   console.log("Somebody else is writing to the file");
+   * }
+   * ```
+   */
+  tryLockSync(rid: number, exclusive = false): boolean {
+    return internal_try_lock_file(rid, exclusive === true) === true;
+  },
+
+  /**
+   * The `unlockSync` function releases the advisory lock held on the whole file.
+   *
+   * @example
+   * ```ts
+   * Andromeda.unlockSync(rid);
+   * ```
+   */
+  unlockSync(rid: number): void {
+    internal_unlock_file(rid);
+  },
+
+  /**
+   * The `lockRangeSync` function acquires an advisory lock on `length` bytes of the file starting at `start`,
+   * waiting until it is available. A `length` of 0 locks until the end of the file.
+   * Exclusive locks need the file opened with `{ write: true }`.
+   * Outside of Linux, closing any resource of the file, even through `readTextFileSync`, releases the
+   * range locks of the process, and resources of the same process never conflict.
+   *
+   * @example
+   * ```ts
+   * const rid = Andromeda.openFileSync("hello.txt", { write: true });
+   * Andromeda.lockRangeSync(rid, 0, 512, true);
+   * ```
+   */
+  lockRangeSync(
+    rid: number,
+    start: number,
+    length: number,
+    exclusive = false,
+  ): void {
+    internal_lock_file_range(rid, exclusive === true, start, length, true);
+  },
+
+  /**
+   * The `tryLockRangeSync` function tries to acquire an advisory lock on a byte range of the file
+   * without waiting. It returns `false` if the range is locked by somebody else.
+   *
+   * @example
+   * ```ts
+   * if (!Andromeda.tryLockRangeSync(rid, 0, 512, true)) {
+   *   console.log("Somebody else is writing the header");
+   * }
+   * ```
+   */
+  tryLockRangeSync(
+    rid: number,
+    start: number,
+    length: number,
+    exclusive = false,
+  ): boolean {
+    return internal_lock_file_range(
+      rid,
+      exclusive === true,
+      start,
+      length,
+      false,
+    );
+  },
+
+  /**
+   * The `lockRange` function acquires an advisory lock on a byte range of the file without blocking
+   * the event loop. It has the same arguments as `lockRangeSync`.
+   *
+   * @example
+   * ```ts
+   * const rid = Andromeda.openFileSync("hello.txt", { write: true });
+   * await Andromeda.lockRange(rid, 0, 512, true);
+   * ```
+   */
+  lockRange(
+    rid: number,
+    start: number,
+    length: number,
+    exclusive = false,
+  ): Promise<void> {
+    return internal_lock_file_range_async(
+      rid,
+      exclusive === true,
+      start,
+      length,
+    );
+  },
+
+  /**
+   * The `unlockRangeSync` function releases an advisory lock on a byte range of the file.
+   *
+   * @example
+   * ```ts
+   * Andromeda.unlockRangeSync(rid, 0, 512);
+   * ```
+   */
+  unlockRangeSync(rid: number, start: number, length: number): void {
+    internal_unlock_file_range(rid, start, length);
+  },
+
+  /**
+   * The `chmod` function changes the permissions of a file using a unix mode.
+   * On Windows only the owner write bit is honored, by toggling the read-only flag.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.chmod("script.sh", 0o755);
+   * ```
+   */
+  chmod(path: string, mode: number): Promise<void> {
+    return internal_chmod(path, mode);
+  },
+
+  /**
+   * The `chown` function changes the owner user and group of a file. Pass `null` to leave an id unchanged.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.chown("hello.txt", 1000, null);
+   * ```
+   */
+  chown(path: string, uid: number | null, gid: number | null): Promise<void> {
+    return internal_chown(path, uid, gid);
+  },
+
+  /**
+   * The `utime` function changes the access and modification times of a file or a directory.
+   * Numbers are seconds since the unix epoch, and times the system can't represent throw a `RangeError`.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.utime("hello.txt", new Date(), new Date());
+   * ```
+   */
+  utime(path: string, atime: number | Date, mtime: number | Date): Promise<void> {
+    const toSeconds = (time: number | Date) =>
+      time instanceof Date ? time.getTime() / 1000 : time;
+    return internal_utime(path, toSeconds(atime), toSeconds(mtime));
+  },
+
+  /**
+   * The `link` function creates a hard link to a file.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.link("hello.txt", "hello-link.txt");
+   * ```
+   */
+  link(oldpath: string, newpath: string): Promise<void> {
+    return internal_link(oldpath, newpath);
+  },
+
+  /**
+   * The `readLink` function returns the target of a symbolic link.
+   *
+   * @example
+   * ```ts
+   * const target = await Andromeda.readLink("current");
+   * ```
+   */
+  readLink(path: string): Promise<string> {
+    return internal_read_link(path);
+  },
+
+  /**
+   * The `realPath` function resolves a path to its absolute form with every symbolic link resolved.
+   *
+   * @example
+   * ```ts
+   * const path = await Andromeda.realPath("./hello.txt");
+   * ```
+   */
+  realPath(path: string): Promise<string> {
+    return internal_real_path(path);
+  },
+
+  /**
+   * The `truncate` function truncates or extends a file to the given length in bytes, 0 by default.
+   * A length that isn't a finite number between 0 and `Number.MAX_SAFE_INTEGER` throws a `TypeError`.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.truncate("hello.txt", 5);
+   * ```
+   */
+  truncate(path: string, len = 0): Promise<void> {
+    return internal_truncate(path, len);
+  },
+
+  /**
+   * The `readJsonLines` function reads a newline-delimited JSON (NDJSON) file one line at a time,
+   * yielding the parsed value of each non-empty line. Only the current line is kept in memory.
+   *
+   * @example
+   * ```ts
+   * for await (const entry of Andromeda.readJsonLines("logs.ndjson")) {
+   *  console.log(entry.level, entry.message);
+   * }
+   * ```
+   */
+  readJsonLines(path: string): AsyncIteratorHelpers<unknown> & AsyncDisposable {
+    const rid = internal_open_lines(path);
+    let done = false;
+    let lineNumber = 0;
+    return Object.assign(Object.create(asyncIteratorPrototype), {
+      [Symbol.asyncIterator]() {
+        return this;
+      },
+      async next() {
+        while (!done) {
+          const line = await internal_next_line(rid);
+          if (line === undefined) {
+            done = true;
+            internal_close_lines(rid);
+            break;
+          }
+          lineNumber++;
+          if (line.trim() === "") {
+            continue;
+          }
+          try {
+            return { value: JSON.parse(line), done: false };
+          } catch (error) {
+            done = true;
+            internal_close_lines(rid);
+            throw new SyntaxError(
+              `${path}:${lineNumber}: ${(error as Error).message}`,
+            );
+          }
+        }
+        return { value: undefined, done: true };
+      },
+      async return() {
+        if (!done) {
+          done = true;
+          internal_close_lines(rid);
+        }
+        return { value: undefined, done: true };
+      },
+      async [Symbol.asyncDispose]() {
+        await this.return();
+      },
+    });
+  },
+
+  /**
+   * The `parseArgs` function parses command-line arguments, following the signature of Node's
+   * `util.parseArgs`. Options are `boolean`, `string` or `number`, can have a `short` alias, a
+   * `default`, and collect every occurrence with `multiple`. With `allowNegative`, `--no-<name>`
+   * sets a boolean option to `false`. `args` defaults to the arguments after the script path.
+   *
+   * @example
+   * ```ts
+   * const { values, positionals } = Andromeda.parseArgs({
+   *   args: ["-v", "--port", "8080", "serve"],
+   *   options: {
+   *     verbose: { type: "boolean", short: "v" },
+   *     port: { type: "number", default: 3000 },
+   *   },
+   *   allowPositionals: true,
+   * });
+   * ```
+   */
+  parseArgs(
+    config: Andromeda.ParseArgsConfig = {},
+  ): Andromeda.ParsedArgs {
+    const args = config.args ?? Andromeda.args.slice(2);
+    const options = config.options ?? {};
+    const strict = config.strict ?? true;
+    const allowPositionals = config.allowPositionals ?? !strict;
+    const allowNegative = config.allowNegative ?? false;
+
+    const values: Record<string, Andromeda.ParsedArgValue> = {};
+    const positionals: string[] = [];
+    const tokens: Andromeda.ParseArgsToken[] = [];
+
+    const shortNames: Record<string, string> = {};
+    for (const [name, option] of Object.entries(options)) {
+      if (
+        option.type !== "boolean" && option.type !== "string" &&
+        option.type !== "number"
+      ) {
+        throw new TypeError(
+          `The option "${name}" must have a type of "boolean", "string" or "number"`,
+        );
+      }
+      if (option.short !== undefined) {
+        if (option.short.length !== 1) {
+          throw new TypeError(
+            `The short alias of "${name}" must be a single character`,
+          );
+        }
+        shortNames[option.short] = name;
+      }
+    }
+
+    const convert = (name: string, rawName: string, value: string) => {
+      if (options[name]?.type !== "number") return value;
+      const number = Number(value);
+      if (value.trim() === "" || Number.isNaN(number)) {
+        throw new TypeError(`Option '${rawName}' expects a number, got '${value}'`);
+      }
+      return number;
+    };
+
+    const store = (
+      name: string,
+      rawName: string,
+      index: number,
+      value: string | undefined,
+      inlineValue: boolean | undefined,
+      negated = false,
+    ) => {
+      tokens.push({ kind: "option", name, rawName, index, value, inlineValue });
+      const option = options[name];
+      if (option === undefined) {
+        if (strict) {
+          throw new TypeError(
+            `Unknown option '${rawName}'${
+              allowPositionals ? "" : ". To specify a positional argument starting with a '-', place it after '--'"
+            }`,
+          );
+        }
+      } else if (option.type === "boolean" && value !== undefined && strict) {
+        throw new TypeError(`Option '${rawName}' does not take an argument`);
+      }
+
+      const parsed = value === undefined
+        ? !negated
+        : convert(name, rawName, value);
+      if (option?.multiple) {
+        const previous = values[name];
+        values[name] = [
+          ...(Array.isArray(previous) ? previous : []),
+          parsed,
+        ] as Andromeda.ParsedArgValue;
+      } else {
+        values[name] = parsed;
+      }
+    };
+
+    // Take the value of a string or number option from the next argument.
+    const takesValue = (name: string) =>
+      options[name] !== undefined && options[name].type !== "boolean";
+    const nextValue = (rawName: string, index: number) => {
+      if (index + 1 >= args.length) {
+        throw new TypeError(`Option '${rawName} <value>' argument missing`);
+      }
+      return args[index + 1];
+    };
+
+    for (let index = 0; index < args.length; index++) {
+      const arg = args[index];
+      if (arg === "--") {
+        tokens.push({ kind: "option-terminator", index });
+        for (let rest = index + 1; rest < args.length; rest++) {
+          positionals.push(args[rest]);
+          tokens.push({ kind: "positional", index: rest, value: args[rest] });
+        }
+        break;
+      }
+
+      if (arg.startsWith("--")) {
+        const equals = arg.indexOf("=");
+        if (equals !== -1) {
+          const name = arg.slice(2, equals);
+          store(name, `--${name}`, index, arg.slice(equals + 1), true);
+          continue;
+        }
+        let name = arg.slice(2);
+        if (
+          allowNegative && name.startsWith("no-") &&
+          options[name.slice(3)]?.type === "boolean"
+        ) {
+          store(name.slice(3), arg, index, undefined, undefined, true);
+          continue;
+        }
+        if (takesValue(name)) {
+          store(name, arg, index, nextValue(arg, index), false);
+          index++;
+        } else {
+          store(name, arg, index, undefined, undefined);
+        }
+        continue;
+      }
+
+      if (arg.startsWith("-") && arg !== "-") {
+        // A group of short options, where a string or number option takes the rest as its value.
+        for (let position = 1; position < arg.length; position++) {
+          const short = arg[position];
+          const name = shortNames[short] ?? short;
+          const rawName = `-${short}`;
+          if (!takesValue(name)) {
+            store(name, rawName, index, undefined, undefined);
+            continue;
+          }
+          if (position + 1 < arg.length) {
+            store(name, rawName, index, arg.slice(position + 1), true);
+          } else {
+            store(name, rawName, index, nextValue(rawName, index), false);
+            index++;
+          }
+          break;
+        }
+        continue;
+      }
+
+      if (strict && !allowPositionals) {
+        throw new TypeError(
+          `Unexpected argument '${arg}'. This command does not take positional arguments`,
+        );
+      }
+      positionals.push(arg);
+      tokens.push({ kind: "positional", index, value: arg });
+    }
+
+    for (const [name, option] of Object.entries(options)) {
+      if (values[name] === undefined && option.default !== undefined) {
+        values[name] = option.default;
+      }
+    }
+
+    return config.tokens
+      ? { values, positionals, tokens }
+      : { values, positionals };
+  },
+
+  /**
+   * The `exit` function exits the program with an optional exit code.
    *
    * @example
    * ```ts
@@ -142,8 +1803,57 @@ const Andromeda = {
     internal_exit(code || 0);
   },
 
+  /**
+   * The `memoryUsage` function reports the memory used by the runtime, in bytes. `external` is
+   * the memory held outside of the JavaScript heap on behalf of JavaScript objects, such as the
+   * buffers of open files, the compiled patterns of `Andromeda.regex` (estimated) and the keys
+   * of the caches.
+   *
+   * @example
+   * ```ts
+   * console.log(`${Andromeda.memoryUsage().external} bytes held by resources`);
+   * ```
+   */
+  memoryUsage(): Andromeda.MemoryUsage {
+    return { external: internal_external_memory() };
+  },
+
+  /**
+   * shutdown namespace for shutting down gracefully when the process receives SIGINT or SIGTERM,
+   * or Ctrl+C on Windows.
+   */
+  shutdown: {
+    /**
+     * Milliseconds the hooks get to settle before the process exits anyway.
+     */
+    timeout: 10000,
+
+    /**
+     * The `on` function registers a hook to run with the name of the signal, e.g. to stop
+     * accepting connections and wait for the in-flight requests. The hooks run concurrently and
+     * the process exits with the code of the signal once they all settled, or after
+     * `Andromeda.shutdown.timeout`. A second signal exits right away. Listening for the signals
+     * doesn't keep the event loop alive.
+     *
+     * @example
+     * ```ts
+     * Andromeda.shutdown.on(async (signal) => {
+     *   console.log(`Received ${signal}, closing the server`);
+     *   await server.close();
+     * });
+     * ```
+     */
+    on(hook: (signal: string) => unknown): void {
+      if (shutdownHooks.length === 0) {
+        internal_shutdown_listen(runShutdownHooks);
+      }
+      shutdownHooks.push(hook);
+    },
+  },
+
   /**
    * The `sleep` function returns a Promise to be resolved after the specified time in milliseconds.
+   * If a `signal` is given the Promise rejects with its reason as soon as it is aborted.
    *
    * @example
    * ```ts
@@ -152,8 +1862,837 @@ const Andromeda = {
    * });
    * ```
    */
-  sleep(duration: number): Promise<void> {
-    return internal_sleep(duration);
+  sleep(duration: number, options?: { signal?: AbortSignal }): Promise<void> {
+    const signal = options?.signal;
+    if (!signal) {
+      return internal_sleep(duration);
+    }
+    if (signal.aborted) {
+      return Promise.reject(signal.reason);
+    }
+    return new Promise((resolve, reject) => {
+      const onAbort = () => {
+        clearTimeout(id);
+        reject(signal.reason);
+      };
+      const id = setTimeout(() => {
+        signal.removeEventListener("abort", onAbort);
+        resolve();
+      }, duration);
+      signal.addEventListener("abort", onAbort, { once: true });
+    });
+  },
+
+  /**
+   * csv namespace for parsing and writing CSV and TSV.
+   */
+  csv: {
+    /**
+     * The `parse` function parses CSV text into rows of fields. With `header` the rows become objects
+     * keyed by the column names of the first row (or of the given array), and with `cast` numbers and
+     * booleans are converted from their text. Pass `delimiter: "\t"` for TSV.
+     *
+     * @example
+     * ```ts
+     * const rows = Andromeda.csv.parse("name,age\nAda,36", { header: true, cast: true });
+     * console.log(rows[0].age + 1);
+     * ```
+     */
+    parse(
+      text: string,
+      options: {
+        delimiter?: string;
+        quote?: string;
+        escape?: string;
+        header?: boolean | string[];
+        cast?: boolean;
+      } = {},
+    ): unknown[][] | Record<string, unknown>[] {
+      const rows: string[][] = JSON.parse(
+        internal_csv_parse(
+          text,
+          JSON.stringify({
+            delimiter: options.delimiter,
+            quote: options.quote,
+            escape: options.escape,
+          }),
+        ),
+      );
+      const convert = (field: string): unknown => {
+        if (!options.cast) return field;
+        if (field === "true") return true;
+        if (field === "false") return false;
+        if (/^[-+]?(\d+\.?\d*|\.\d+)([eE][-+]?\d+)?$/.test(field)) {
+          return Number(field);
+        }
+        return field;
+      };
+      if (!options.header) {
+        return rows.map((row) => row.map(convert));
+      }
+      const columns = Array.isArray(options.header)
+        ? options.header
+        : rows.shift() ?? [];
+      return rows.map((row) => {
+        const record: Record<string, unknown> = {};
+        columns.forEach((column, index) => {
+          record[column] = convert(row[index] ?? "");
+        });
+        return record;
+      });
+    },
+
+    /**
+     * The `stringify` function writes rows as CSV text, quoting fields when needed. Rows can be arrays
+     * or objects; for objects a header row is written with the `columns`, which default to the keys
+     * of the first row, unless `header` is `false`.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.csv.stringify([{ name: "Ada", age: 36 }]);
+     * ```
+     */
+    stringify(
+      data: unknown[][] | Record<string, unknown>[],
+      options: {
+        delimiter?: string;
+        quote?: string;
+        escape?: string;
+        header?: boolean;
+        columns?: string[];
+      } = {},
+    ): string {
+      const field = (value: unknown) =>
+        value === null || value === undefined ? "" : String(value);
+      let rows: string[][];
+      if (data.length > 0 && !Array.isArray(data[0])) {
+        const records = data as Record<string, unknown>[];
+        const columns = options.columns ?? Object.keys(records[0]);
+        rows = records.map((record) =>
+          columns.map((column) => field(record[column]))
+        );
+        if (options.header !== false) {
+          rows.unshift(columns);
+        }
+      } else {
+        rows = (data as unknown[][]).map((row) => row.map(field));
+      }
+      return internal_csv_stringify(
+        JSON.stringify(rows),
+        JSON.stringify({
+          delimiter: options.delimiter,
+          quote: options.quote,
+          escape: options.escape,
+        }),
+      );
+    },
+  },
+
+  /**
+   * toml namespace for parsing and writing TOML documents.
+   */
+  toml: {
+    /**
+     * The `parse` function parses a TOML document. Dates and times are returned as RFC 3339 strings.
+     *
+     * @example
+     * ```ts
+     * const config = Andromeda.toml.parse(Andromeda.readTextFileSync("config.toml"));
+     * ```
+     */
+    parse(text: string): Record<string, unknown> {
+      return JSON.parse(internal_toml_parse(text));
+    },
+
+    /**
+     * The `stringify` function writes an object as a TOML document.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.toml.stringify({ server: { port: 8080 } });
+     * ```
+     */
+    stringify(value: Record<string, unknown>): string {
+      return internal_toml_stringify(JSON.stringify(value));
+    },
+  },
+
+  /**
+   * yaml namespace for parsing and writing YAML documents.
+   */
+  yaml: {
+    /**
+     * The `parse` function parses a YAML document.
+     *
+     * @example
+     * ```ts
+     * const config = Andromeda.yaml.parse("name: andromeda\nversion: 1");
+     * ```
+     */
+    parse(text: string): unknown {
+      return JSON.parse(internal_yaml_parse(text));
+    },
+
+    /**
+     * The `stringify` function writes a value as a YAML document.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.yaml.stringify({ name: "andromeda", tags: ["js", "ts"] });
+     * ```
+     */
+    stringify(value: unknown): string {
+      return internal_yaml_stringify(JSON.stringify(value));
+    },
+  },
+
+  /**
+   * diff namespace for comparing texts and working with unified diffs.
+   */
+  diff: {
+    /**
+     * The `lines` function compares two texts line by line. Consecutive lines with the same change
+     * type are merged into one change.
+     *
+     * @example
+     * ```ts
+     * for (const change of Andromeda.diff.lines("a\nb\n", "a\nc\n")) {
+     *  console.log(change.type, change.value);
+     * }
+     * ```
+     */
+    lines(a: string, b: string): Andromeda.diff.Change[] {
+      return JSON.parse(internal_diff(a, b, "lines"));
+    },
+
+    /**
+     * The `words` function compares two texts word by word.
+     *
+     * @example
+     * ```ts
+     * const changes = Andromeda.diff.words("hello world", "hello there");
+     * ```
+     */
+    words(a: string, b: string): Andromeda.diff.Change[] {
+      return JSON.parse(internal_diff(a, b, "words"));
+    },
+
+    /**
+     * The `chars` function compares two texts character by character.
+     *
+     * @example
+     * ```ts
+     * const changes = Andromeda.diff.chars("kitten", "sitting");
+     * ```
+     */
+    chars(a: string, b: string): Andromeda.diff.Change[] {
+      return JSON.parse(internal_diff(a, b, "chars"));
+    },
+
+    /**
+     * The `unified` function writes a unified diff between two texts, with 3 lines of context by default.
+     *
+     * @example
+     * ```ts
+     * const patch = Andromeda.diff.unified("a\nb\n", "a\nc\n", { oldName: "a.txt", newName: "b.txt" });
+     * ```
+     */
+    unified(
+      a: string,
+      b: string,
+      options: { oldName?: string; newName?: string; context?: number } = {},
+    ): string {
+      return internal_diff_unified(
+        a,
+        b,
+        options.oldName ?? "a",
+        options.newName ?? "b",
+        options.context ?? 3,
+      );
+    },
+
+    /**
+     * The `apply` function applies a unified diff to a text. It throws if the patch context doesn't match.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.diff.apply("a\nb\n", patch);
+     * ```
+     */
+    apply(text: string, patch: string): string {
+      return internal_diff_apply(text, patch);
+    },
+  },
+
+  /**
+   * text namespace for laying out text in the terminal. Widths are in terminal columns: East Asian
+   * wide characters and emoji take two, ANSI escape sequences none.
+   */
+  text: {
+    /**
+     * The `width` function measures the width of the widest line of the text.
+     *
+     * @example
+     * ```ts
+     * console.log(Andromeda.text.width("日本語")); // 6
+     * ```
+     */
+    width(text: string): number {
+      return internal_text_width(String(text));
+    },
+
+    /**
+     * The `truncate` function cuts the text to fit in `width` columns, ending it with `tail`
+     * (`"…"` by default) if anything was cut. Colors are kept.
+     *
+     * @example
+     * ```ts
+     * console.log(Andromeda.text.truncate("こんにちは世界", 9)); // "こんにち…"
+     * ```
+     */
+    truncate(text: string, width: number, options?: { tail?: string }): string {
+      return internal_text_truncate(String(text), width, options?.tail ?? "…");
+    },
+
+    /**
+     * The `wrap` function wraps the text in lines of at most `width` columns, breaking at
+     * whitespace when possible. Characters and emoji are never split.
+     *
+     * @example
+     * ```ts
+     * for (const line of Andromeda.text.wrap(description, 40)) {
+     *   console.log(line);
+     * }
+     * ```
+     */
+    wrap(text: string, width: number): string[] {
+      return JSON.parse(internal_text_wrap(String(text), width));
+    },
+
+    /**
+     * The `columns` function returns the width of the terminal, or `undefined` if the output
+     * isn't a terminal.
+     */
+    columns(): number | undefined {
+      return internal_terminal_columns();
+    },
+  },
+
+  /**
+   * html namespace for escaping text and sanitizing untrusted HTML.
+   */
+  html: {
+    /**
+     * The `escape` function escapes `&`, `<`, `>`, quotes and backticks so the text can be put
+     * in HTML text or a quoted attribute value.
+     *
+     * @example
+     * ```ts
+     * console.log(Andromeda.html.escape("<b>Tom & Jerry</b>"));
+     * ```
+     */
+    escape(text: string): string {
+      return internal_html_escape(String(text));
+    },
+
+    /**
+     * The `sanitize` function removes every tag, attribute and URL scheme that isn't in the
+     * allowlist, along with scripts and styles. The default allowlist keeps common formatting tags.
+     *
+     * @example
+     * ```ts
+     * const clean = Andromeda.html.sanitize('<a href="javascript:alert(1)" title="x">hi</a><script>x</script>', {
+     *   tags: ["a", "b", "i"],
+     *   attributes: { a: ["href", "title"] },
+     * });
+     * ```
+     */
+    sanitize(html: string, options: Andromeda.html.SanitizeOptions = {}): string {
+      return internal_html_sanitize(String(html), JSON.stringify(options));
+    },
+
+    /**
+     * The `raw` function marks HTML as safe, so templates insert it without escaping it.
+     */
+    raw(html: string): Andromeda.html.SafeHtml {
+      return new SafeHtml(String(html));
+    },
+
+    /**
+     * The `template` tag builds HTML from a template literal, escaping every interpolation unless
+     * it is safe HTML. Arrays are joined, and `null`, `undefined` and `false` are left out.
+     *
+     * @example
+     * ```ts
+     * const { template: html } = Andromeda.html;
+     * const items = ["<apple>", "pear"].map((item) => html`<li>${item}</li>`);
+     * console.log(String(html`<ul>${items}</ul>`));
+     * ```
+     */
+    template(
+      strings: TemplateStringsArray,
+      ...values: unknown[]
+    ): Andromeda.html.SafeHtml {
+      let output = strings[0];
+      for (let i = 0; i < values.length; i++) {
+        output += htmlChunk(values[i]) + strings[i + 1];
+      }
+      return new SafeHtml(output);
+    },
+
+    /**
+     * The `stream` tag is like `template`, but returns the HTML as an async iterator of chunks.
+     * Interpolations can be promises and async iterables, whose values are escaped one by one as
+     * they arrive, so a page can be sent while it's still being rendered.
+     *
+     * @example
+     * ```ts
+     * const { template: html, stream } = Andromeda.html;
+     * const page = stream`<h1>${title}</h1><ul>${rows.map((row) => html`<li>${row}</li>`)}</ul>`;
+     * for await (const chunk of page) {
+     *   Andromeda.stdout.write(chunk);
+     * }
+     * ```
+     */
+    stream(
+      strings: TemplateStringsArray,
+      ...values: unknown[]
+    ): AsyncIteratorHelpers<string> {
+      // Even positions are the literal strings, odd ones the interpolated values.
+      const end = strings.length * 2 - 1;
+      let position = 0;
+      let inner: AsyncIterator<unknown> | undefined;
+      const stream = Object.create(asyncIteratorPrototype);
+      stream.next = async (): Promise<IteratorResult<string>> => {
+        while (true) {
+          if (inner) {
+            const result = await inner.next();
+            if (!result.done) return { value: htmlChunk(result.value), done: false };
+            inner = undefined;
+          }
+          if (position >= end) return { value: undefined, done: true };
+          const index = position++;
+          if (index % 2 === 0) {
+            const chunk = strings[index / 2];
+            if (chunk) return { value: chunk, done: false };
+            continue;
+          }
+          const value = await values[(index - 1) / 2];
+          if ((value as any)?.[Symbol.asyncIterator]) {
+            inner = (value as AsyncIterable<unknown>)[Symbol.asyncIterator]();
+            continue;
+          }
+          const chunk = htmlChunk(value);
+          if (chunk) return { value: chunk, done: false };
+        }
+      };
+      stream.return = async (): Promise<IteratorResult<string>> => {
+        position = end;
+        const pending = inner;
+        inner = undefined;
+        await pending?.return?.();
+        return { value: undefined, done: true };
+      };
+      return stream;
+    },
+  },
+
+  /**
+   * regex namespace for matching large inputs with a linear-time regex engine. It doesn't support
+   * look-around or backreferences, in exchange for never backtracking.
+   */
+  regex: {
+    /**
+     * The `compile` function compiles a pattern with optional flags: `i` (case insensitive),
+     * `m` (multi-line), `s` (`.` matches new lines) and `x` (ignore whitespace). Call `close`, or
+     * dispose it, once the pattern is no longer needed.
+     *
+     * @example
+     * ```ts
+     * const email = Andromeda.regex.compile("(?P<user>\\w+)@(?P<host>[\\w.]+)", "i");
+     * console.log(email.find("mail ada@example.com")?.groups.host);
+     * email.close();
+     * ```
+     */
+    compile(pattern: string, flags = ""): Andromeda.regex.Regex {
+      const rid = internal_regex_compile(pattern, flags);
+      let closed = false;
+      return {
+        pattern,
+        flags,
+        find(text: string): Andromeda.regex.Match | null {
+          return JSON.parse(internal_regex_find(rid, text));
+        },
+        findAll(text: string): Andromeda.regex.Match[] {
+          return JSON.parse(internal_regex_find_all(rid, text));
+        },
+        replace(text: string, replacement: string): string {
+          return internal_regex_replace(rid, text, replacement, false);
+        },
+        replaceAll(text: string, replacement: string): string {
+          return internal_regex_replace(rid, text, replacement, true);
+        },
+        split(text: string, limit?: number): string[] {
+          return JSON.parse(internal_regex_split(rid, text, limit));
+        },
+        close(): void {
+          if (closed) return;
+          closed = true;
+          internal_regex_close(rid);
+        },
+        [Symbol.dispose](): void {
+          this.close();
+        },
+      };
+    },
+  },
+
+  /**
+   * caches namespace for in-memory caches.
+   */
+  caches: {
+    /**
+     * The `memory` function creates a cache keeping at most `capacity` entries, evicting the least
+     * recently used one when full, for `ttl` milliseconds each. Both are unlimited by default.
+     * Call `close`, or dispose it, to free the cache and its values.
+     *
+     * @example
+     * ```ts
+     * const users = Andromeda.caches.memory<User>({ capacity: 1000, ttl: 60_000 });
+     * const user = await users.getOrInsertWith(id, () => loadUser(id));
+     * ```
+     */
+    memory<V>(
+      options: { capacity?: number; ttl?: number } = {},
+    ): Andromeda.caches.MemoryCache<V> {
+      const ttl = (value: number) => value === Infinity ? 0 : value;
+      const rid = internal_cache_create(
+        options.capacity ?? 0,
+        ttl(options.ttl ?? 0),
+      );
+      // Values are boxed so a cached `undefined` can be told apart from a missing key.
+      const lookup = (key: string): [V] | undefined =>
+        internal_cache_get(rid, String(key));
+      const pending = new Map<string, Promise<V>>();
+      let closed = false;
+      return {
+        get(key: string): V | undefined {
+          return lookup(key)?.[0];
+        },
+        has(key: string): boolean {
+          return lookup(key) !== undefined;
+        },
+        set(key: string, value: V, options?: { ttl?: number }): void {
+          internal_cache_set(
+            rid,
+            String(key),
+            [value],
+            options?.ttl === undefined ? undefined : ttl(options.ttl),
+          );
+        },
+        delete(key: string): boolean {
+          return internal_cache_delete(rid, String(key));
+        },
+        clear(): void {
+          internal_cache_clear(rid);
+        },
+        get size(): number {
+          return internal_cache_size(rid);
+        },
+        getOrInsertWith(
+          key: string,
+          compute: (key: string) => V | Promise<V>,
+        ): Promise<V> {
+          key = String(key);
+          const cached = lookup(key);
+          if (cached) return Promise.resolve(cached[0]);
+          // Concurrent calls for the same key share the computation instead of running it again.
+          const running = pending.get(key);
+          if (running) return running;
+          const promise = new Promise<V>((resolve) => resolve(compute(key)))
+            .then(
+              (value) => {
+                pending.delete(key);
+                if (!closed) this.set(key, value);
+                return value;
+              },
+              (error) => {
+                pending.delete(key);
+                throw error;
+              },
+            );
+          pending.set(key, promise);
+          return promise;
+        },
+        close(): void {
+          if (closed) return;
+          closed = true;
+          internal_cache_close(rid);
+        },
+        [Symbol.dispose](): void {
+          this.close();
+        },
+      };
+    },
+  },
+
+  /**
+   * The `memoize` function caches the results of an async function in a memory cache. Calls with
+   * the same key share one call of `fn` while it's running, and failures aren't cached. The key is
+   * the JSON of the arguments unless a `key` function is given.
+   *
+   * @example
+   * ```ts
+   * const getWeather = Andromeda.memoize(
+   *   (city: string) => fetchWeather(city),
+   *   { ttl: 10 * 60_000 },
+   * );
+   * ```
+   */
+  memoize<A extends unknown[], V>(
+    fn: (...args: A) => V | Promise<V>,
+    options: {
+      capacity?: number;
+      ttl?: number;
+      key?: (...args: A) => string;
+    } = {},
+  ): (...args: A) => Promise<V> {
+    const cache = Andromeda.caches.memory<V>(options);
+    const key = options.key ?? ((...args: A) => JSON.stringify(args));
+    return (...args: A) => cache.getOrInsertWith(key(...args), () => fn(...args));
+  },
+
+  /**
+   * passwords namespace for hashing and verifying passwords.
+   */
+  passwords: {
+    /**
+     * The `hash` function hashes a password with a random salt, using argon2id unless another
+     * `algorithm` is given. The returned string holds the algorithm and its parameters.
+     *
+     * @example
+     * ```ts
+     * Andromeda.passwords.hash("hunter2").then((hash) => console.log(hash));
+     * ```
+     */
+    hash(
+      password: string,
+      options?: { algorithm?: "argon2id" | "bcrypt" | "scrypt" },
+    ): Promise<string> {
+      const algorithm = options?.algorithm ?? "argon2id";
+      if (
+        algorithm !== "argon2id" && algorithm !== "bcrypt" &&
+        algorithm !== "scrypt"
+      ) {
+        return Promise.reject(
+          new TypeError(`Unsupported password hashing algorithm: ${algorithm}`),
+        );
+      }
+      return internal_password_hash(password, algorithm);
+    },
+
+    /**
+     * The `verify` function checks a password against a hash returned by `hash`.
+     *
+     * @example
+     * ```ts
+     * Andromeda.passwords.verify("hunter2", hash).then((ok) => {
+     *  if (ok) console.log("Welcome back!");
+     * });
+     * ```
+     */
+    verify(password: string, hash: string): Promise<boolean> {
+      return internal_password_verify(password, hash);
+    },
+  },
+
+  /**
+   * smtp namespace for sending email through an SMTP server.
+   */
+  smtp: {
+    /**
+     * The `send` function sends a message and resolves to the response of the server. The
+     * connection uses implicit TLS unless `tls` is `"starttls"` or `"none"`, and authenticates
+     * with AUTH PLAIN or LOGIN when a username is given. Attachments are strings or bytes.
+     *
+     * @example
+     * ```ts
+     * await Andromeda.smtp.send(
+     *   { hostname: "smtp.example.com", username: "alerts", password: Andromeda.env.get("SMTP_PASSWORD") },
+     *   {
+     *     from: "Alerts <alerts@example.com>",
+     *     to: ["ops@example.com"],
+     *     subject: "Disk almost full",
+     *     text: "Only 2% left on /var.",
+     *     attachments: [{ filename: "df.txt", content: report }],
+     *   },
+     * );
+     * ```
+     */
+    send(
+      transport: Andromeda.smtp.TransportOptions,
+      message: Andromeda.smtp.Message,
+    ): Promise<string> {
+      const tls = transport.tls ?? "implicit";
+      if (tls !== "implicit" && tls !== "starttls" && tls !== "none") {
+        return Promise.reject(new TypeError(`Unsupported SMTP TLS mode: ${tls}`));
+      }
+      const toList = (value?: string | string[]) =>
+        value === undefined ? [] : Array.isArray(value) ? value : [value];
+      return internal_smtp_send(
+        JSON.stringify({
+          hostname: transport.hostname,
+          port: transport.port,
+          tls,
+          username: transport.username,
+          password: transport.password,
+          mechanism: transport.mechanism,
+          timeout: transport.timeout,
+        }),
+        JSON.stringify({
+          from: message.from,
+          to: toList(message.to),
+          cc: toList(message.cc),
+          bcc: toList(message.bcc),
+          replyTo: message.replyTo,
+          subject: message.subject,
+          text: message.text,
+          html: message.html,
+          attachments: (message.attachments ?? []).map((attachment) => ({
+            filename: attachment.filename,
+            contentType: attachment.contentType ??
+              (typeof attachment.content === "string"
+                ? "text/plain; charset=utf-8"
+                : "application/octet-stream"),
+            content: typeof attachment.content === "string"
+              ? attachment.content
+              : Array.from(attachment.content),
+          })),
+        }),
+      );
+    },
+  },
+
+  /**
+   * prompts namespace for interactive terminal prompts.
+   */
+  prompts: {
+    /**
+     * The `select` function asks to pick one of the options and returns its value. Options are
+     * either plain values or `{ value, label, hint }` objects.
+     *
+     * @example
+     * ```ts
+     * const color = Andromeda.prompts.select("Pick a color", [
+     *  { value: "#f00", label: "Red" },
+     *  { value: "#0f0", label: "Green", hint: "recommended" },
+     * ]);
+     * ```
+     */
+    select<T>(
+      message: string,
+      options: (T | { value: T; label?: string; hint?: string })[],
+      config?: { initial?: T },
+    ): T {
+      const choices = promptChoices(options);
+      const initial = choices.findIndex((choice) =>
+        choice.value === config?.initial
+      );
+      const index = internal_prompt_select(
+        message,
+        JSON.stringify(choices.map(({ label, hint }) => ({ label, hint }))),
+        Math.max(initial, 0),
+      );
+      return choices[index].value;
+    },
+
+    /**
+     * The `multiselect` function asks to pick any of the options and returns their values. Set
+     * `required` to refuse an empty selection.
+     *
+     * @example
+     * ```ts
+     * const toppings = Andromeda.prompts.multiselect("Toppings", ["cheese", "ham", "olives"], {
+     *  initial: ["cheese"],
+     * });
+     * ```
+     */
+    multiselect<T>(
+      message: string,
+      options: (T | { value: T; label?: string; hint?: string })[],
+      config?: { initial?: T[]; required?: boolean },
+    ): T[] {
+      const choices = promptChoices(options);
+      const initial: number[] = [];
+      choices.forEach((choice, index) => {
+        if (config?.initial?.includes(choice.value)) initial.push(index);
+      });
+      const indexes: number[] = JSON.parse(internal_prompt_multiselect(
+        message,
+        JSON.stringify(choices.map(({ label, hint }) => ({ label, hint }))),
+        config?.required ?? false,
+        JSON.stringify(initial),
+      ));
+      return indexes.map((index) => choices[index].value);
+    },
+
+    /**
+     * The `password` function asks for a secret without echoing it.
+     *
+     * @example
+     * ```ts
+     * const token = Andromeda.prompts.password("API token");
+     * ```
+     */
+    password(message: string): string {
+      return internal_prompt_password(message);
+    },
+
+    /**
+     * The `confirm` function asks a yes or no question.
+     *
+     * @example
+     * ```ts
+     * if (Andromeda.prompts.confirm("Overwrite the file?", { initial: false })) {
+     *  Andromeda.writeTextFileSync("out.txt", "");
+     * }
+     * ```
+     */
+    confirm(message: string, config?: { initial?: boolean }): boolean {
+      return internal_prompt_confirm(message, config?.initial ?? true);
+    },
+
+    /**
+     * The `spinner` function shows a spinner with a message until it is stopped.
+     *
+     * @example
+     * ```ts
+     * const spinner = Andromeda.prompts.spinner("Downloading");
+     * setTimeout(() => spinner.stop("Downloaded"), 1000);
+     * ```
+     */
+    spinner(message: string): Andromeda.prompts.Spinner {
+      const rid = internal_spinner_start(message);
+      let stopped = false;
+      return {
+        message(message: string) {
+          internal_spinner_message(rid, message);
+        },
+        stop(message = "") {
+          if (stopped) return;
+          stopped = true;
+          internal_spinner_stop(rid, message, false);
+        },
+        error(message: string) {
+          if (stopped) return;
+          stopped = true;
+          internal_spinner_stop(rid, message, true);
+        },
+        [Symbol.dispose]() {
+          this.stop();
+        },
+      };
+    },
   },
 
   /**
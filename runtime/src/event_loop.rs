@@ -1,4 +1,10 @@
-use crate::ext::{interval::IntervalId, timeout::TimeoutId};
+use andromeda_core::HostData;
+use nova_vm::ecmascript::execution::{agent::JsError, Agent};
+
+use crate::{
+    ext::{immediate::ImmediateId, interval::IntervalId, timeout::TimeoutId},
+    Redactor, ShutdownSignal,
+};
 
 pub enum RuntimeMacroTask {
     /// Run an interval.
@@ -9,4 +15,22 @@ pub enum RuntimeMacroTask {
     RunAndClearTimeout(TimeoutId),
     /// Stop a timeout from running no further.
     ClearTimeout(TimeoutId),
+    /// Run an immediate, queued with [HostData::queue_immediate].
+    RunImmediate(ImmediateId),
+    /// Run the animation frame callbacks requested so far.
+    RunAnimationFrames,
+    /// Run the shutdown hooks after the process received the signal.
+    RunShutdownListener(ShutdownSignal),
+}
+
+/// Print an exception nobody caught, e.g. thrown by the main script or by a timer callback and
+/// returned by the event loop, and exit the process.
+pub fn exit_with_uncaught_exception(agent: &mut Agent, error: JsError) -> ! {
+    let message = error.value().string_repr(agent);
+    let message = message.as_str(agent);
+    let message = HostData::<RuntimeMacroTask>::from_agent(agent)
+        .try_with_storage(|redactor: &Redactor| redactor.redact(message).into_owned())
+        .unwrap_or_else(|| message.to_string());
+    eprintln!("Uncaught exception: {}", message);
+    std::process::exit(1);
 }
@@ -0,0 +1,357 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+use andromeda_core::{
+    extension_op, webidl::to_double, Extension, HostData, OpError, OpsStorage, ResourceTable, Rid,
+};
+use nova_vm::{
+    ecmascript::{
+        builtins::ArgumentsList,
+        execution::{Agent, JsResult},
+        types::{Global, Value},
+    },
+    SmallInteger,
+};
+
+use crate::RuntimeMacroTask;
+
+struct CacheExtResources {
+    caches: ResourceTable<MemoryCache>,
+}
+
+struct CacheEntry {
+    value: Global<Value>,
+    expires_at: Option<Instant>,
+    /// Position in [MemoryCache::recency], bumped on every access.
+    tick: u64,
+}
+
+impl CacheEntry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// In-memory cache evicting the least recently used entry once `capacity` is reached, and
+/// entries older than their time to live when they are accessed.
+///
+/// Values are JavaScript values, so evicted ones are handed back to the caller to be released
+/// with the agent, which isn't available while the storage is borrowed. They live in the
+/// JavaScript heap, while the keys and the bookkeeping of the entries are counted in `bytes`
+/// to be reported as external memory.
+struct MemoryCache {
+    /// Maximum number of entries, 0 for no limit.
+    capacity: usize,
+    /// Default time to live of the entries, `None` to keep them until they are evicted.
+    ttl: Option<Duration>,
+    entries: HashMap<String, CacheEntry>,
+    recency: BTreeMap<u64, String>,
+    next_tick: u64,
+    /// Bytes held by the entries outside of the JavaScript heap, see [entry_size].
+    bytes: usize,
+}
+
+impl MemoryCache {
+    fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            next_tick: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Remove the entry of the key to read it, along with an expired value to release.
+    /// Put it back with [MemoryCache::restore].
+    fn take(&mut self, key: &str, now: Instant) -> (Option<CacheEntry>, Option<Global<Value>>) {
+        let Some(entry) = self.remove(key) else {
+            return (None, None);
+        };
+        if entry.is_expired(now) {
+            (None, Some(entry.value))
+        } else {
+            (Some(entry), None)
+        }
+    }
+
+    fn restore(&mut self, key: String, mut entry: CacheEntry) {
+        entry.tick = self.bump(&key);
+        self.bytes += entry_size(&key);
+        self.entries.insert(key, entry);
+    }
+
+    /// Insert a value expiring at the given instant, returning the replaced and evicted values
+    /// to release.
+    fn insert(
+        &mut self,
+        key: String,
+        value: Global<Value>,
+        expires_at: Option<Instant>,
+        now: Instant,
+    ) -> Vec<Global<Value>> {
+        let mut released: Vec<Global<Value>> = self
+            .remove(&key)
+            .map(|entry| entry.value)
+            .into_iter()
+            .collect();
+        if self.capacity > 0 && self.entries.len() >= self.capacity {
+            released.extend(self.sweep(now));
+        }
+        while self.capacity > 0 && self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.bytes -= entry_size(&oldest);
+                released.push(entry.value);
+            }
+        }
+
+        let tick = self.bump(&key);
+        self.bytes += entry_size(&key);
+        self.entries.insert(
+            key,
+            CacheEntry {
+                value,
+                expires_at,
+                tick,
+            },
+        );
+        released
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.tick);
+        self.bytes -= entry_size(key);
+        Some(entry)
+    }
+
+    /// Remove every expired entry, returning their values to release.
+    fn sweep(&mut self, now: Instant) -> Vec<Global<Value>> {
+        let expired: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.is_expired(now))
+            .map(|(key, _)| key.clone())
+            .collect();
+        expired
+            .iter()
+            .filter_map(|key| self.remove(key))
+            .map(|entry| entry.value)
+            .collect()
+    }
+
+    fn clear(&mut self) -> Vec<Global<Value>> {
+        self.recency.clear();
+        self.bytes = 0;
+        self.entries.drain().map(|(_, entry)| entry.value).collect()
+    }
+
+    fn bump(&mut self, key: &str) -> u64 {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.recency.insert(tick, key.to_string());
+        tick
+    }
+}
+
+/// Cache extension for Andromeda.
+/// This extension provides in-memory LRU caches with a time to live. The single-flight logic of
+/// `getOrInsertWith` lives in JavaScript, on top of these ops.
+#[derive(Default)]
+pub struct CacheExt;
+
+impl CacheExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "cache",
+            ops: vec![
+                extension_op!("internal_cache_create", Self::internal_cache_create, 2),
+                extension_op!("internal_cache_close", Self::internal_cache_close, 1),
+                extension_op!("internal_cache_get", Self::internal_cache_get, 2),
+                extension_op!("internal_cache_set", Self::internal_cache_set, 4),
+                extension_op!("internal_cache_delete", Self::internal_cache_delete, 2),
+                extension_op!("internal_cache_clear", Self::internal_cache_clear, 1),
+                extension_op!("internal_cache_size", Self::internal_cache_size, 1),
+            ],
+            storage: Some(Box::new(|storage: &mut OpsStorage| {
+                storage.insert(CacheExtResources {
+                    caches: ResourceTable::<MemoryCache>::new(),
+                });
+            })),
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Create a cache holding at most `capacity` entries (0 for no limit) for `ttl` milliseconds
+    /// (0 to keep them until evicted), and return its Rid.
+    fn internal_cache_create(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let capacity = args.get(0).to_uint32(agent)? as usize;
+        let ttl = to_ttl(agent, args.get(1))?;
+
+        let rid = HostData::<RuntimeMacroTask>::from_agent(agent).with_storage(
+            |resources: &CacheExtResources| resources.caches.push(MemoryCache::new(capacity, ttl)),
+        );
+
+        Ok(Value::Integer(SmallInteger::from(rid.index())))
+    }
+
+    /// Free a cache and every value it holds.
+    fn internal_cache_close(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+
+        let cache = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .with_storage(|resources: &CacheExtResources| resources.caches.remove(rid));
+
+        match cache {
+            Some(mut cache) => {
+                HostData::<RuntimeMacroTask>::from_agent(agent)
+                    .external_memory
+                    .free(cache.bytes);
+                release(agent, cache.clear());
+                Ok(Value::Undefined)
+            }
+            None => Err(OpError::bad_resource().throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+
+    /// Get the value of a key, or `undefined` if it's missing or expired.
+    fn internal_cache_get(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let key = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let now = Instant::now();
+
+        let (entry, expired) = Self::with_cache(agent, args.get(0), |cache| cache.take(&key, now))?;
+        release(agent, expired);
+
+        let Some(entry) = entry else {
+            return Ok(Value::Undefined);
+        };
+        let value = entry.value.get(agent);
+        Self::with_cache(agent, args.get(0), |cache| cache.restore(key, entry))?;
+        Ok(value)
+    }
+
+    /// Set the value of a key, with its own time to live in milliseconds if given.
+    fn internal_cache_set(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let key = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let ttl = match args.get(3) {
+            Value::Undefined => None,
+            ttl => Some(to_ttl(agent, ttl)?),
+        };
+        let value = Global::new(agent, args.get(2));
+        let now = Instant::now();
+
+        let released = Self::with_cache(agent, args.get(0), |cache| {
+            let expires_at = ttl
+                .unwrap_or(cache.ttl)
+                .and_then(|ttl| now.checked_add(ttl));
+            cache.insert(key, value, expires_at, now)
+        })?;
+        release(agent, released);
+        Ok(Value::Undefined)
+    }
+
+    /// Delete a key, returning whether it was in the cache.
+    fn internal_cache_delete(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let key = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let now = Instant::now();
+
+        let entry = Self::with_cache(agent, args.get(0), |cache| cache.remove(&key))?;
+        let found = entry.as_ref().is_some_and(|entry| !entry.is_expired(now));
+        release(agent, entry.map(|entry| entry.value));
+        Ok(Value::Boolean(found))
+    }
+
+    /// Delete every key.
+    fn internal_cache_clear(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let released = Self::with_cache(agent, args.get(0), MemoryCache::clear)?;
+        release(agent, released);
+        Ok(Value::Undefined)
+    }
+
+    /// Count the entries that haven't expired.
+    fn internal_cache_size(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let now = Instant::now();
+        let (size, expired) = Self::with_cache(agent, args.get(0), |cache| {
+            let expired = cache.sweep(now);
+            (cache.entries.len(), expired)
+        })?;
+        release(agent, expired);
+        Ok(Value::from_f64(agent, size as f64))
+    }
+
+    fn with_cache<R>(
+        agent: &mut Agent,
+        rid: Value,
+        run: impl FnOnce(&mut MemoryCache) -> R,
+    ) -> JsResult<R> {
+        let rid = Rid::from_index(rid.to_uint32(agent)?);
+
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let result = host_data.with_storage(|resources: &CacheExtResources| {
+            resources.caches.get_mut(rid).map(|mut cache| {
+                let bytes = cache.bytes;
+                let result = run(&mut cache);
+                if cache.bytes > bytes {
+                    host_data.external_memory.allocate(cache.bytes - bytes);
+                } else {
+                    host_data.external_memory.free(bytes - cache.bytes);
+                }
+                result
+            })
+        });
+
+        result.ok_or_else(|| OpError::bad_resource().throw::<RuntimeMacroTask>(agent))
+    }
+}
+
+/// Bytes held outside of the JavaScript heap by the entry of a key, which is stored twice.
+fn entry_size(key: &str) -> usize {
+    2 * (key.len() + std::mem::size_of::<String>())
+        + std::mem::size_of::<CacheEntry>()
+        + std::mem::size_of::<u64>()
+}
+
+/// Convert a time to live in milliseconds, where 0 means no expiration.
+fn to_ttl(agent: &mut Agent, value: Value) -> JsResult<Option<Duration>> {
+    let ttl = to_double(agent, value, "The time to live")?;
+    if ttl > 0.0 {
+        Ok(Duration::try_from_secs_f64(ttl / 1000.0).ok())
+    } else {
+        Ok(None)
+    }
+}
+
+/// Release the JavaScript values removed from a cache.
+fn release(agent: &mut Agent, values: impl IntoIterator<Item = Global<Value>>) {
+    for value in values {
+        value.take(agent);
+    }
+}
@@ -1,12 +1,21 @@
-use std::io::{stdout, Write};
+mod redact;
+mod width;
 
-use andromeda_core::{Extension, ExtensionOp};
+use std::{
+    env,
+    io::{stdout, Write},
+};
+
+use andromeda_core::{extension_op, Extension, HostData, OpError, OpsStorage};
 use nova_vm::ecmascript::{
     builtins::ArgumentsList,
     execution::{Agent, JsResult},
     types::Value,
 };
 
+use crate::RuntimeMacroTask;
+pub use redact::{Redactor, REDACT_ENV_VAR};
+
 #[derive(Default)]
 pub struct ConsoleExt;
 
@@ -15,36 +24,54 @@ impl ConsoleExt {
         Extension {
             name: "console",
             ops: vec![
-                ExtensionOp::new("internal_read", Self::internal_read, 1),
-                ExtensionOp::new("internal_read_line", Self::internal_read_line, 1),
-                ExtensionOp::new("internal_write", Self::internal_write, 1),
-                ExtensionOp::new("internal_write_line", Self::internal_write_line, 1),
-                ExtensionOp::new("internal_print", Self::internal_print, 1),
-                ExtensionOp::new("internal_exit", Self::internal_exit, 1),
+                extension_op!("internal_read", Self::internal_read, 1),
+                extension_op!("internal_read_line", Self::internal_read_line, 1),
+                extension_op!("internal_write", Self::internal_write, 1),
+                extension_op!("internal_write_line", Self::internal_write_line, 1),
+                extension_op!("internal_print", Self::internal_print, 1),
+                extension_op!("internal_exit", Self::internal_exit, 1),
+                extension_op!("internal_text_width", Self::internal_text_width, 1),
+                extension_op!("internal_text_truncate", Self::internal_text_truncate, 3),
+                extension_op!("internal_text_wrap", Self::internal_text_wrap, 2),
+                extension_op!(
+                    "internal_terminal_columns",
+                    Self::internal_terminal_columns,
+                    0
+                ),
+                extension_op!("internal_no_color", Self::internal_no_color, 0),
             ],
-            storage: None,
+            storage: Some(Box::new(|storage: &mut OpsStorage| {
+                storage.insert(Redactor::from_env());
+            })),
             files: vec![include_str!("./mod.ts")],
+            deps: &[],
+            cfg: None,
         }
     }
 
     /// Print function that prints the first argument to the console.
     fn internal_print(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        stdout()
-            .write_all(args[0].to_string(agent)?.as_str(agent).as_bytes())
-            .unwrap();
-        stdout().flush().unwrap();
+        let message = args.get(0).to_string(agent)?;
+        let message = Self::redact(agent, message.as_str(agent));
+        let mut stdout = stdout();
+        stdout
+            .write_all(message.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
         Ok(Value::Undefined)
     }
 
     /// Exit the process with the given exit code.
     pub fn internal_exit(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        std::process::exit(args[0].to_int32(agent)?);
+        std::process::exit(args.get(0).to_int32(agent)?);
     }
 
     /// Internal read for reading from the console.
     pub fn internal_read(agent: &mut Agent, _this: Value, _args: ArgumentsList) -> JsResult<Value> {
         let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
+        std::io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
         Ok(Value::from_string(agent, input.trim_end().to_string()))
     }
 
@@ -55,14 +82,17 @@ impl ConsoleExt {
         _args: ArgumentsList,
     ) -> JsResult<Value> {
         let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
+        std::io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
         Ok(Value::from_string(agent, input.trim_end().to_string()))
     }
 
     /// Internal write for writing to the console.
     pub fn internal_write(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
         for arg in args.iter() {
-            print!("{}", arg.to_string(agent)?.as_str(agent));
+            let message = arg.to_string(agent)?;
+            print!("{}", Self::redact(agent, message.as_str(agent)));
         }
         Ok(Value::Undefined)
     }
@@ -74,9 +104,78 @@ impl ConsoleExt {
         args: ArgumentsList,
     ) -> JsResult<Value> {
         for arg in args.iter() {
-            print!("{}", arg.to_string(agent)?.as_str(agent));
+            let message = arg.to_string(agent)?;
+            print!("{}", Self::redact(agent, message.as_str(agent)));
         }
         println!();
         Ok(Value::Undefined)
     }
+
+    /// Number of terminal columns taken by the widest line of the text.
+    fn internal_text_width(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(0).to_string(agent)?;
+        let width = width::text_width(text.as_str(agent));
+        Ok(Value::from_f64(agent, width as f64))
+    }
+
+    /// Cut the text to fit in the given number of columns, ending it with the tail if it was cut.
+    fn internal_text_truncate(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let columns = args.get(1).to_uint32(agent)? as usize;
+        let tail = args.get(2).to_string(agent)?.as_str(agent).to_string();
+        Ok(Value::from_string(
+            agent,
+            width::truncate(&text, columns, &tail),
+        ))
+    }
+
+    /// Wrap the text to fit in the given number of columns, returning the lines as a JSON array.
+    fn internal_text_wrap(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let text = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let columns = args.get(1).to_uint32(agent)? as usize;
+        let lines = width::wrap(&text, columns);
+        Ok(Value::from_string(
+            agent,
+            serde_json::to_string(&lines).unwrap(),
+        ))
+    }
+
+    /// Width of the terminal standard output is written to, or `undefined` if it isn't one.
+    fn internal_terminal_columns(
+        agent: &mut Agent,
+        _this: Value,
+        _args: ArgumentsList,
+    ) -> JsResult<Value> {
+        Ok(match width::terminal_columns() {
+            Some(columns) => Value::from_f64(agent, columns as f64),
+            None => Value::Undefined,
+        })
+    }
+
+    /// Whether colors are turned off with a non-empty `NO_COLOR`, see <https://no-color.org>.
+    /// Like the redaction patterns, it configures the output of the runtime itself, so reading
+    /// it doesn't need the env permission.
+    fn internal_no_color(
+        _agent: &mut Agent,
+        _this: Value,
+        _args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Ok(Value::Boolean(no_color))
+    }
+
+    /// Mask the secrets in a message written to the console, see [Redactor].
+    fn redact(agent: &Agent, message: &str) -> String {
+        HostData::<RuntimeMacroTask>::from_agent(agent)
+            .try_with_storage(|redactor: &Redactor| redactor.redact(message).into_owned())
+            .unwrap_or_else(|| message.to_string())
+    }
 }
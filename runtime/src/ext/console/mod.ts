@@ -29,6 +29,69 @@ const COLORS = {
   },
 };
 
+/**
+ * Format a value for a cell of `console.table`.
+ */
+function formatTableCell(value: unknown): string {
+  if (typeof value === "string") return value;
+  if (typeof value === "function") return `[Function: ${value.name || "(anonymous)"}]`;
+  if (value !== null && typeof value === "object") {
+    try {
+      return JSON.stringify(value);
+    } catch {
+      return String(value);
+    }
+  }
+  return String(value);
+}
+
+/**
+ * Draw a table with box-drawing characters. Cells are measured by their width in the terminal,
+ * so wide characters, emoji and colors line up, and the widest columns are wrapped until the
+ * table fits in the terminal.
+ */
+function renderTable(header: string[], rows: string[][]): string {
+  const widths = header.map((title, column) =>
+    Math.max(
+      internal_text_width(title),
+      ...rows.map((row) => internal_text_width(row[column])),
+    )
+  );
+  const available = internal_terminal_columns();
+  if (available !== undefined) {
+    // Each column takes its width, a space on both sides and a border, plus the last border.
+    const total = () => widths.reduce((sum, width) => sum + width + 3, 1);
+    while (total() > available) {
+      const widest = widths.indexOf(Math.max(...widths));
+      if (widths[widest] <= 3) break;
+      widths[widest]--;
+    }
+  }
+
+  const border = (left: string, middle: string, right: string) =>
+    left + widths.map((width) => "─".repeat(width + 2)).join(middle) + right +
+    "\n";
+  const renderRow = (cells: string[]) => {
+    const lines: string[][] = cells.map((cell, column) =>
+      JSON.parse(internal_text_wrap(cell, widths[column]))
+    );
+    const height = Math.max(...lines.map((cellLines) => cellLines.length));
+    let output = "";
+    for (let i = 0; i < height; i++) {
+      const padded = lines.map((cellLines, column) => {
+        const text = cellLines[i] ?? "";
+        const padding = Math.max(0, widths[column] - internal_text_width(text));
+        return ` ${text}${" ".repeat(padding)} `;
+      });
+      output += `│${padded.join("│")}│\n`;
+    }
+    return output;
+  };
+
+  return border("┌", "┬", "┐") + renderRow(header) + border("├", "┼", "┤") +
+    rows.map(renderRow).join("") + border("└", "┴", "┘");
+}
+
 /**
  * The `console` module provides a simple debugging console that is similar to the JavaScript console mechanism provided by web browsers.
  */
@@ -106,6 +169,48 @@ const console = {
     }
   },
 
+  /**
+   * table function logs the entries of an array or object as a table, with a column for each
+   * property of the rows, or only the given `properties`. Primitive rows go in a `Values` column.
+   *
+   * @example
+   * ```ts
+   * console.table([{ city: "東京", people: 37 }, { city: "Paris", people: 11 }]);
+   * ```
+   */
+  table(data: unknown, properties?: string[]) {
+    if (data === null || typeof data !== "object") {
+      console.log(String(data));
+      return;
+    }
+    const rows = Object.entries(data as Record<string, unknown>);
+    const isObject = (row: unknown): row is Record<string, unknown> =>
+      row !== null && typeof row === "object";
+
+    const columns: string[] = [];
+    let hasValues = false;
+    for (const [, row] of rows) {
+      if (isObject(row)) {
+        for (const key of Object.keys(row)) {
+          if (!columns.includes(key)) columns.push(key);
+        }
+      } else {
+        hasValues = true;
+      }
+    }
+    const shown = properties ?? columns;
+
+    const header = ["(idx)", ...shown, ...(hasValues ? ["Values"] : [])];
+    const cells = rows.map(([index, row]) => [
+      index,
+      ...shown.map((key) =>
+        isObject(row) && key in row ? formatTableCell(row[key]) : ""
+      ),
+      ...(hasValues ? [isObject(row) ? "" : formatTableCell(row)] : []),
+    ]);
+    internal_print(renderTable(header, cells));
+  },
+
   /**
    * clear function clears the console.
    *
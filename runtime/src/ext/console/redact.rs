@@ -0,0 +1,72 @@
+use std::{borrow::Cow, env};
+
+/// Environment variable enabling the redaction. `1` or `true` use the default patterns,
+/// otherwise it is a comma-separated list of patterns, e.g. `ANDROMEDA_REDACT=TOKEN,AUTH`.
+pub const REDACT_ENV_VAR: &str = "ANDROMEDA_REDACT";
+
+const DEFAULT_PATTERNS: [&str; 4] = ["TOKEN", "SECRET", "KEY", "PASSWORD"];
+
+/// Shorter values are too likely to appear in unrelated output to be masked.
+const MIN_SECRET_LEN: usize = 4;
+
+const MASK: &str = "[REDACTED]";
+
+/// Masks the values of secret environment variables, those whose name contains one of the
+/// patterns, in the console and error output. Disabled unless [REDACT_ENV_VAR] is set.
+#[derive(Debug, Default)]
+pub struct Redactor {
+    /// Secret values, longest first so that overlapping secrets are fully masked.
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    pub fn from_env() -> Self {
+        let patterns: Vec<String> = match env::var(REDACT_ENV_VAR) {
+            Ok(value) if value == "1" || value.eq_ignore_ascii_case("true") => {
+                DEFAULT_PATTERNS.iter().map(|p| p.to_string()).collect()
+            }
+            Ok(value) if value != "0" && !value.eq_ignore_ascii_case("false") => value
+                .split(',')
+                .map(|pattern| pattern.trim().to_uppercase())
+                .filter(|pattern| !pattern.is_empty())
+                .collect(),
+            _ => vec![],
+        };
+        let vars = env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+        Self::new(&patterns, vars)
+    }
+
+    pub fn new(patterns: &[String], vars: impl Iterator<Item = (String, String)>) -> Self {
+        let mut secrets: Vec<String> = vars
+            .filter(|(name, value)| {
+                let name = name.to_uppercase();
+                value.len() >= MIN_SECRET_LEN
+                    && patterns
+                        .iter()
+                        .any(|pattern| name.contains(pattern.as_str()))
+            })
+            .map(|(_, value)| value)
+            .collect();
+        secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        secrets.dedup();
+        Self { secrets }
+    }
+
+    /// Replace every secret value in the text with `[REDACTED]`.
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self
+            .secrets
+            .iter()
+            .any(|secret| text.contains(secret.as_str()))
+        {
+            return Cow::Borrowed(text);
+        }
+        let mut text = text.to_string();
+        for secret in &self.secrets {
+            text = text.replace(secret.as_str(), MASK);
+        }
+        Cow::Owned(text)
+    }
+}
@@ -0,0 +1,98 @@
+use ::console::{measure_text_width, AnsiCodeIterator};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Number of terminal columns taken by the widest line of the text. East Asian wide characters
+/// and emoji take two columns, ANSI escape sequences none.
+pub fn text_width(text: &str) -> usize {
+    text.split('\n').map(measure_text_width).max().unwrap_or(0)
+}
+
+/// Cut the text to fit in `width` columns, ending it with `tail` if anything was cut.
+/// Escape sequences are kept so the style of the visible text doesn't change.
+pub fn truncate(text: &str, width: usize, tail: &str) -> String {
+    ::console::truncate_str(text, width, tail).into_owned()
+}
+
+/// Wrap each line of the text to fit in `width` columns, breaking at whitespace when possible and
+/// between grapheme clusters otherwise, so wide characters and emoji are never split.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+    for line in text.split('\n') {
+        let mut wrapper = Wrapper::new(width);
+        for (part, is_ansi) in AnsiCodeIterator::new(line) {
+            if is_ansi {
+                wrapper.current.push_str(part);
+            } else {
+                part.graphemes(true)
+                    .for_each(|grapheme| wrapper.push(grapheme, &mut lines));
+            }
+        }
+        lines.push(wrapper.current);
+    }
+    lines
+}
+
+/// Line being filled by [wrap].
+struct Wrapper {
+    width: usize,
+    current: String,
+    current_width: usize,
+    /// Byte offset in `current` after its last whitespace, where the line can be broken.
+    break_at: Option<usize>,
+}
+
+impl Wrapper {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            current: String::new(),
+            current_width: 0,
+            break_at: None,
+        }
+    }
+
+    fn push(&mut self, grapheme: &str, lines: &mut Vec<String>) {
+        let grapheme_width = measure_text_width(grapheme);
+        let is_whitespace = grapheme.chars().all(char::is_whitespace);
+
+        if self.current_width + grapheme_width > self.width {
+            if is_whitespace {
+                // The whitespace itself is the break.
+                self.break_line(self.current.len(), lines);
+                return;
+            }
+            match self.break_at {
+                Some(break_at) => self.break_line(break_at, lines),
+                None if self.current_width > 0 => self.break_line(self.current.len(), lines),
+                None => {}
+            }
+            // A word longer than the line is broken between grapheme clusters.
+            if self.current_width > 0 && self.current_width + grapheme_width > self.width {
+                self.break_line(self.current.len(), lines);
+            }
+        }
+
+        self.current.push_str(grapheme);
+        self.current_width += grapheme_width;
+        if is_whitespace {
+            self.break_at = Some(self.current.len());
+        }
+    }
+
+    /// End the line at the byte offset, moving the rest of it to the next line.
+    fn break_line(&mut self, at: usize, lines: &mut Vec<String>) {
+        let rest = self.current.split_off(at);
+        lines.push(self.current.trim_end().to_string());
+        self.current = rest.trim_start().to_string();
+        self.current_width = measure_text_width(&self.current);
+        self.break_at = None;
+    }
+}
+
+/// Width of the terminal standard output is written to, if it is one.
+pub fn terminal_columns() -> Option<u16> {
+    ::console::Term::stdout()
+        .size_checked()
+        .map(|(_rows, columns)| columns)
+}
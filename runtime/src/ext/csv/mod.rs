@@ -0,0 +1,136 @@
+use andromeda_core::{extension_op, ErrorClass, Extension, OpError};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::Value,
+};
+use serde::Deserialize;
+
+use crate::RuntimeMacroTask;
+
+/// Dialect options shared by the parser and the writer, sent from JavaScript as JSON.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct CsvOptions {
+    delimiter: Option<String>,
+    quote: Option<String>,
+    escape: Option<String>,
+}
+
+impl CsvOptions {
+    fn from_json(json: &str) -> Result<Self, OpError> {
+        serde_json::from_str(json).map_err(|e| OpError::new(ErrorClass::InvalidData, e.to_string()))
+    }
+}
+
+/// CSV extension for Andromeda.
+/// Rows cross the JavaScript boundary as JSON arrays of string arrays; headers and casting are done in JavaScript.
+#[derive(Default)]
+pub struct CsvExt;
+
+impl CsvExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "csv",
+            ops: vec![
+                extension_op!("internal_csv_parse", Self::internal_csv_parse, 2),
+                extension_op!("internal_csv_stringify", Self::internal_csv_stringify, 2),
+            ],
+            storage: None,
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Parse CSV text into a JSON array of records, each an array of fields.
+    fn internal_csv_parse(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let text = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let options = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        match parse(&text, &options) {
+            Ok(rows) => Ok(Value::from_string(agent, rows)),
+            Err(e) => Err(e.throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+
+    /// Write a JSON array of records, each an array of fields, as CSV text.
+    fn internal_csv_stringify(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rows = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let options = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        match stringify(&rows, &options) {
+            Ok(text) => Ok(Value::from_string(agent, text)),
+            Err(e) => Err(e.throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+}
+
+fn parse(text: &str, options: &str) -> Result<String, OpError> {
+    let options = CsvOptions::from_json(options)?;
+    let mut builder = csv::ReaderBuilder::new();
+    builder
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(dialect_byte(&options.delimiter, b',', "delimiter")?)
+        .quote(dialect_byte(&options.quote, b'"', "quote")?);
+    if options.escape.is_some() {
+        builder.escape(Some(dialect_byte(&options.escape, b'\\', "escape")?));
+    }
+
+    let rows = builder
+        .from_reader(text.as_bytes())
+        .records()
+        .map(|record| {
+            record
+                .map(|record| record.iter().map(str::to_string).collect::<Vec<_>>())
+                .map_err(invalid_data)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    serde_json::to_string(&rows).map_err(invalid_data)
+}
+
+fn stringify(rows: &str, options: &str) -> Result<String, OpError> {
+    let options = CsvOptions::from_json(options)?;
+    let rows: Vec<Vec<String>> = serde_json::from_str(rows).map_err(invalid_data)?;
+    let mut builder = csv::WriterBuilder::new();
+    builder
+        .flexible(true)
+        .delimiter(dialect_byte(&options.delimiter, b',', "delimiter")?)
+        .quote(dialect_byte(&options.quote, b'"', "quote")?);
+    if options.escape.is_some() {
+        builder
+            .double_quote(false)
+            .escape(dialect_byte(&options.escape, b'\\', "escape")?);
+    }
+
+    let mut writer = builder.from_writer(vec![]);
+    for row in rows {
+        writer.write_record(&row).map_err(invalid_data)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| OpError::new(ErrorClass::Error, e.to_string()))?;
+    String::from_utf8(bytes).map_err(invalid_data)
+}
+
+/// The csv crate works on bytes, so dialect characters have to be a single ASCII character.
+fn dialect_byte(value: &Option<String>, default: u8, name: &str) -> Result<u8, OpError> {
+    match value.as_deref() {
+        None => Ok(default),
+        Some(value) if value.len() == 1 && value.is_ascii() => Ok(value.as_bytes()[0]),
+        Some(value) => Err(OpError::new(
+            ErrorClass::InvalidData,
+            format!("The CSV {name} must be a single ASCII character, got {value:?}"),
+        )),
+    }
+}
+
+fn invalid_data(error: impl std::fmt::Display) -> OpError {
+    OpError::new(ErrorClass::InvalidData, error.to_string())
+}
@@ -0,0 +1,19 @@
+use andromeda_core::Extension;
+
+/// Opt-in compatibility layer exposing a subset of the `Deno` namespace on top of the Andromeda
+/// APIs, so existing Deno scripts run with minimal changes. Not part of the recommended extensions.
+#[derive(Default)]
+pub struct DenoExt;
+
+impl DenoExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "deno",
+            ops: vec![],
+            storage: None,
+            files: vec![include_str!("./mod.ts")],
+            deps: &["errors", "fs", "process"],
+            cfg: None,
+        }
+    }
+}
@@ -0,0 +1,120 @@
+/**
+ * Subset of the `Deno` namespace mapped onto the Andromeda APIs. The `Andromeda` namespace is
+ * only defined after the extensions are loaded, so every member looks it up when it's used.
+ *
+ * Deno's async file system functions settle asynchronously but do the work synchronously when
+ * Andromeda only has a sync version. `Deno.serve` and `Deno.Command` are left out, as the runtime
+ * has no HTTP server nor subprocesses, so feature detection keeps working.
+ */
+(() => {
+  const later = <T>(callback: () => T): Promise<T> =>
+    Promise.resolve().then(callback);
+  // Deno takes `file:` URLs wherever it takes paths.
+  const toPath = (path: string | URL): string => {
+    const string = String(path);
+    return string.startsWith("file://")
+      ? decodeURIComponent(string.slice("file://".length))
+      : string;
+  };
+
+  const env = {
+    get(key: string): string | undefined {
+      return Andromeda.env.get(key);
+    },
+    set(key: string, value: string): void {
+      Andromeda.env.set(key, value);
+    },
+    delete(key: string): void {
+      Andromeda.env.remove(key);
+    },
+    has(key: string): boolean {
+      return Andromeda.env.get(key) !== undefined;
+    },
+    toObject(): Record<string, string> {
+      const object: Record<string, string> = {};
+      for (const key of Andromeda.env.keys()) {
+        object[key] = Andromeda.env.get(key);
+      }
+      return object;
+    },
+  };
+
+  const Deno = {
+    get args(): string[] {
+      return Andromeda.args;
+    },
+    get mainModule(): string {
+      return Andromeda.mainModule;
+    },
+    get errors() {
+      return Andromeda.errors;
+    },
+    env,
+    exit(code?: number): never {
+      Andromeda.exit(code);
+      throw new Error("unreachable");
+    },
+    readTextFileSync(path: string | URL): string {
+      return Andromeda.readTextFileSync(toPath(path));
+    },
+    readTextFile(path: string | URL): Promise<string> {
+      return later(() => Andromeda.readTextFileSync(toPath(path)));
+    },
+    writeTextFileSync(path: string | URL, data: string): void {
+      Andromeda.writeTextFileSync(toPath(path), data);
+    },
+    writeTextFile(path: string | URL, data: string): Promise<void> {
+      return later(() => Andromeda.writeTextFileSync(toPath(path), data));
+    },
+    copyFileSync(source: string | URL, destination: string | URL): void {
+      Andromeda.copyFileSync(toPath(source), toPath(destination));
+    },
+    copyFile(source: string | URL, destination: string | URL): Promise<void> {
+      return later(() =>
+        Andromeda.copyFileSync(toPath(source), toPath(destination))
+      );
+    },
+    mkdirSync(path: string | URL): void {
+      Andromeda.mkdirSync(toPath(path));
+    },
+    mkdir(path: string | URL): Promise<void> {
+      return later(() => Andromeda.mkdirSync(toPath(path)));
+    },
+    chmod(path: string | URL, mode: number): Promise<void> {
+      return Andromeda.chmod(toPath(path), mode);
+    },
+    chown(
+      path: string | URL,
+      uid: number | null,
+      gid: number | null,
+    ): Promise<void> {
+      return Andromeda.chown(toPath(path), uid, gid);
+    },
+    utime(
+      path: string | URL,
+      atime: number | Date,
+      mtime: number | Date,
+    ): Promise<void> {
+      return Andromeda.utime(toPath(path), atime, mtime);
+    },
+    link(oldpath: string | URL, newpath: string | URL): Promise<void> {
+      return Andromeda.link(toPath(oldpath), toPath(newpath));
+    },
+    readLink(path: string | URL): Promise<string> {
+      return Andromeda.readLink(toPath(path));
+    },
+    realPath(path: string | URL): Promise<string> {
+      return Andromeda.realPath(toPath(path));
+    },
+    truncate(name: string | URL, len?: number): Promise<void> {
+      return Andromeda.truncate(toPath(name), len);
+    },
+  };
+
+  Object.defineProperty(globalThis, "Deno", {
+    value: Deno,
+    writable: true,
+    configurable: true,
+    enumerable: false,
+  });
+})();
@@ -0,0 +1,210 @@
+use andromeda_core::{extension_op, ErrorClass, Extension, OpError};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::Value,
+};
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+
+use crate::RuntimeMacroTask;
+
+/// A run of consecutive text with the same change kind.
+#[derive(Serialize)]
+struct Change {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    value: String,
+}
+
+/// Diff extension for Andromeda.
+/// This extension compares texts by lines, words or characters and writes and applies unified diffs.
+#[derive(Default)]
+pub struct DiffExt;
+
+impl DiffExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "diff",
+            ops: vec![
+                extension_op!("internal_diff", Self::internal_diff, 3),
+                extension_op!("internal_diff_unified", Self::internal_diff_unified, 5),
+                extension_op!("internal_diff_apply", Self::internal_diff_apply, 2),
+            ],
+            storage: None,
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Compare two texts by `"lines"`, `"words"` or `"chars"`, returning the changes as a JSON array.
+    fn internal_diff(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let old = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let new = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let granularity = args.get(2).to_string(agent)?.as_str(agent).to_string();
+
+        let diff = match granularity.as_str() {
+            "lines" => TextDiff::from_lines(&old, &new),
+            "words" => TextDiff::from_words(&old, &new),
+            "chars" => TextDiff::from_chars(&old, &new),
+            granularity => {
+                return Err(OpError::new(
+                    ErrorClass::NotSupported,
+                    format!("Unsupported diff granularity: {granularity}"),
+                )
+                .throw::<RuntimeMacroTask>(agent))
+            }
+        };
+
+        let mut changes: Vec<Change> = vec![];
+        for change in diff.iter_all_changes() {
+            let kind = match change.tag() {
+                ChangeTag::Equal => "equal",
+                ChangeTag::Delete => "delete",
+                ChangeTag::Insert => "insert",
+            };
+            match changes.last_mut() {
+                Some(last) if last.kind == kind => last.value.push_str(change.value()),
+                _ => changes.push(Change {
+                    kind,
+                    value: change.value().to_string(),
+                }),
+            }
+        }
+
+        Ok(Value::from_string(
+            agent,
+            serde_json::to_string(&changes).unwrap(),
+        ))
+    }
+
+    /// Write a unified diff between two texts, with `context` lines around each hunk.
+    fn internal_diff_unified(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let old = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let new = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let old_name = args.get(2).to_string(agent)?.as_str(agent).to_string();
+        let new_name = args.get(3).to_string(agent)?.as_str(agent).to_string();
+        let context = args.get(4).to_uint32(agent)? as usize;
+
+        let diff = TextDiff::from_lines(&old, &new);
+        let patch = diff
+            .unified_diff()
+            .context_radius(context)
+            .header(&old_name, &new_name)
+            .to_string();
+
+        Ok(Value::from_string(agent, patch))
+    }
+
+    /// Apply a unified diff to a text, throwing if its context doesn't match.
+    fn internal_diff_apply(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let patch = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        match apply(&text, &patch) {
+            Ok(output) => Ok(Value::from_string(agent, output)),
+            Err(e) => Err(e.throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+}
+
+/// Apply the hunks of a unified diff in order. File headers and anything outside of hunks are ignored.
+fn apply(text: &str, patch: &str) -> Result<String, OpError> {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let mut output = String::with_capacity(text.len());
+    let mut position = 0;
+    let mut patch_lines = patch.split_inclusive('\n').peekable();
+
+    while let Some(header) = patch_lines.next() {
+        if !header.starts_with("@@ ") {
+            continue;
+        }
+        let (old_start, old_len) = parse_hunk_header(header)?;
+        // An empty old range starts after the given line, a non-empty one at it.
+        let hunk_start = if old_len == 0 {
+            old_start
+        } else {
+            old_start.saturating_sub(1)
+        };
+        if hunk_start < position || hunk_start > lines.len() {
+            return Err(patch_error(format!(
+                "hunk {} is out of range",
+                header.trim_end()
+            )));
+        }
+        lines[position..hunk_start]
+            .iter()
+            .for_each(|line| output.push_str(line));
+        position = hunk_start;
+
+        let mut last_kind = ' ';
+        while let Some(&line) = patch_lines.peek() {
+            // Some tools drop the leading space of empty context lines.
+            let (kind, content) = match line.chars().next() {
+                Some(kind @ (' ' | '-' | '+' | '\\')) => (kind, &line[1..]),
+                Some('\n') => (' ', line),
+                _ => break,
+            };
+            patch_lines.next();
+            match kind {
+                ' ' | '-' => {
+                    let original = lines.get(position).copied().unwrap_or_default();
+                    if original.trim_end_matches('\n') != content.trim_end_matches('\n') {
+                        return Err(patch_error(format!(
+                            "line {} doesn't match the patch context",
+                            position + 1
+                        )));
+                    }
+                    if kind == ' ' {
+                        output.push_str(original);
+                    }
+                    position += 1;
+                }
+                '+' => output.push_str(content),
+                // "\ No newline at end of file" applies to the previous line.
+                _ => {
+                    if last_kind != '-' && output.ends_with('\n') {
+                        output.pop();
+                    }
+                }
+            }
+            last_kind = kind;
+        }
+    }
+
+    lines[position.min(lines.len())..]
+        .iter()
+        .for_each(|line| output.push_str(line));
+    Ok(output)
+}
+
+/// Parse the old range of a `@@ -start,len +start,len @@` hunk header.
+fn parse_hunk_header(header: &str) -> Result<(usize, usize), OpError> {
+    let invalid = || patch_error(format!("invalid hunk header {}", header.trim_end()));
+    let old_range = header
+        .split_whitespace()
+        .nth(1)
+        .and_then(|range| range.strip_prefix('-'))
+        .ok_or_else(invalid)?;
+    let (start, len) = old_range.split_once(',').unwrap_or((old_range, "1"));
+    Ok((
+        start.parse().map_err(|_| invalid())?,
+        len.parse().map_err(|_| invalid())?,
+    ))
+}
+
+fn patch_error(message: String) -> OpError {
+    OpError::new(
+        ErrorClass::InvalidData,
+        format!("Cannot apply patch: {message}"),
+    )
+}
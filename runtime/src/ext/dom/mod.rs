@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+
+use andromeda_core::{extension_op, webidl::to_enum, Extension};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::Value,
+};
+use serde::Serialize;
+
+/// Values of the `DOMParserSupportedType` enumeration.
+const SUPPORTED_TYPES: &[&str] = &[
+    "text/html",
+    "text/xml",
+    "application/xml",
+    "application/xhtml+xml",
+    "image/svg+xml",
+];
+
+/// Depth past which the elements of a parsed document stop nesting, like in the HTML parsers of
+/// browsers. The descendants of the elements at this depth become their children instead, so
+/// converting, serializing and building the tree doesn't overflow the stack.
+const MAX_DEPTH: usize = 512;
+
+/// A node of a parsed document, sent to JavaScript as JSON to build the read-only DOM.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum DomNode {
+    Element {
+        name: String,
+        namespace: Option<String>,
+        attributes: Vec<(String, String)>,
+        children: Vec<DomNode>,
+    },
+    Text {
+        value: String,
+    },
+    Comment {
+        value: String,
+    },
+    Doctype {
+        name: String,
+    },
+}
+
+/// DOM extension for Andromeda.
+/// This extension provides `DOMParser`, parsing HTML with html5ever and XML with roxmltree into a read-only DOM.
+#[derive(Default)]
+pub struct DomExt;
+
+impl DomExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "dom",
+            ops: vec![extension_op!(
+                "internal_dom_parse",
+                Self::internal_dom_parse,
+                2
+            )],
+            storage: None,
+            files: vec![include_str!("./mod.ts")],
+            deps: &["web"],
+            cfg: None,
+        }
+    }
+
+    /// Parse a document of the given type, returning the children of the document as a JSON array.
+    fn internal_dom_parse(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let text = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let mime_type = to_enum(agent, args.get(1), SUPPORTED_TYPES, "The type")?;
+
+        let nodes = if mime_type == "text/html" {
+            parse_html(&text)
+        } else {
+            parse_xml(&text)
+        };
+
+        Ok(Value::from_string(
+            agent,
+            serde_json::to_string(&nodes).unwrap(),
+        ))
+    }
+}
+
+fn parse_html(text: &str) -> Vec<DomNode> {
+    let document = scraper::Html::parse_document(text);
+    document
+        .tree
+        .root()
+        .children()
+        .filter_map(|child| html_node(child, 1))
+        .collect()
+}
+
+fn html_node(node: ego_tree::NodeRef<scraper::Node>, depth: usize) -> Option<DomNode> {
+    Some(match node.value() {
+        scraper::Node::Element(element) => DomNode::Element {
+            name: element.name().to_string(),
+            namespace: Some(element.name.ns.to_string()),
+            attributes: element
+                .attrs()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            children: match depth.cmp(&MAX_DEPTH) {
+                Ordering::Less => node
+                    .children()
+                    .filter_map(|child| html_node(child, depth + 1))
+                    .collect(),
+                Ordering::Equal => node
+                    .descendants()
+                    .skip(1)
+                    .filter_map(|descendant| html_node(descendant, depth + 1))
+                    .collect(),
+                Ordering::Greater => Vec::new(),
+            },
+        },
+        scraper::Node::Text(text) => DomNode::Text {
+            value: text.text.to_string(),
+        },
+        scraper::Node::Comment(comment) => DomNode::Comment {
+            value: comment.comment.to_string(),
+        },
+        scraper::Node::Doctype(doctype) => DomNode::Doctype {
+            name: doctype.name().to_string(),
+        },
+        _ => return None,
+    })
+}
+
+/// Parse XML. Like browsers, a malformed document parses to a `parsererror` element describing the error.
+fn parse_xml(text: &str) -> Vec<DomNode> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    match roxmltree::Document::parse_with_options(text, options) {
+        Ok(document) => document
+            .root()
+            .children()
+            .filter_map(|child| xml_node(child, 1))
+            .collect(),
+        Err(e) => vec![DomNode::Element {
+            name: "parsererror".to_string(),
+            namespace: Some("http://www.mozilla.org/newlayout/xml/parsererror.xml".to_string()),
+            attributes: vec![],
+            children: vec![DomNode::Text {
+                value: e.to_string(),
+            }],
+        }],
+    }
+}
+
+fn xml_node(node: roxmltree::Node, depth: usize) -> Option<DomNode> {
+    Some(match node.node_type() {
+        roxmltree::NodeType::Element => DomNode::Element {
+            name: qualified_name(node, node.tag_name().namespace(), node.tag_name().name()),
+            namespace: node.tag_name().namespace().map(str::to_string),
+            attributes: node
+                .attributes()
+                .map(|attribute| {
+                    let name = qualified_name(node, attribute.namespace(), attribute.name());
+                    (name, attribute.value().to_string())
+                })
+                .collect(),
+            children: match depth.cmp(&MAX_DEPTH) {
+                Ordering::Less => node
+                    .children()
+                    .filter_map(|child| xml_node(child, depth + 1))
+                    .collect(),
+                Ordering::Equal => node
+                    .descendants()
+                    .skip(1)
+                    .filter_map(|descendant| xml_node(descendant, depth + 1))
+                    .collect(),
+                Ordering::Greater => Vec::new(),
+            },
+        },
+        roxmltree::NodeType::Text => DomNode::Text {
+            value: node.text().unwrap_or_default().to_string(),
+        },
+        roxmltree::NodeType::Comment => DomNode::Comment {
+            value: node.text().unwrap_or_default().to_string(),
+        },
+        _ => return None,
+    })
+}
+
+/// roxmltree resolves prefixes to namespaces, so look the prefix back up to get the qualified name.
+fn qualified_name(node: roxmltree::Node, namespace: Option<&str>, name: &str) -> String {
+    match namespace.and_then(|namespace| node.lookup_prefix(namespace)) {
+        Some(prefix) if !prefix.is_empty() => format!("{prefix}:{name}"),
+        _ => name.to_string(),
+    }
+}
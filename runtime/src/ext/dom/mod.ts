@@ -0,0 +1,856 @@
+// deno-lint-ignore-file no-unused-vars no-explicit-any
+
+/**
+ * A node as sent by `internal_dom_parse`.
+ */
+type ParsedDomNode =
+  | {
+    type: "element";
+    name: string;
+    namespace: string | null;
+    attributes: [string, string][];
+    children: ParsedDomNode[];
+  }
+  | { type: "text"; value: string }
+  | { type: "comment"; value: string }
+  | { type: "doctype"; name: string };
+
+const HTML_NAMESPACE = "http://www.w3.org/1999/xhtml";
+
+/** Elements without an end tag when serialized as HTML. */
+const voidElements = new Set([
+  "area",
+  "base",
+  "br",
+  "col",
+  "embed",
+  "hr",
+  "img",
+  "input",
+  "link",
+  "meta",
+  "source",
+  "track",
+  "wbr",
+]);
+
+/**
+ * A node of a read-only document created by `DOMParser`.
+ */
+class DomNode {
+  static readonly ELEMENT_NODE = 1;
+  static readonly TEXT_NODE = 3;
+  static readonly COMMENT_NODE = 8;
+  static readonly DOCUMENT_NODE = 9;
+  static readonly DOCUMENT_TYPE_NODE = 10;
+
+  parentNode: DomNode | null = null;
+  previousSibling: DomNode | null = null;
+  nextSibling: DomNode | null = null;
+  readonly childNodes: readonly DomNode[] = [];
+
+  readonly nodeType: number;
+  readonly nodeName: string;
+
+  constructor(nodeType: number, nodeName: string) {
+    this.nodeType = nodeType;
+    this.nodeName = nodeName;
+  }
+
+  get ownerDocument(): DomDocument | null {
+    let node: DomNode | null = this.parentNode;
+    while (node && !(node instanceof DomDocument)) node = node.parentNode;
+    return node as DomDocument | null;
+  }
+
+  get parentElement(): DomElement | null {
+    return this.parentNode instanceof DomElement ? this.parentNode : null;
+  }
+
+  get firstChild(): DomNode | null {
+    return this.childNodes[0] ?? null;
+  }
+
+  get lastChild(): DomNode | null {
+    return this.childNodes[this.childNodes.length - 1] ?? null;
+  }
+
+  hasChildNodes(): boolean {
+    return this.childNodes.length > 0;
+  }
+
+  get textContent(): string | null {
+    let text = "";
+    for (const child of this.childNodes) {
+      if (child instanceof DomElement || child instanceof DomCharacterData) {
+        text += child.textContent;
+      }
+    }
+    return text;
+  }
+
+  contains(other: DomNode | null): boolean {
+    for (let node = other; node; node = node.parentNode) {
+      if (node === this) return true;
+    }
+    return false;
+  }
+}
+
+/**
+ * Base of the nodes holding text: `Text` and `Comment`.
+ */
+class DomCharacterData extends DomNode {
+  readonly data: string;
+
+  constructor(nodeType: number, nodeName: string, data: string) {
+    super(nodeType, nodeName);
+    this.data = data;
+  }
+
+  get textContent(): string {
+    return this.data;
+  }
+
+  get nodeValue(): string {
+    return this.data;
+  }
+
+  get length(): number {
+    return this.data.length;
+  }
+}
+
+class DomText extends DomCharacterData {
+  constructor(data: string) {
+    super(DomNode.TEXT_NODE, "#text", data);
+  }
+
+  get wholeText(): string {
+    return this.data;
+  }
+}
+
+class DomComment extends DomCharacterData {
+  constructor(data: string) {
+    super(DomNode.COMMENT_NODE, "#comment", data);
+  }
+}
+
+class DomDocumentType extends DomNode {
+  readonly name: string;
+
+  constructor(name: string) {
+    super(DomNode.DOCUMENT_TYPE_NODE, name);
+    this.name = name;
+  }
+
+  get textContent(): null {
+    return null;
+  }
+}
+
+/**
+ * An attribute of an element.
+ */
+interface DomAttr {
+  readonly name: string;
+  readonly value: string;
+}
+
+/**
+ * Methods shared by documents and elements to search their descendants.
+ */
+class DomParentNode extends DomNode {
+  get children(): readonly DomElement[] {
+    return this.childNodes.filter((child): child is DomElement =>
+      child instanceof DomElement
+    );
+  }
+
+  get childElementCount(): number {
+    return this.children.length;
+  }
+
+  get firstElementChild(): DomElement | null {
+    return this.children[0] ?? null;
+  }
+
+  get lastElementChild(): DomElement | null {
+    const children = this.children;
+    return children[children.length - 1] ?? null;
+  }
+
+  querySelector(selectors: string): DomElement | null {
+    const selector = parseSelectorList(String(selectors));
+    let found: DomElement | null = null;
+    walkElements(this, (element) => {
+      if (matchesSelectorList(element, selector, this)) {
+        found = element;
+        return true;
+      }
+      return false;
+    });
+    return found;
+  }
+
+  querySelectorAll(selectors: string): DomElement[] {
+    const selector = parseSelectorList(String(selectors));
+    const found: DomElement[] = [];
+    walkElements(this, (element) => {
+      if (matchesSelectorList(element, selector, this)) found.push(element);
+      return false;
+    });
+    return found;
+  }
+
+  getElementsByTagName(qualifiedName: string): DomElement[] {
+    const name = String(qualifiedName);
+    const found: DomElement[] = [];
+    walkElements(this, (element) => {
+      if (name === "*" || element.matchesTagName(name)) found.push(element);
+      return false;
+    });
+    return found;
+  }
+
+  getElementsByClassName(classNames: string): DomElement[] {
+    const classes = String(classNames).split(/\s+/).filter(Boolean);
+    const found: DomElement[] = [];
+    if (classes.length === 0) return found;
+    walkElements(this, (element) => {
+      const own = element.classList;
+      if (classes.every((name) => own.includes(name))) found.push(element);
+      return false;
+    });
+    return found;
+  }
+}
+
+/**
+ * An element of a read-only document created by `DOMParser`.
+ */
+class DomElement extends DomParentNode {
+  readonly tagName: string;
+  readonly localName: string;
+  readonly prefix: string | null;
+  readonly namespaceURI: string | null;
+  readonly attributes: readonly DomAttr[];
+  /** Whether the element is an HTML element of an HTML document, where names ignore case. */
+  private readonly isHtml: boolean;
+
+  constructor(
+    tagName: string,
+    namespaceURI: string | null,
+    attributes: readonly DomAttr[],
+    isHtml: boolean,
+  ) {
+    super(DomNode.ELEMENT_NODE, tagName);
+    this.tagName = tagName;
+    this.namespaceURI = namespaceURI;
+    this.attributes = attributes;
+    this.isHtml = isHtml;
+    const separator = tagName.indexOf(":");
+    this.prefix = separator === -1 ? null : tagName.slice(0, separator);
+    this.localName = separator === -1
+      ? (isHtml ? tagName.toLowerCase() : tagName)
+      : tagName.slice(separator + 1);
+  }
+
+  get id(): string {
+    return this.getAttribute("id") ?? "";
+  }
+
+  get className(): string {
+    return this.getAttribute("class") ?? "";
+  }
+
+  get classList(): readonly string[] {
+    return this.className.split(/\s+/).filter(Boolean);
+  }
+
+  get textContent(): string {
+    return super.textContent ?? "";
+  }
+
+  get previousElementSibling(): DomElement | null {
+    let node = this.previousSibling;
+    while (node && !(node instanceof DomElement)) node = node.previousSibling;
+    return node;
+  }
+
+  get nextElementSibling(): DomElement | null {
+    let node = this.nextSibling;
+    while (node && !(node instanceof DomElement)) node = node.nextSibling;
+    return node;
+  }
+
+  get innerHTML(): string {
+    return this.childNodes.map((child) => serializeNode(child, this.isHtml))
+      .join("");
+  }
+
+  get outerHTML(): string {
+    return serializeNode(this, this.isHtml);
+  }
+
+  getAttribute(qualifiedName: string): string | null {
+    const name = this.isHtml
+      ? String(qualifiedName).toLowerCase()
+      : String(qualifiedName);
+    return this.attributes.find((attribute) => attribute.name === name)?.value ??
+      null;
+  }
+
+  hasAttribute(qualifiedName: string): boolean {
+    return this.getAttribute(qualifiedName) !== null;
+  }
+
+  hasAttributes(): boolean {
+    return this.attributes.length > 0;
+  }
+
+  getAttributeNames(): string[] {
+    return this.attributes.map((attribute) => attribute.name);
+  }
+
+  matches(selectors: string): boolean {
+    return matchesSelectorList(this, parseSelectorList(String(selectors)), null);
+  }
+
+  closest(selectors: string): DomElement | null {
+    const selector = parseSelectorList(String(selectors));
+    for (
+      let element: DomElement | null = this;
+      element;
+      element = element.parentElement
+    ) {
+      if (matchesSelectorList(element, selector, null)) return element;
+    }
+    return null;
+  }
+
+  /**
+   * Whether the element has the given tag name, ignoring the case in HTML documents.
+   */
+  matchesTagName(name: string): boolean {
+    return this.isHtml
+      ? this.localName === name.toLowerCase()
+      : this.tagName === name || this.localName === name;
+  }
+}
+
+/**
+ * A read-only document created by `DOMParser`.
+ */
+class DomDocument extends DomParentNode {
+  readonly contentType: string;
+
+  constructor(contentType: string) {
+    super(DomNode.DOCUMENT_NODE, "#document");
+    this.contentType = contentType;
+  }
+
+  get textContent(): null {
+    return null;
+  }
+
+  get doctype(): DomDocumentType | null {
+    return (this.childNodes.find((child) => child instanceof DomDocumentType) as
+      | DomDocumentType
+      | undefined) ?? null;
+  }
+
+  get documentElement(): DomElement | null {
+    return this.firstElementChild;
+  }
+
+  get head(): DomElement | null {
+    return this.documentElement?.children.find((child) =>
+      child.localName === "head"
+    ) ?? null;
+  }
+
+  get body(): DomElement | null {
+    return this.documentElement?.children.find((child) =>
+      child.localName === "body"
+    ) ?? null;
+  }
+
+  get title(): string {
+    const title = this.getElementsByTagName("title")[0];
+    return title?.textContent.replace(/\s+/g, " ").trim() ?? "";
+  }
+
+  getElementById(elementId: string): DomElement | null {
+    const id = String(elementId);
+    let found: DomElement | null = null;
+    walkElements(this, (element) => {
+      if (element.getAttribute("id") === id) {
+        found = element;
+        return true;
+      }
+      return false;
+    });
+    return found;
+  }
+}
+
+/**
+ * Visit the descendant elements in tree order until `visit` returns true.
+ */
+function walkElements(
+  root: DomNode,
+  visit: (element: DomElement) => boolean,
+): boolean {
+  for (const child of root.childNodes) {
+    if (child instanceof DomElement) {
+      if (visit(child) || walkElements(child, visit)) return true;
+    }
+  }
+  return false;
+}
+
+/**
+ * Build the read-only nodes of a parsed document, linking parents and siblings.
+ */
+function buildDomNode(node: ParsedDomNode, isHtml: boolean): DomNode {
+  switch (node.type) {
+    case "element": {
+      const inHtmlNamespace = isHtml && node.namespace === HTML_NAMESPACE;
+      const element = new DomElement(
+        inHtmlNamespace ? node.name.toUpperCase() : node.name,
+        node.namespace,
+        Object.freeze(
+          node.attributes.map(([name, value]) => Object.freeze({ name, value })),
+        ),
+        inHtmlNamespace,
+      );
+      appendDomChildren(element, node.children, isHtml);
+      return element;
+    }
+    case "text":
+      return new DomText(node.value);
+    case "comment":
+      return new DomComment(node.value);
+    case "doctype":
+      return new DomDocumentType(node.name);
+  }
+}
+
+function appendDomChildren(
+  parent: DomNode,
+  children: ParsedDomNode[],
+  isHtml: boolean,
+) {
+  const nodes = children.map((child) => buildDomNode(child, isHtml));
+  nodes.forEach((node, index) => {
+    node.parentNode = parent;
+    node.previousSibling = nodes[index - 1] ?? null;
+    node.nextSibling = nodes[index + 1] ?? null;
+  });
+  (parent as { childNodes: readonly DomNode[] }).childNodes = Object.freeze(
+    nodes,
+  );
+}
+
+function escapeMarkup(text: string, attribute: boolean): string {
+  const escaped = text.replace(/&/g, "&amp;").replace(/\u00a0/g, "&nbsp;");
+  return attribute
+    ? escaped.replace(/"/g, "&quot;")
+    : escaped.replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
+/**
+ * Serialize a node as HTML or XML.
+ */
+function serializeNode(node: DomNode, isHtml: boolean): string {
+  if (node instanceof DomText) {
+    const parent = node.parentElement?.localName;
+    // The content of raw text elements is never escaped in HTML.
+    if (isHtml && (parent === "script" || parent === "style")) return node.data;
+    return escapeMarkup(node.data, false);
+  }
+  if (node instanceof DomComment) return `<!--${node.data}-->`;
+  if (node instanceof DomDocumentType) return `<!DOCTYPE ${node.name}>`;
+  if (node instanceof DomElement) {
+    const name = isHtml ? node.localName : node.tagName;
+    const attributes = node.attributes
+      .map((attribute) =>
+        ` ${attribute.name}="${escapeMarkup(attribute.value, true)}"`
+      )
+      .join("");
+    if (isHtml && voidElements.has(name)) return `<${name}${attributes}>`;
+    if (!isHtml && node.childNodes.length === 0) {
+      return `<${name}${attributes}/>`;
+    }
+    return `<${name}${attributes}>${node.innerHTML}</${name}>`;
+  }
+  return node.childNodes.map((child) => serializeNode(child, isHtml)).join("");
+}
+
+/**
+ * A simple selector of a compound selector, e.g. `div`, `#id`, `.class` or `[attr=value]`.
+ */
+type SimpleSelector =
+  | { kind: "type"; name: string }
+  | { kind: "id"; id: string }
+  | { kind: "class"; name: string }
+  | {
+    kind: "attribute";
+    name: string;
+    operator: string | null;
+    value: string;
+    caseInsensitive: boolean;
+  }
+  | { kind: "pseudo"; name: string; argument: SelectorList | [number, number] | null };
+
+/**
+ * A complex selector, from the leftmost compound selector to the subject of the selector.
+ */
+type ComplexSelector = { combinator: string; compound: SimpleSelector[] }[];
+
+type SelectorList = ComplexSelector[];
+
+function selectorSyntaxError(selectors: string): DOMException {
+  return new DOMException(
+    `'${selectors}' is not a valid selector`,
+    "SyntaxError",
+  );
+}
+
+/**
+ * Parse a selector list supporting type, universal, id, class and attribute selectors, the
+ * descendant, child and sibling combinators and the `:not()`, `:is()`, `:root`, `:empty`,
+ * `:first-child`, `:last-child`, `:only-child`, `:nth-child()` and `:nth-last-child()`
+ * pseudo-classes. These are the structural selectors a static document can match, others throw
+ * a `SyntaxError`.
+ */
+function parseSelectorList(selectors: string): SelectorList {
+  let position = 0;
+  const identifier = /^(?:[a-zA-Z0-9_\-\u00a0-\uffff]|\\.)+/;
+
+  const skipWhitespace = () => {
+    while (/\s/.test(selectors[position] ?? "")) position++;
+  };
+  const readIdentifier = (): string => {
+    const match = identifier.exec(selectors.slice(position));
+    if (!match) throw selectorSyntaxError(selectors);
+    position += match[0].length;
+    return match[0].replace(/\\(.)/g, "$1");
+  };
+  const readValue = (): string => {
+    const quote = selectors[position];
+    if (quote !== '"' && quote !== "'") return readIdentifier();
+    const end = selectors.indexOf(quote, position + 1);
+    if (end === -1) throw selectorSyntaxError(selectors);
+    const value = selectors.slice(position + 1, end);
+    position = end + 1;
+    return value;
+  };
+  const readArgument = (): string => {
+    let depth = 1;
+    const start = position;
+    while (position < selectors.length) {
+      const c = selectors[position++];
+      if (c === "(") depth++;
+      if (c === ")" && --depth === 0) return selectors.slice(start, position - 1);
+    }
+    throw selectorSyntaxError(selectors);
+  };
+
+  const readCompound = (): SimpleSelector[] => {
+    const compound: SimpleSelector[] = [];
+    if (selectors[position] === "*") {
+      position++;
+    } else if (identifier.test(selectors.slice(position))) {
+      compound.push({ kind: "type", name: readIdentifier() });
+    }
+    while (position < selectors.length) {
+      const c = selectors[position];
+      if (c === "#") {
+        position++;
+        compound.push({ kind: "id", id: readIdentifier() });
+      } else if (c === ".") {
+        position++;
+        compound.push({ kind: "class", name: readIdentifier() });
+      } else if (c === "[") {
+        position++;
+        skipWhitespace();
+        const name = readIdentifier();
+        skipWhitespace();
+        let operator: string | null = null;
+        let value = "";
+        let caseInsensitive = false;
+        const match = /^([~|^$*]?=)/.exec(selectors.slice(position));
+        if (match) {
+          operator = match[1];
+          position += operator.length;
+          skipWhitespace();
+          value = readValue();
+          skipWhitespace();
+          if (/^[iI]\b/.test(selectors.slice(position))) {
+            caseInsensitive = true;
+            position++;
+            skipWhitespace();
+          }
+        }
+        if (selectors[position++] !== "]") throw selectorSyntaxError(selectors);
+        compound.push({ kind: "attribute", name, operator, value, caseInsensitive });
+      } else if (c === ":") {
+        position++;
+        const name = readIdentifier().toLowerCase();
+        let argument: SimpleSelector & { kind: "pseudo" } = {
+          kind: "pseudo",
+          name,
+          argument: null,
+        };
+        if (selectors[position] === "(") {
+          position++;
+          const text = readArgument().trim();
+          if (name === "not" || name === "is") {
+            argument = { kind: "pseudo", name, argument: parseSelectorList(text) };
+          } else if (name === "nth-child" || name === "nth-last-child") {
+            argument = { kind: "pseudo", name, argument: parseNth(text, selectors) };
+          } else {
+            throw selectorSyntaxError(selectors);
+          }
+        } else if (
+          ![
+            "root",
+            "empty",
+            "first-child",
+            "last-child",
+            "only-child",
+          ].includes(name)
+        ) {
+          throw selectorSyntaxError(selectors);
+        }
+        compound.push(argument);
+      } else {
+        break;
+      }
+    }
+    return compound;
+  };
+
+  const list: SelectorList = [];
+  while (true) {
+    const complex: ComplexSelector = [];
+    let combinator = " ";
+    skipWhitespace();
+    while (true) {
+      const start = position;
+      const compound = readCompound();
+      if (position === start) throw selectorSyntaxError(selectors);
+      complex.push({ combinator, compound });
+      const beforeWhitespace = position;
+      skipWhitespace();
+      const c = selectors[position];
+      if (c === ">" || c === "+" || c === "~") {
+        combinator = c;
+        position++;
+        skipWhitespace();
+      } else if (c === "," || c === undefined) {
+        break;
+      } else if (position > beforeWhitespace) {
+        combinator = " ";
+      } else {
+        throw selectorSyntaxError(selectors);
+      }
+    }
+    list.push(complex);
+    if (selectors[position] === undefined) return list;
+    position++;
+  }
+}
+
+/**
+ * Parse the `An+B` argument of `:nth-child()`.
+ */
+function parseNth(text: string, selectors: string): [number, number] {
+  const normalized = text.replace(/\s+/g, "").toLowerCase();
+  if (normalized === "odd") return [2, 1];
+  if (normalized === "even") return [2, 0];
+  const match = /^([+-]?\d*)n([+-]\d+)?$|^([+-]?\d+)$/.exec(normalized);
+  if (!match) throw selectorSyntaxError(selectors);
+  if (match[3] !== undefined) return [0, Number(match[3])];
+  const a = match[1] === "" || match[1] === "+"
+    ? 1
+    : match[1] === "-"
+    ? -1
+    : Number(match[1]);
+  return [a, Number(match[2] ?? 0)];
+}
+
+function matchesSelectorList(
+  element: DomElement,
+  list: SelectorList,
+  scope: DomNode | null,
+): boolean {
+  return list.some((complex) =>
+    matchesComplex(element, complex, complex.length - 1, scope)
+  );
+}
+
+/**
+ * Match a complex selector right to left, starting with the compound selector at `index`.
+ */
+function matchesComplex(
+  element: DomElement,
+  complex: ComplexSelector,
+  index: number,
+  scope: DomNode | null,
+): boolean {
+  if (!matchesCompound(element, complex[index].compound)) return false;
+  if (index === 0) return true;
+  // Elements outside of the node `querySelector` was called on can't match.
+  const inScope = (candidate: DomElement) =>
+    scope === null || (scope !== candidate && scope.contains(candidate));
+
+  switch (complex[index].combinator) {
+    case ">": {
+      const parent = element.parentElement;
+      return !!parent && inScope(parent) &&
+        matchesComplex(parent, complex, index - 1, scope);
+    }
+    case "+": {
+      const sibling = element.previousElementSibling;
+      return !!sibling && matchesComplex(sibling, complex, index - 1, scope);
+    }
+    case "~": {
+      for (
+        let sibling = element.previousElementSibling;
+        sibling;
+        sibling = sibling.previousElementSibling
+      ) {
+        if (matchesComplex(sibling, complex, index - 1, scope)) return true;
+      }
+      return false;
+    }
+    default: {
+      for (
+        let ancestor = element.parentElement;
+        ancestor && inScope(ancestor);
+        ancestor = ancestor.parentElement
+      ) {
+        if (matchesComplex(ancestor, complex, index - 1, scope)) return true;
+      }
+      return false;
+    }
+  }
+}
+
+function matchesCompound(
+  element: DomElement,
+  compound: SimpleSelector[],
+): boolean {
+  return compound.every((selector) => matchesSimple(element, selector));
+}
+
+function matchesSimple(element: DomElement, selector: SimpleSelector): boolean {
+  switch (selector.kind) {
+    case "type":
+      return element.matchesTagName(selector.name);
+    case "id":
+      return element.id === selector.id;
+    case "class":
+      return element.classList.includes(selector.name);
+    case "attribute": {
+      let actual = element.getAttribute(selector.name);
+      if (actual === null) return false;
+      if (selector.operator === null) return true;
+      let expected = selector.value;
+      if (selector.caseInsensitive) {
+        actual = actual.toLowerCase();
+        expected = expected.toLowerCase();
+      }
+      switch (selector.operator) {
+        case "=":
+          return actual === expected;
+        case "~=":
+          return actual.split(/\s+/).includes(expected);
+        case "|=":
+          return actual === expected || actual.startsWith(`${expected}-`);
+        case "^=":
+          return expected !== "" && actual.startsWith(expected);
+        case "$=":
+          return expected !== "" && actual.endsWith(expected);
+        default:
+          return expected !== "" && actual.includes(expected);
+      }
+    }
+    case "pseudo":
+      return matchesPseudoClass(element, selector);
+  }
+}
+
+function matchesPseudoClass(
+  element: DomElement,
+  selector: SimpleSelector & { kind: "pseudo" },
+): boolean {
+  const siblings = element.parentNode?.childNodes.filter((
+    node,
+  ): node is DomElement => node instanceof DomElement) ?? [element];
+  switch (selector.name) {
+    case "not":
+      return !matchesSelectorList(
+        element,
+        selector.argument as SelectorList,
+        null,
+      );
+    case "is":
+      return matchesSelectorList(element, selector.argument as SelectorList, null);
+    case "root":
+      return element.parentNode instanceof DomDocument;
+    case "empty":
+      return element.childNodes.every((node) =>
+        node instanceof DomComment || (node instanceof DomText && node.data === "")
+      );
+    case "first-child":
+      return siblings[0] === element;
+    case "last-child":
+      return siblings[siblings.length - 1] === element;
+    case "only-child":
+      return siblings.length === 1;
+    default: {
+      const [a, b] = selector.argument as [number, number];
+      const index = selector.name === "nth-child"
+        ? siblings.indexOf(element) + 1
+        : siblings.length - siblings.indexOf(element);
+      return a === 0 ? index === b : (index - b) / a >= 0 && (index - b) % a === 0;
+    }
+  }
+}
+
+/**
+ * The `DOMParser` interface parses HTML or XML source into a read-only document, which can be
+ * searched with `querySelector`, `getElementsByTagName` and friends. Malformed XML parses to a
+ * document with a `parsererror` root element, like in browsers. Elements nested deeper than 512
+ * levels get their descendants as children instead, as browsers do for HTML.
+ *
+ * The selectors are a subset of the CSS ones, listed on `parseSelectorList`. The document only
+ * exists in JavaScript once parsed, so the selectors are matched here rather than with the
+ * `Selector` of the scraper crate, which only matches the nodes of its own tree. Unsupported
+ * selectors, such as `:hover` or `:has()`, throw a `SyntaxError` instead of never matching.
+ *
+ * @example
+ * ```ts
+ * const document = new DOMParser().parseFromString(
+ *   "<ul><li class='done'>Write</li><li>Ship</li></ul>",
+ *   "text/html",
+ * );
+ * console.log(document.querySelector("li:not(.done)")?.textContent);
+ * ```
+ */
+class DOMParser {
+  parseFromString(string: string, type: string): DomDocument {
+    const contentType = String(type);
+    const nodes: ParsedDomNode[] = JSON.parse(
+      internal_dom_parse(String(string), contentType),
+    );
+    const document = new DomDocument(contentType);
+    appendDomChildren(document, nodes, contentType === "text/html");
+    return document;
+  }
+}
@@ -0,0 +1,44 @@
+use andromeda_core::{extension_op, ErrorThrower, Extension, HostData};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::{Global, Value},
+};
+
+use crate::RuntimeMacroTask;
+
+/// Errors extension for Andromeda.
+/// This extension defines the error classes thrown by the other extensions' ops.
+#[derive(Default)]
+pub struct ErrorsExt;
+
+impl ErrorsExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "errors",
+            ops: vec![extension_op!(
+                "internal_set_error_thrower",
+                Self::internal_set_error_thrower,
+                1
+            )],
+            storage: None,
+            files: vec![include_str!("./mod.ts")],
+            deps: &["web"],
+            cfg: None,
+        }
+    }
+
+    /// Register the function used by the ops to throw instances of the error classes.
+    fn internal_set_error_thrower(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let thrower = ErrorThrower(Global::new(agent, args.get(0)));
+
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        host_data.insert_storage(thrower);
+
+        Ok(Value::Undefined)
+    }
+}
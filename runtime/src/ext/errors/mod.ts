@@ -0,0 +1,114 @@
+// deno-lint-ignore-file no-unused-vars
+
+/**
+ * Error classes thrown by the runtime, exposed as `Andromeda.errors`.
+ * Every class extends `Error` so they can be caught by type.
+ *
+ * @example
+ * ```ts
+ * try {
+ *   Andromeda.readTextFileSync("missing.txt");
+ * } catch (error) {
+ *   if (error instanceof Andromeda.errors.NotFound) {
+ *     console.log("The file does not exist");
+ *   }
+ * }
+ * ```
+ */
+const AndromedaErrors = (() => {
+  const names = [
+    "NotFound",
+    "PermissionDenied",
+    "ConnectionRefused",
+    "ConnectionReset",
+    "ConnectionAborted",
+    "NotConnected",
+    "AddrInUse",
+    "AddrNotAvailable",
+    "BrokenPipe",
+    "AlreadyExists",
+    "InvalidData",
+    "TimedOut",
+    "Interrupted",
+    "WriteZero",
+    "UnexpectedEof",
+    "Busy",
+    "NotSupported",
+    "BadResource",
+  ];
+  const errors: Record<string, ErrorConstructor> = {};
+  for (const name of names) {
+    const ErrorClass = class extends Error {
+      constructor(message?: string) {
+        super(message);
+        this.name = name;
+      }
+    };
+    Object.defineProperty(ErrorClass, "name", { value: name });
+    errors[name] = ErrorClass as ErrorConstructor;
+  }
+  return errors;
+})();
+
+internal_set_error_thrower(
+  (name: string, message: string, domExceptionName?: string) => {
+    if (name === "DOMException") {
+      throw new DOMException(message, domExceptionName);
+    }
+    const ErrorClass = AndromedaErrors[name] || Error;
+    throw new ErrorClass(message);
+  },
+);
+
+/**
+ * V8's stack trace API, which logging and telemetry libraries rely on. The engine doesn't record
+ * call sites, so `prepareStackTrace` gets an empty list of frames and `stack` only holds the
+ * `name: message` header that starts V8 stack traces.
+ */
+(() => {
+  const ErrorConstructor = Error as any;
+  const formatStack = (error: object) => {
+    const prepare = ErrorConstructor.prepareStackTrace;
+    return typeof prepare === "function"
+      ? prepare(error, [])
+      : Error.prototype.toString.call(error);
+  };
+  const defineStack = (target: object, stack: unknown) => {
+    Object.defineProperty(target, "stack", {
+      value: stack,
+      writable: true,
+      configurable: true,
+      enumerable: false,
+    });
+  };
+
+  if (!("stackTraceLimit" in ErrorConstructor)) {
+    ErrorConstructor.stackTraceLimit = 10;
+  }
+  if (typeof ErrorConstructor.captureStackTrace !== "function") {
+    ErrorConstructor.captureStackTrace = function captureStackTrace(
+      target: object,
+      _constructorOpt?: (...args: any[]) => unknown,
+    ) {
+      if (
+        target === null ||
+        (typeof target !== "object" && typeof target !== "function")
+      ) {
+        throw new TypeError("Error.captureStackTrace target must be an object");
+      }
+      defineStack(target, formatStack(target));
+    };
+  }
+  if (!("stack" in Error.prototype) && !("stack" in new Error())) {
+    Object.defineProperty(Error.prototype, "stack", {
+      get(this: object) {
+        return formatStack(this);
+      },
+      set(this: object, stack: unknown) {
+        defineStack(this, stack);
+      },
+      configurable: true,
+      enumerable: false,
+    });
+  }
+})();
@@ -0,0 +1,112 @@
+use andromeda_core::{extension_op, ErrorClass, Extension, OpError};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::Value,
+};
+
+use crate::RuntimeMacroTask;
+
+/// Formats extension for Andromeda.
+/// This extension parses and writes TOML and YAML documents. Values cross the JavaScript boundary as JSON.
+#[derive(Default)]
+pub struct FormatsExt;
+
+impl FormatsExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "formats",
+            ops: vec![
+                extension_op!("internal_toml_parse", Self::internal_toml_parse, 1),
+                extension_op!("internal_toml_stringify", Self::internal_toml_stringify, 1),
+                extension_op!("internal_yaml_parse", Self::internal_yaml_parse, 1),
+                extension_op!("internal_yaml_stringify", Self::internal_yaml_stringify, 1),
+            ],
+            storage: None,
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Parse a TOML document into JSON. Dates and times become RFC 3339 strings.
+    fn internal_toml_parse(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        Self::convert(agent, args, |text| {
+            let value: toml::Value = toml::from_str(text).map_err(invalid_data)?;
+            serde_json::to_string(&toml_to_json(value)).map_err(invalid_data)
+        })
+    }
+
+    /// Write JSON as a TOML document. The top-level value must be an object.
+    fn internal_toml_stringify(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        Self::convert(agent, args, |json| {
+            let value: serde_json::Value = serde_json::from_str(json).map_err(invalid_data)?;
+            toml::to_string(&value).map_err(invalid_data)
+        })
+    }
+
+    /// Parse a YAML document into JSON.
+    fn internal_yaml_parse(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        Self::convert(agent, args, |text| {
+            let value: serde_json::Value = serde_yaml::from_str(text).map_err(invalid_data)?;
+            serde_json::to_string(&value).map_err(invalid_data)
+        })
+    }
+
+    /// Write JSON as a YAML document.
+    fn internal_yaml_stringify(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        Self::convert(agent, args, |json| {
+            let value: serde_json::Value = serde_json::from_str(json).map_err(invalid_data)?;
+            serde_yaml::to_string(&value).map_err(invalid_data)
+        })
+    }
+
+    /// Run a conversion on the first argument as a string, returning its output string or throwing its error.
+    fn convert(
+        agent: &mut Agent,
+        args: ArgumentsList,
+        convert: impl FnOnce(&str) -> Result<String, OpError>,
+    ) -> JsResult<Value> {
+        let input = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        match convert(&input) {
+            Ok(output) => Ok(Value::from_string(agent, output)),
+            Err(e) => Err(e.throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+}
+
+/// Convert a TOML value to JSON, which has no date type, formatting dates and times as strings.
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(string) => serde_json::Value::String(string),
+        toml::Value::Integer(integer) => serde_json::Value::from(integer),
+        toml::Value::Float(float) => serde_json::Value::from(float),
+        toml::Value::Boolean(boolean) => serde_json::Value::Bool(boolean),
+        toml::Value::Datetime(datetime) => serde_json::Value::String(datetime.to_string()),
+        toml::Value::Array(array) => array.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => table
+            .into_iter()
+            .map(|(key, value)| (key, toml_to_json(value)))
+            .collect(),
+    }
+}
+
+fn invalid_data(error: impl std::fmt::Display) -> OpError {
+    OpError::new(ErrorClass::InvalidData, error.to_string())
+}
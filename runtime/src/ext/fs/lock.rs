@@ -0,0 +1,250 @@
+use std::{fs::File, io};
+
+/// Kind of advisory lock held on a file or a byte range of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Many holders can share the lock, usually readers.
+    Shared,
+    /// Only one holder can own the lock, usually a writer.
+    Exclusive,
+}
+
+impl LockMode {
+    pub fn from_exclusive(exclusive: bool) -> Self {
+        if exclusive {
+            Self::Exclusive
+        } else {
+            Self::Shared
+        }
+    }
+}
+
+/// Lock the whole file, `flock`-style. When `wait` is false it returns `Ok(false)`
+/// instead of blocking if the lock is held by somebody else.
+pub fn lock(file: &File, mode: LockMode, wait: bool) -> io::Result<bool> {
+    acquired(sys::lock(file, mode, wait))
+}
+
+/// Release a lock previously acquired with [lock].
+pub fn unlock(file: &File) -> io::Result<()> {
+    sys::unlock(file)
+}
+
+/// Lock `length` bytes of the file starting at `start`. A `length` of 0 locks
+/// until the end of the file, including bytes appended later.
+///
+/// On Linux the lock belongs to the opened file, so two handles of the same file conflict
+/// like two processes would. Other unix systems only have process-wide POSIX locks: handles
+/// of the same process never conflict, and closing any descriptor of the file releases them.
+/// On unix range locks and whole-file [lock]s don't interact.
+pub fn lock_range(
+    file: &File,
+    mode: LockMode,
+    start: u64,
+    length: u64,
+    wait: bool,
+) -> io::Result<bool> {
+    acquired(sys::lock_range(file, mode, start, length, wait))
+}
+
+/// Release a byte-range lock previously acquired with [lock_range].
+pub fn unlock_range(file: &File, start: u64, length: u64) -> io::Result<()> {
+    sys::unlock_range(file, start, length)
+}
+
+fn acquired(result: io::Result<()>) -> io::Result<bool> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(e) if sys::is_contended(&e) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::{fs::File, io, os::unix::io::AsRawFd};
+
+    use super::LockMode;
+
+    pub fn lock(file: &File, mode: LockMode, wait: bool) -> io::Result<()> {
+        let mut operation = match mode {
+            LockMode::Shared => libc::LOCK_SH,
+            LockMode::Exclusive => libc::LOCK_EX,
+        };
+        if !wait {
+            operation |= libc::LOCK_NB;
+        }
+        cvt(unsafe { libc::flock(file.as_raw_fd(), operation) })
+    }
+
+    pub fn unlock(file: &File) -> io::Result<()> {
+        cvt(unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_UN) })
+    }
+
+    pub fn lock_range(
+        file: &File,
+        mode: LockMode,
+        start: u64,
+        length: u64,
+        wait: bool,
+    ) -> io::Result<()> {
+        let kind = match mode {
+            LockMode::Shared => libc::F_RDLCK,
+            LockMode::Exclusive => libc::F_WRLCK,
+        };
+        match fcntl_lock(file, kind, start, length, wait) {
+            // `fcntl` only gives write locks on file descriptors open for writing.
+            Err(e) if mode == LockMode::Exclusive && e.raw_os_error() == Some(libc::EBADF) => {
+                Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "exclusive range locks need a file opened for writing",
+                ))
+            }
+            result => result,
+        }
+    }
+
+    pub fn unlock_range(file: &File, start: u64, length: u64) -> io::Result<()> {
+        fcntl_lock(file, libc::F_UNLCK, start, length, false)
+    }
+
+    pub fn is_contended(error: &io::Error) -> bool {
+        error.kind() == io::ErrorKind::WouldBlock
+            || matches!(error.raw_os_error(), Some(libc::EACCES | libc::EAGAIN))
+    }
+
+    fn fcntl_lock(
+        file: &File,
+        kind: libc::c_short,
+        start: u64,
+        length: u64,
+        wait: bool,
+    ) -> io::Result<()> {
+        // SAFETY: `flock` is a plain C struct, all zeroes is a valid value.
+        let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+        lock.l_type = kind;
+        lock.l_whence = libc::SEEK_SET as _;
+        lock.l_start = start as _;
+        lock.l_len = length as _;
+        // Open file description locks need `l_pid` to be 0, which it already is.
+        let command = if wait { SET_LOCK_WAIT } else { SET_LOCK };
+        cvt(unsafe { libc::fcntl(file.as_raw_fd(), command, &lock) })
+    }
+
+    /// Open file description locks, owned by the opened file rather than by the process.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const SET_LOCK: libc::c_int = libc::F_OFD_SETLK;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const SET_LOCK_WAIT: libc::c_int = libc::F_OFD_SETLKW;
+
+    /// Classic POSIX locks, owned by the process and released when any descriptor of the file closes.
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const SET_LOCK: libc::c_int = libc::F_SETLK;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const SET_LOCK_WAIT: libc::c_int = libc::F_SETLKW;
+
+    fn cvt(result: libc::c_int) -> io::Result<()> {
+        if result == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::{fs::File, io, os::windows::io::AsRawHandle};
+
+    use windows_sys::Win32::{
+        Foundation::{ERROR_IO_PENDING, ERROR_LOCK_VIOLATION},
+        Storage::FileSystem::{
+            LockFileEx, UnlockFileEx, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY,
+        },
+        System::IO::OVERLAPPED,
+    };
+
+    use super::LockMode;
+
+    pub fn lock(file: &File, mode: LockMode, wait: bool) -> io::Result<()> {
+        lock_range(file, mode, 0, 0, wait)
+    }
+
+    pub fn unlock(file: &File) -> io::Result<()> {
+        unlock_range(file, 0, 0)
+    }
+
+    pub fn lock_range(
+        file: &File,
+        mode: LockMode,
+        start: u64,
+        length: u64,
+        wait: bool,
+    ) -> io::Result<()> {
+        let mut flags = 0;
+        if mode == LockMode::Exclusive {
+            flags |= LOCKFILE_EXCLUSIVE_LOCK;
+        }
+        if !wait {
+            flags |= LOCKFILE_FAIL_IMMEDIATELY;
+        }
+        let length = whole_file_length(length);
+        let mut overlapped = overlapped_at(start);
+        cvt(unsafe {
+            LockFileEx(
+                file.as_raw_handle() as _,
+                flags,
+                0,
+                length as u32,
+                (length >> 32) as u32,
+                &mut overlapped,
+            )
+        })
+    }
+
+    pub fn unlock_range(file: &File, start: u64, length: u64) -> io::Result<()> {
+        let length = whole_file_length(length);
+        let mut overlapped = overlapped_at(start);
+        cvt(unsafe {
+            UnlockFileEx(
+                file.as_raw_handle() as _,
+                0,
+                length as u32,
+                (length >> 32) as u32,
+                &mut overlapped,
+            )
+        })
+    }
+
+    pub fn is_contended(error: &io::Error) -> bool {
+        matches!(
+            error.raw_os_error(),
+            Some(code) if code == ERROR_LOCK_VIOLATION as i32 || code == ERROR_IO_PENDING as i32
+        )
+    }
+
+    /// Windows has no "until the end of the file" lock, so lock every possible byte instead.
+    fn whole_file_length(length: u64) -> u64 {
+        if length == 0 {
+            u64::MAX
+        } else {
+            length
+        }
+    }
+
+    fn overlapped_at(start: u64) -> OVERLAPPED {
+        // SAFETY: `OVERLAPPED` is a plain C struct, all zeroes is a valid value.
+        let mut overlapped: OVERLAPPED = unsafe { std::mem::zeroed() };
+        overlapped.Anonymous.Anonymous.Offset = start as u32;
+        overlapped.Anonymous.Anonymous.OffsetHigh = (start >> 32) as u32;
+        overlapped
+    }
+
+    fn cvt(result: i32) -> io::Result<()> {
+        if result == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,675 @@
+mod lock;
+
+use std::{
+    borrow::BorrowMut,
+    fs::{File, FileTimes, OpenOptions},
+    io::{BufRead, BufReader},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use nova_vm::{
+    ecmascript::{
+        builtins::{
+            promise_objects::promise_abstract_operations::promise_capability_records::PromiseCapability,
+            ArgumentsList,
+        },
+        execution::{agent::ExceptionType, Agent, JsResult},
+        types::{Global, IntoValue, Value},
+    },
+    SmallInteger,
+};
+
+use andromeda_core::{
+    extension_op,
+    webidl::{to_double, to_unsigned_long_long, IntegerConversion::EnforceRange},
+    Extension, HostData, MacroTask, OpError, OpsStorage, Permissions, ResourceTable, Rid,
+};
+
+use crate::RuntimeMacroTask;
+use lock::LockMode;
+
+/// Reader of [FsExt::internal_open_lines], shared with the blocking pool while it reads a line.
+type LineReader = Arc<Mutex<BufReader<File>>>;
+
+struct FsExtResources {
+    /// Open files, shared with the blocking pool while it waits for a lock.
+    files: ResourceTable<Arc<File>>,
+    line_readers: ResourceTable<LineReader>,
+}
+
+#[derive(Default)]
+pub struct FsExt;
+
+impl FsExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "fs",
+            ops: vec![
+                extension_op!("internal_read_text_file", Self::internal_read_text_file, 1),
+                extension_op!(
+                    "internal_write_text_file",
+                    Self::internal_write_text_file,
+                    2
+                ),
+                extension_op!("internal_create_file", Self::internal_create_file, 1),
+                extension_op!("internal_copy_file", Self::internal_copy_file, 2),
+                extension_op!("internal_mk_dir", Self::internal_mk_dir, 1),
+                extension_op!("internal_open_file", Self::internal_open_file, 2),
+                extension_op!("internal_close_file", Self::internal_close_file, 1),
+                extension_op!("internal_lock_file", Self::internal_lock_file, 2),
+                extension_op!(async "internal_lock_file_async", Self::internal_lock_file_async, 2),
+                extension_op!("internal_try_lock_file", Self::internal_try_lock_file, 2),
+                extension_op!("internal_unlock_file", Self::internal_unlock_file, 1),
+                extension_op!(
+                    "internal_lock_file_range",
+                    Self::internal_lock_file_range,
+                    5
+                ),
+                extension_op!(
+                    async "internal_lock_file_range_async",
+                    Self::internal_lock_file_range_async,
+                    4
+                ),
+                extension_op!(
+                    "internal_unlock_file_range",
+                    Self::internal_unlock_file_range,
+                    3
+                ),
+                extension_op!(async "internal_chmod", Self::internal_chmod, 2),
+                extension_op!(async "internal_chown", Self::internal_chown, 3),
+                extension_op!(async "internal_utime", Self::internal_utime, 3),
+                extension_op!(async "internal_link", Self::internal_link, 2),
+                extension_op!(async "internal_read_link", Self::internal_read_link, 1),
+                extension_op!(async "internal_real_path", Self::internal_real_path, 1),
+                extension_op!(async "internal_truncate", Self::internal_truncate, 2),
+                extension_op!("internal_open_lines", Self::internal_open_lines, 1),
+                extension_op!(async "internal_next_line", Self::internal_next_line, 1),
+                extension_op!("internal_close_lines", Self::internal_close_lines, 1),
+            ],
+            storage: Some(Box::new(|storage: &mut OpsStorage| {
+                storage.insert(FsExtResources {
+                    files: ResourceTable::<Arc<File>>::new(),
+                    line_readers: ResourceTable::<LineReader>::new(),
+                });
+            })),
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Read a text file and return the content as a string.
+    pub fn internal_read_text_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_read(Path::new(&path))
+        })?;
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::from_string(agent, content))
+    }
+
+    /// Write a text file with the content of the second argument.
+    pub fn internal_write_text_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let content = args.get(1).to_string(agent.borrow_mut())?;
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_write(Path::new(&path))
+        })?;
+        std::fs::write(path, content.as_str(agent))
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Undefined)
+    }
+
+    /// Create a file and return a Rid.
+    pub fn internal_create_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_write(Path::new(&path))
+        })?;
+        let file =
+            File::create(path).map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+
+        let rid = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .with_storage(|resources: &FsExtResources| resources.files.push(Arc::new(file)));
+
+        Ok(Value::Integer(SmallInteger::from(rid.index())))
+    }
+
+    /// Copy a file from the first argument to the second argument.
+    pub fn internal_copy_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let from = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let to = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_read(Path::new(&from))?;
+            permissions.check_write(Path::new(&to))
+        })?;
+
+        std::fs::copy(from, to).map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Undefined)
+    }
+
+    /// Create a directory.
+    pub fn internal_mk_dir(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_write(Path::new(&path))
+        })?;
+        std::fs::create_dir(path).map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Undefined)
+    }
+
+    /// Open a file and return a Rid. The file is opened for reading, and also for writing if the
+    /// second argument is `true`, which exclusive byte-range locks need on unix.
+    pub fn internal_open_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let write = args.get(1) == Value::Boolean(true);
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_read(Path::new(&path))?;
+            if write {
+                permissions.check_write(Path::new(&path))?;
+            }
+            Ok(())
+        })?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(write)
+            .open(path)
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+
+        let rid = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .with_storage(|resources: &FsExtResources| resources.files.push(Arc::new(file)));
+
+        Ok(Value::Integer(SmallInteger::from(rid.index())))
+    }
+
+    /// Close a file given its Rid.
+    pub fn internal_close_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+
+        let file = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .with_storage(|resources: &FsExtResources| resources.files.remove(rid));
+
+        match file {
+            Some(_) => Ok(Value::Undefined),
+            None => Err(OpError::bad_resource().throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+
+    /// Lock the whole file given its Rid, waiting until the lock is available.
+    /// The lock is exclusive if the second argument is `true`, shared otherwise.
+    pub fn internal_lock_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let mode = LockMode::from_exclusive(args.get(1) == Value::Boolean(true));
+
+        Self::with_file(agent, rid, |file| lock::lock(file, mode, true))
+            .map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Undefined)
+    }
+
+    /// Lock the whole file given its Rid on the blocking pool, returning a Promise resolved once
+    /// the lock is acquired. The lock is exclusive if the second argument is `true`.
+    pub fn internal_lock_file_async(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let mode = LockMode::from_exclusive(args.get(1) == Value::Boolean(true));
+        let file = Self::file(agent, rid).map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            lock::lock(&file, mode, true)?;
+            Ok(None)
+        }))
+    }
+
+    /// Try to lock the whole file given its Rid without waiting.
+    /// Returns `true` if the lock was acquired and `false` if somebody else holds it.
+    pub fn internal_try_lock_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let mode = LockMode::from_exclusive(args.get(1) == Value::Boolean(true));
+
+        let acquired = Self::with_file(agent, rid, |file| lock::lock(file, mode, false))
+            .map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Boolean(acquired))
+    }
+
+    /// Release the whole-file lock held on a file given its Rid.
+    pub fn internal_unlock_file(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+
+        Self::with_file(agent, rid, lock::unlock)
+            .map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Undefined)
+    }
+
+    /// Lock a byte range of a file given its Rid, the exclusiveness, the start offset, the length
+    /// and whether to wait until the lock is available. Returns `false` if it didn't wait and
+    /// somebody else holds the lock. A length of 0 locks until the end of the file. Exclusive
+    /// locks need the file opened for writing.
+    pub fn internal_lock_file_range(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let mode = LockMode::from_exclusive(args.get(1) == Value::Boolean(true));
+        let start = to_unsigned_long_long(agent, args.get(2), EnforceRange, "start")?;
+        let length = to_unsigned_long_long(agent, args.get(3), EnforceRange, "length")?;
+        let wait = args.get(4) == Value::Boolean(true);
+
+        let acquired = Self::with_file(agent, rid, |file| {
+            lock::lock_range(file, mode, start, length, wait)
+        })
+        .map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Boolean(acquired))
+    }
+
+    /// Lock a byte range of a file on the blocking pool given the same arguments as
+    /// [FsExt::internal_lock_file_range], returning a Promise resolved once the lock is acquired.
+    pub fn internal_lock_file_range_async(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let mode = LockMode::from_exclusive(args.get(1) == Value::Boolean(true));
+        let start = to_unsigned_long_long(agent, args.get(2), EnforceRange, "start")?;
+        let length = to_unsigned_long_long(agent, args.get(3), EnforceRange, "length")?;
+        let file = Self::file(agent, rid).map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            lock::lock_range(&file, mode, start, length, true)?;
+            Ok(None)
+        }))
+    }
+
+    /// Release a byte-range lock of a file given its Rid, the start offset and the length.
+    pub fn internal_unlock_file_range(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let start = to_unsigned_long_long(agent, args.get(1), EnforceRange, "start")?;
+        let length = to_unsigned_long_long(agent, args.get(2), EnforceRange, "length")?;
+
+        Self::with_file(agent, rid, |file| lock::unlock_range(file, start, length))
+            .map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+        Ok(Value::Undefined)
+    }
+
+    /// Change the permissions of a file given its path and a unix mode.
+    /// On Windows only the write bit of the owner is honored, by toggling the read-only flag.
+    pub fn internal_chmod(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let mode = args.get(1).to_uint32(agent)?;
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_write(Path::new(&path))
+        })?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            set_mode(Path::new(&path), mode)?;
+            Ok(None)
+        }))
+    }
+
+    /// Change the owner user and group of a file given its path, a uid and a gid.
+    /// `null` leaves the respective id unchanged. Not supported on Windows.
+    pub fn internal_chown(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let uid = Self::optional_uint32(agent, args.get(1))?;
+        let gid = Self::optional_uint32(agent, args.get(2))?;
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_write(Path::new(&path))
+        })?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            set_owner(Path::new(&path), uid, gid)?;
+            Ok(None)
+        }))
+    }
+
+    /// Change the access and modification times of a file or a directory given its path and both
+    /// times in seconds since the unix epoch.
+    pub fn internal_utime(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let accessed = Self::system_time(agent, args.get(1), "atime")?;
+        let modified = Self::system_time(agent, args.get(2), "mtime")?;
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_write(Path::new(&path))
+        })?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            let times = FileTimes::new()
+                .set_accessed(accessed)
+                .set_modified(modified);
+            open_for_times(Path::new(&path))?.set_times(times)?;
+            Ok(None)
+        }))
+    }
+
+    /// Create a hard link from the first argument to the second argument.
+    pub fn internal_link(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let original = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let link = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_read(Path::new(&original))?;
+            permissions.check_write(Path::new(&link))
+        })?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            std::fs::hard_link(original, link)?;
+            Ok(None)
+        }))
+    }
+
+    /// Read the target of a symbolic link given its path.
+    pub fn internal_read_link(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_read(Path::new(&path))
+        })?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            let target = std::fs::read_link(path)?;
+            Ok(Some(Self::display_path(&target)))
+        }))
+    }
+
+    /// Resolve a path to its absolute form with every symbolic link resolved.
+    pub fn internal_real_path(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_read(Path::new(&path))
+        })?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            let real_path = std::fs::canonicalize(path)?;
+            Ok(Some(Self::display_path(&real_path)))
+        }))
+    }
+
+    /// Truncate or extend a file given its path to the length in bytes of the second argument.
+    /// Lengths out of the range of `unsigned long long` throw a `TypeError`.
+    pub fn internal_truncate(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let length = to_unsigned_long_long(agent, args.get(1), EnforceRange, "length")?;
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_write(Path::new(&path))
+        })?;
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            OpenOptions::new()
+                .write(true)
+                .open(&path)?
+                .set_len(length)?;
+            Ok(None)
+        }))
+    }
+
+    /// Open a file to read it line by line and return the Rid of the reader.
+    pub fn internal_open_lines(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_read(Path::new(&path))
+        })?;
+        let file =
+            File::open(path).map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let reader = BufReader::new(file);
+        host_data.external_memory.allocate(reader.capacity());
+        let rid = host_data.with_storage(|resources: &FsExtResources| {
+            resources.line_readers.push(Arc::new(Mutex::new(reader)))
+        });
+
+        Ok(Value::Integer(SmallInteger::from(rid.index())))
+    }
+
+    /// Read the next line, without its line terminator, given the Rid of a line reader.
+    /// Returns a Promise that resolves with the line, or with `undefined` once the end of the
+    /// file is reached.
+    pub fn internal_next_line(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+
+        let reader = HostData::<RuntimeMacroTask>::from_agent(agent).with_storage(
+            |resources: &FsExtResources| {
+                resources
+                    .line_readers
+                    .get(rid)
+                    .map(|reader| Arc::clone(&reader))
+            },
+        );
+        let Some(reader) = reader else {
+            return Err(OpError::bad_resource().throw::<RuntimeMacroTask>(agent));
+        };
+
+        Ok(Self::spawn_fs_op(agent, move || {
+            let mut line = String::new();
+            if reader.lock().unwrap().read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.strip_suffix('\n').unwrap_or(&line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            Ok(Some(line.to_string()))
+        }))
+    }
+
+    /// Close a line reader given its Rid.
+    pub fn internal_close_lines(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let reader =
+            host_data.with_storage(|resources: &FsExtResources| resources.line_readers.remove(rid));
+
+        match reader {
+            Some(reader) => {
+                let capacity = reader.lock().unwrap().capacity();
+                host_data.external_memory.free(capacity);
+                Ok(Value::Undefined)
+            }
+            None => Err(OpError::bad_resource().throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+
+    /// Run a blocking file system operation outside of the JavaScript thread and return a Promise
+    /// that resolves with its string output (or `undefined`), or rejects with its error.
+    fn spawn_fs_op(
+        agent: &mut Agent,
+        op: impl FnOnce() -> std::io::Result<Option<String>> + Send + 'static,
+    ) -> Value {
+        let promise_capability = PromiseCapability::new(agent);
+        let root_value = Global::new(agent, promise_capability.promise().into_value());
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let macro_task_tx = host_data.macro_task_tx();
+        let job = host_data.blocking_pool.spawn(op);
+
+        host_data.spawn_macro_task(async move {
+            let macro_task = match job.await {
+                Ok(Ok(None)) => MacroTask::ResolvePromise(root_value),
+                Ok(Ok(Some(output))) => MacroTask::ResolvePromiseWithString(root_value, output),
+                Ok(Err(e)) => MacroTask::RejectPromise(root_value, e.into()),
+                Err(e) => {
+                    MacroTask::RejectPromise(root_value, OpError::from(std::io::Error::from(e)))
+                }
+            };
+            macro_task_tx.send(macro_task).unwrap();
+        });
+
+        Value::Promise(promise_capability.promise())
+    }
+
+    /// Convert a time in seconds since the unix epoch, throwing a `RangeError` if the system
+    /// can't represent it.
+    fn system_time(agent: &mut Agent, value: Value, context: &str) -> JsResult<SystemTime> {
+        let seconds = to_double(agent, value, context)?;
+        let time = Duration::try_from_secs_f64(seconds.abs())
+            .ok()
+            .and_then(|duration| {
+                if seconds < 0.0 {
+                    UNIX_EPOCH.checked_sub(duration)
+                } else {
+                    UNIX_EPOCH.checked_add(duration)
+                }
+            });
+        time.ok_or_else(|| {
+            agent.throw_exception(
+                ExceptionType::RangeError,
+                format!("{context} is out of the range of the system times"),
+            )
+        })
+    }
+
+    /// Convert an optional integer argument, where `null` and `undefined` mean no value.
+    fn optional_uint32(agent: &mut Agent, value: Value) -> JsResult<Option<u32>> {
+        match value {
+            Value::Null | Value::Undefined => Ok(None),
+            value => Ok(Some(value.to_uint32(agent)?)),
+        }
+    }
+
+    /// Format a path for JavaScript, dropping the `\\?\` verbatim prefix Windows adds to canonical paths.
+    fn display_path(path: &Path) -> String {
+        let path = path.to_string_lossy().into_owned();
+        if let Some(stripped) = path.strip_prefix(r"\\?\") {
+            if !stripped.starts_with(r"UNC\") {
+                return stripped.to_string();
+            }
+        }
+        path
+    }
+
+    /// The [File] behind the given Rid.
+    fn file(agent: &Agent, rid: Rid) -> Result<Arc<File>, OpError> {
+        HostData::<RuntimeMacroTask>::from_agent(agent).with_storage(
+            |resources: &FsExtResources| {
+                let file = resources.files.get(rid).ok_or_else(OpError::bad_resource)?;
+                Ok(Arc::clone(&file))
+            },
+        )
+    }
+
+    /// Run a closure with the [File] behind the given Rid. The storage isn't borrowed meanwhile,
+    /// so the closure may block, e.g. waiting for a lock.
+    fn with_file<R>(
+        agent: &Agent,
+        rid: Rid,
+        run: impl FnOnce(&File) -> std::io::Result<R>,
+    ) -> Result<R, OpError> {
+        let file = Self::file(agent, rid)?;
+        run(&file).map_err(OpError::from)
+    }
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    permissions.set_readonly(mode & 0o200 == 0);
+    std::fs::set_permissions(path, permissions)
+}
+
+/// Open a file or a directory to change its times, which doesn't need write access on unix.
+#[cfg(unix)]
+fn open_for_times(path: &Path) -> std::io::Result<File> {
+    File::open(path)
+}
+
+/// Open a file or a directory to change its times. Windows only opens directories with
+/// backup semantics.
+#[cfg(windows)]
+fn open_for_times(path: &Path) -> std::io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use windows_sys::Win32::Storage::FileSystem::FILE_FLAG_BACKUP_SEMANTICS;
+    OpenOptions::new()
+        .write(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)
+}
+
+#[cfg(unix)]
+fn set_owner(path: &Path, uid: Option<u32>, gid: Option<u32>) -> std::io::Result<()> {
+    std::os::unix::fs::chown(path, uid, gid)
+}
+
+#[cfg(not(unix))]
+fn set_owner(_path: &Path, _uid: Option<u32>, _gid: Option<u32>) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "chown is not supported on this platform",
+    ))
+}
@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+
+use andromeda_core::{extension_op, ErrorClass, Extension, OpError};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::Value,
+};
+use serde::Deserialize;
+
+use crate::RuntimeMacroTask;
+
+/// Allowlist of the sanitizer, sent from JavaScript as JSON. Missing fields keep ammonia's defaults.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct SanitizeOptions {
+    tags: Option<Vec<String>>,
+    attributes: Option<HashMap<String, Vec<String>>>,
+    generic_attributes: Option<Vec<String>>,
+    url_schemes: Option<Vec<String>>,
+    strip_comments: Option<bool>,
+    /// `rel` added to links, `null` to leave them alone.
+    link_rel: Option<Option<String>>,
+}
+
+/// HTML extension for Andromeda.
+/// This extension escapes text for HTML and sanitizes untrusted HTML with an allowlist.
+#[derive(Default)]
+pub struct HtmlExt;
+
+impl HtmlExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "html",
+            ops: vec![
+                extension_op!("internal_html_escape", Self::internal_html_escape, 1),
+                extension_op!("internal_html_sanitize", Self::internal_html_sanitize, 2),
+            ],
+            storage: None,
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Escape the characters that are special in HTML text and quoted attribute values.
+    fn internal_html_escape(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(0).to_string(agent)?;
+        let escaped = escape(text.as_str(agent));
+        Ok(Value::from_string(agent, escaped))
+    }
+
+    /// Remove every tag, attribute and URL scheme of the HTML that isn't in the allowlist.
+    fn internal_html_sanitize(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let html = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let options = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        match sanitize(&html, &options) {
+            Ok(output) => Ok(Value::from_string(agent, output)),
+            Err(e) => Err(e.throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            // Closes unquoted attribute values in some old browsers.
+            '`' => escaped.push_str("&#96;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn sanitize(html: &str, options: &str) -> Result<String, OpError> {
+    let options: SanitizeOptions = serde_json::from_str(options)
+        .map_err(|e| OpError::new(ErrorClass::InvalidData, e.to_string()))?;
+
+    let mut builder = ammonia::Builder::default();
+    if let Some(tags) = &options.tags {
+        // ammonia always removes these along with their content.
+        if let Some(tag) = tags
+            .iter()
+            .find(|tag| matches!(tag.as_str(), "script" | "style"))
+        {
+            return Err(OpError::new(
+                ErrorClass::InvalidData,
+                format!("The <{tag}> tag can't be allowed"),
+            ));
+        }
+        builder.tags(tags.iter().map(String::as_str).collect());
+    }
+    if let Some(attributes) = &options.attributes {
+        // ammonia refuses attributes for the tags it removes along with their content.
+        if let Some(tag) = attributes
+            .keys()
+            .find(|tag| matches!(tag.as_str(), "script" | "style"))
+        {
+            return Err(OpError::new(
+                ErrorClass::InvalidData,
+                format!("Attributes of the <{tag}> tag can't be allowed"),
+            ));
+        }
+        builder.tag_attributes(
+            attributes
+                .iter()
+                .map(|(tag, attributes)| {
+                    let attributes: HashSet<&str> = attributes.iter().map(String::as_str).collect();
+                    (tag.as_str(), attributes)
+                })
+                .collect(),
+        );
+    }
+    if let Some(attributes) = &options.generic_attributes {
+        builder.generic_attributes(attributes.iter().map(String::as_str).collect());
+    }
+    if let Some(schemes) = &options.url_schemes {
+        builder.url_schemes(schemes.iter().map(String::as_str).collect());
+    }
+    if let Some(strip_comments) = options.strip_comments {
+        builder.strip_comments(strip_comments);
+    }
+    if let Some(link_rel) = &options.link_rel {
+        builder.link_rel(link_rel.as_deref());
+    }
+
+    // ammonia refuses a `rel` attribute in the allowlist while it sets one itself, which it does
+    // by default.
+    let sets_link_rel = options.link_rel.as_ref().map_or(true, Option::is_some);
+    let allows_rel = |attributes: Option<&Vec<String>>| {
+        attributes.is_some_and(|attributes| attributes.iter().any(|attribute| attribute == "rel"))
+    };
+    if sets_link_rel
+        && (allows_rel(options.generic_attributes.as_ref())
+            || allows_rel(
+                options
+                    .attributes
+                    .as_ref()
+                    .and_then(|attributes| attributes.get("a")),
+            ))
+    {
+        return Err(OpError::new(
+            ErrorClass::InvalidData,
+            "The \"rel\" attribute of links can't be allowed unless linkRel is null",
+        ));
+    }
+
+    Ok(builder.clean(html).to_string())
+}
@@ -0,0 +1,97 @@
+use andromeda_core::{
+    extension_op, ErrorClass, ExposeInternals, Extension, HostData, OpError, OpRegistry,
+};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::Value,
+};
+use serde_json::json;
+
+use crate::RuntimeMacroTask;
+
+/// Internals extension for Andromeda.
+/// This extension provides introspection of the runtime for tooling. Its ops throw unless the
+/// runtime was started with `--expose-internals`.
+#[derive(Default)]
+pub struct InternalsExt;
+
+impl InternalsExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "internals",
+            ops: vec![
+                extension_op!("internal_ops", Self::internal_ops, 0),
+                extension_op!(
+                    "internal_blocking_pool_stats",
+                    Self::internal_blocking_pool_stats,
+                    0
+                ),
+            ],
+            storage: None,
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// List the registered ops as JSON `{ name, args, async, extension }` objects.
+    fn internal_ops(agent: &mut Agent, _this: Value, _: ArgumentsList) -> JsResult<Value> {
+        Self::check_exposed(agent)?;
+        let ops = HostData::<RuntimeMacroTask>::from_agent(agent).with_storage(
+            |registry: &OpRegistry| {
+                registry
+                    .ops()
+                    .iter()
+                    .map(|op| {
+                        json!({
+                            "name": op.name,
+                            "args": op.args,
+                            "async": op.is_async,
+                            "extension": op.extension,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            },
+        );
+
+        Ok(Value::from_string(
+            agent,
+            serde_json::Value::Array(ops).to_string(),
+        ))
+    }
+
+    /// Get the activity of the blocking pool as JSON `{ queued, running, completed }`.
+    fn internal_blocking_pool_stats(
+        agent: &mut Agent,
+        _this: Value,
+        _: ArgumentsList,
+    ) -> JsResult<Value> {
+        Self::check_exposed(agent)?;
+        let stats = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .blocking_pool
+            .stats();
+        let stats = json!({
+            "queued": stats.queued,
+            "running": stats.running,
+            "completed": stats.completed,
+        });
+
+        Ok(Value::from_string(agent, stats.to_string()))
+    }
+
+    /// Throw unless the runtime was started with `--expose-internals`.
+    fn check_exposed(agent: &mut Agent) -> JsResult<()> {
+        if HostData::<RuntimeMacroTask>::from_agent(agent)
+            .try_with_storage(|_: &ExposeInternals| ())
+            .is_some()
+        {
+            return Ok(());
+        }
+        Err(OpError::new(
+            ErrorClass::PermissionDenied,
+            "Andromeda.internals requires the --expose-internals flag",
+        )
+        .throw::<RuntimeMacroTask>(agent))
+    }
+}
@@ -1,11 +1,39 @@
+mod cache;
 mod console;
+mod csv;
+mod deno;
+mod diff;
+mod dom;
+mod errors;
+mod formats;
 mod fs;
+mod html;
+mod internals;
+mod passwords;
 mod process;
+mod prompts;
+mod regex;
+mod smtp;
 mod time;
 mod url;
+mod web;
 
-pub use console::*;
+pub use self::console::*;
+pub use cache::*;
+pub use csv::*;
+pub use deno::*;
+pub use diff::*;
+pub use dom::*;
+pub use errors::*;
+pub use formats::*;
 pub use fs::*;
+pub use html::*;
+pub use internals::*;
+pub use passwords::*;
 pub use process::*;
+pub use prompts::*;
+pub use regex::*;
+pub use smtp::*;
 pub use time::*;
 pub use url::*;
+pub use web::*;
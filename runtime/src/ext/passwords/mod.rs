@@ -0,0 +1,177 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use nova_vm::ecmascript::{
+    builtins::{
+        promise_objects::promise_abstract_operations::promise_capability_records::PromiseCapability,
+        ArgumentsList,
+    },
+    execution::{Agent, JsResult},
+    types::{Global, IntoValue, Value},
+};
+use scrypt::Scrypt;
+
+use andromeda_core::{extension_op, ErrorClass, Extension, HostData, MacroTask, OpError};
+
+use crate::RuntimeMacroTask;
+
+/// Password hashing algorithms supported by `Andromeda.passwords`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Argon2id,
+    Bcrypt,
+    Scrypt,
+}
+
+impl Algorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "argon2id" => Some(Self::Argon2id),
+            "bcrypt" => Some(Self::Bcrypt),
+            "scrypt" => Some(Self::Scrypt),
+            _ => None,
+        }
+    }
+
+    /// Guess the algorithm from the prefix of an encoded hash.
+    fn from_hash(hash: &str) -> Option<Self> {
+        if hash.starts_with("$argon2id$") {
+            Some(Self::Argon2id)
+        } else if hash.starts_with("$scrypt$") {
+            Some(Self::Scrypt)
+        } else if ["$2a$", "$2b$", "$2x$", "$2y$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+        {
+            Some(Self::Bcrypt)
+        } else {
+            None
+        }
+    }
+}
+
+/// Result of a password op, sent back to the event loop.
+enum Output {
+    Hash(String),
+    Verified(bool),
+}
+
+#[derive(Default)]
+pub struct PasswordsExt;
+
+impl PasswordsExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "passwords",
+            ops: vec![
+                extension_op!(async "internal_password_hash", Self::internal_password_hash, 2),
+                extension_op!(async "internal_password_verify", Self::internal_password_verify, 2),
+            ],
+            storage: None,
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Hash a password with the given algorithm, resolving to the encoded hash with its salt and parameters.
+    fn internal_password_hash(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let password = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let algorithm_name = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let Some(algorithm) = Algorithm::from_name(&algorithm_name) else {
+            return Err(OpError::new(
+                ErrorClass::NotSupported,
+                format!("Unsupported password hashing algorithm: {algorithm_name}"),
+            )
+            .throw::<RuntimeMacroTask>(agent));
+        };
+
+        Ok(Self::spawn_password_op(agent, move || {
+            hash(&password, algorithm).map(Output::Hash)
+        }))
+    }
+
+    /// Check a password against a hash produced by [PasswordsExt::internal_password_hash].
+    fn internal_password_verify(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let password = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let hash = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        Ok(Self::spawn_password_op(agent, move || {
+            verify(&password, &hash).map(Output::Verified)
+        }))
+    }
+
+    /// Run a password op on the blocking thread pool, as hashing is slow on purpose,
+    /// and settle the returned promise with its result.
+    fn spawn_password_op(
+        agent: &mut Agent,
+        op: impl FnOnce() -> Result<Output, OpError> + Send + 'static,
+    ) -> Value {
+        let promise_capability = PromiseCapability::new(agent);
+        let root_value = Global::new(agent, promise_capability.promise().into_value());
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let macro_task_tx = host_data.macro_task_tx();
+        let job = host_data.blocking_pool.spawn(op);
+
+        host_data.spawn_macro_task(async move {
+            let macro_task = match job.await {
+                Ok(Ok(Output::Hash(hash))) => MacroTask::ResolvePromiseWithString(root_value, hash),
+                Ok(Ok(Output::Verified(verified))) => {
+                    MacroTask::ResolvePromiseWithBoolean(root_value, verified)
+                }
+                Ok(Err(e)) => MacroTask::RejectPromise(root_value, e),
+                Err(e) => {
+                    MacroTask::RejectPromise(root_value, OpError::from(std::io::Error::from(e)))
+                }
+            };
+            macro_task_tx.send(macro_task).unwrap();
+        });
+
+        Value::Promise(promise_capability.promise())
+    }
+}
+
+fn hash(password: &str, algorithm: Algorithm) -> Result<String, OpError> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = match algorithm {
+        Algorithm::Argon2id => Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string()),
+        Algorithm::Scrypt => Scrypt
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string()),
+        Algorithm::Bcrypt => {
+            return bcrypt::hash(password, bcrypt::DEFAULT_COST)
+                .map_err(|e| OpError::new(ErrorClass::Error, e.to_string()))
+        }
+    };
+    hash.map_err(|e| OpError::new(ErrorClass::Error, e.to_string()))
+}
+
+fn verify(password: &str, hash: &str) -> Result<bool, OpError> {
+    let invalid_hash = || OpError::new(ErrorClass::InvalidData, "Invalid password hash");
+    match Algorithm::from_hash(hash).ok_or_else(invalid_hash)? {
+        Algorithm::Bcrypt => bcrypt::verify(password, hash).map_err(|_| invalid_hash()),
+        algorithm => {
+            let parsed_hash = PasswordHash::new(hash).map_err(|_| invalid_hash())?;
+            let result = match algorithm {
+                Algorithm::Scrypt => Scrypt.verify_password(password.as_bytes(), &parsed_hash),
+                _ => Argon2::default().verify_password(password.as_bytes(), &parsed_hash),
+            };
+            match result {
+                Ok(()) => Ok(true),
+                Err(argon2::password_hash::Error::Password) => Ok(false),
+                Err(_) => Err(invalid_hash()),
+            }
+        }
+    }
+}
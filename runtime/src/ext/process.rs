@@ -1,10 +1,57 @@
-use andromeda_core::{Extension, ExtensionOp};
+use andromeda_core::{
+    extension_op, webidl::to_callback_function, Extension, HostData, MacroTask, MainModule,
+    OpError, OpsStorage, Permissions,
+};
 use nova_vm::ecmascript::{
     builtins::{ArgumentsList, Array},
-    execution::{Agent, JsResult},
-    types::Value,
+    execution::{
+        agent::{GcAgent, RealmRoot},
+        Agent, JsResult,
+    },
+    types::{Function, Global, IntoValue, Value},
+};
+use std::{
+    env,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+    },
 };
-use std::env;
+use url::Url;
+
+use crate::RuntimeMacroTask;
+
+/// Signals that shut the process down gracefully once `Andromeda.shutdown.on` registered a hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownSignal {
+    /// SIGINT, or Ctrl+C on Windows.
+    Interrupt,
+    /// SIGTERM.
+    Terminate,
+}
+
+impl ShutdownSignal {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Interrupt => "SIGINT",
+            Self::Terminate => "SIGTERM",
+        }
+    }
+
+    /// Exit code of a process killed by the signal.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Interrupt => 130,
+            Self::Terminate => 143,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ShutdownStorage {
+    /// Function running the shutdown hooks, set once the signals are listened to.
+    listener: Option<Global<Value>>,
+}
 
 /// Process extension for Andromeda.
 /// This extension provides access to internal functions relating to the process.
@@ -16,14 +63,29 @@ impl ProcessExt {
         Extension {
             name: "process",
             ops: vec![
-                ExtensionOp::new("internal_get_cli_args", Self::internal_get_cli_args, 0),
-                ExtensionOp::new("internal_get_env", Self::internal_get_env, 1),
-                ExtensionOp::new("internal_set_env", Self::internal_set_env, 2),
-                ExtensionOp::new("internal_delete_env", Self::internal_delete_env, 1),
-                ExtensionOp::new("internal_get_env_keys", Self::internal_get_env_keys, 0),
+                extension_op!("internal_get_cli_args", Self::internal_get_cli_args, 0),
+                extension_op!("internal_get_env", Self::internal_get_env, 1),
+                extension_op!("internal_set_env", Self::internal_set_env, 2),
+                extension_op!("internal_delete_env", Self::internal_delete_env, 1),
+                extension_op!("internal_get_env_keys", Self::internal_get_env_keys, 0),
+                extension_op!("internal_main_module", Self::internal_main_module, 0),
+                extension_op!(
+                    "internal_external_memory",
+                    Self::internal_external_memory,
+                    0
+                ),
+                extension_op!(
+                    "internal_shutdown_listen",
+                    Self::internal_shutdown_listen,
+                    1
+                ),
             ],
-            storage: None,
+            storage: Some(Box::new(|storage: &mut OpsStorage| {
+                storage.insert(ShutdownStorage::default());
+            })),
             files: vec![],
+            deps: &[],
+            cfg: None,
         }
     }
 
@@ -41,22 +103,22 @@ impl ProcessExt {
     }
 
     fn internal_get_env(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        let key = args.get(0);
-        let key = key.to_string(agent)?;
-        match env::var(key.as_str(agent)) {
+        let key = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| permissions.check_env(&key))?;
+        match env::var(key) {
             Ok(value) => Ok(nova_vm::ecmascript::types::String::from_string(agent, value).into()),
             _ => Ok(Value::Undefined),
         }
     }
 
     fn internal_set_env(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        let key = args.get(0);
-        let key = key.to_string(agent)?;
+        let key = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| permissions.check_env(&key))?;
 
         let value = args.get(1);
         let value = value.to_string(agent)?;
 
-        env::set_var(key.as_str(agent), value.as_str(agent));
+        env::set_var(key, value.as_str(agent));
 
         Ok(Value::Undefined)
     }
@@ -66,15 +128,16 @@ impl ProcessExt {
         _this: Value,
         args: ArgumentsList,
     ) -> JsResult<Value> {
-        let key = args.get(0);
-        let key = key.to_string(agent)?;
+        let key = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| permissions.check_env(&key))?;
 
-        env::remove_var(key.as_str(agent));
+        env::remove_var(key);
 
         Ok(Value::Undefined)
     }
 
     fn internal_get_env_keys(agent: &mut Agent, _this: Value, _: ArgumentsList) -> JsResult<Value> {
+        Permissions::check::<RuntimeMacroTask>(agent, Permissions::check_env_all)?;
         let keys = env::vars()
             .map(|(k, _)| k)
             .map(|s| nova_vm::ecmascript::types::String::from_string(agent, s).into_value())
@@ -82,4 +145,125 @@ impl ProcessExt {
 
         Ok(Array::from_slice(agent, keys.as_slice()).into())
     }
+
+    /// Get the `file:` URL of the main script, or `undefined` if the runtime wasn't given any.
+    fn internal_main_module(agent: &mut Agent, _this: Value, _: ArgumentsList) -> JsResult<Value> {
+        let main_module = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .try_with_storage(|main_module: &MainModule| Url::from_file_path(&main_module.0).ok())
+            .flatten();
+
+        match main_module {
+            Some(url) => Ok(Value::from_string(agent, url.to_string())),
+            None => Ok(Value::Undefined),
+        }
+    }
+
+    /// Get the bytes of memory held by the ops outside of the JavaScript heap.
+    fn internal_external_memory(
+        agent: &mut Agent,
+        _this: Value,
+        _: ArgumentsList,
+    ) -> JsResult<Value> {
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let external = host_data.external_memory.total();
+        Ok(Value::from_f64(agent, external as f64))
+    }
+
+    /// Call the listener with the signal name and its exit code on the first SIGINT or SIGTERM.
+    /// The signals don't keep the event loop alive, and a second one exits right away in case
+    /// the shutdown hangs.
+    fn internal_shutdown_listen(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let listener = to_callback_function(agent, args.get(0), "Argument 1 of shutdown listen")?;
+        let listener = Global::new(agent, listener.into_value());
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let listening = host_data.with_storage_mut(|shutdown: &mut ShutdownStorage| {
+            shutdown.listener.replace(listener).is_some()
+        });
+        if !listening {
+            listen_for_shutdown(host_data.macro_task_tx())
+                .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+        }
+        Ok(Value::Undefined)
+    }
+}
+
+/// Send the macro task running the shutdown listener on the first signal, which isn't counted
+/// as a pending macro task so it doesn't keep the event loop alive.
+#[cfg(unix)]
+fn listen_for_shutdown(macro_task_tx: Sender<MacroTask<RuntimeMacroTask>>) -> std::io::Result<()> {
+    use std::sync::Arc;
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let received = Arc::new(AtomicBool::new(false));
+    for (kind, shutdown_signal) in [
+        (SignalKind::interrupt(), ShutdownSignal::Interrupt),
+        (SignalKind::terminate(), ShutdownSignal::Terminate),
+    ] {
+        let mut stream = signal(kind)?;
+        let macro_task_tx = macro_task_tx.clone();
+        let received = received.clone();
+        tokio::spawn(async move {
+            while stream.recv().await.is_some() {
+                shutdown_on(shutdown_signal, &received, &macro_task_tx);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn listen_for_shutdown(macro_task_tx: Sender<MacroTask<RuntimeMacroTask>>) -> std::io::Result<()> {
+    let mut ctrl_c = tokio::signal::windows::ctrl_c()?;
+    let received = AtomicBool::new(false);
+    tokio::spawn(async move {
+        while ctrl_c.recv().await.is_some() {
+            shutdown_on(ShutdownSignal::Interrupt, &received, &macro_task_tx);
+        }
+    });
+    Ok(())
+}
+
+fn shutdown_on(
+    shutdown_signal: ShutdownSignal,
+    received: &AtomicBool,
+    macro_task_tx: &Sender<MacroTask<RuntimeMacroTask>>,
+) {
+    if received.swap(true, Ordering::Relaxed) {
+        std::process::exit(shutdown_signal.exit_code());
+    }
+    let macro_task = RuntimeMacroTask::RunShutdownListener(shutdown_signal);
+    if macro_task_tx.send(MacroTask::User(macro_task)).is_err() {
+        // The event loop is gone, nothing is left to shut down.
+        std::process::exit(shutdown_signal.exit_code());
+    }
+}
+
+/// Run the shutdown listener, which exits the process once the hooks are done.
+/// An exception thrown by the listener is returned to the event loop instead.
+pub fn run_shutdown_listener(
+    agent: &mut GcAgent,
+    host_data: &HostData<RuntimeMacroTask>,
+    realm_root: &RealmRoot,
+    shutdown_signal: ShutdownSignal,
+) -> JsResult<()> {
+    agent.run_in_realm(realm_root, |agent| {
+        let Some(listener) = host_data.with_storage(|shutdown: &ShutdownStorage| {
+            shutdown
+                .listener
+                .as_ref()
+                .map(|listener| listener.get(agent))
+        }) else {
+            return Ok(());
+        };
+        // The op only stores functions.
+        let listener: Function = listener.try_into().unwrap();
+        let name = Value::from_string(agent, shutdown_signal.name().to_string());
+        let exit_code = Value::from_f64(agent, shutdown_signal.exit_code() as f64);
+        listener.call(agent, Value::Undefined, &[name, exit_code])?;
+        Ok(())
+    })
 }
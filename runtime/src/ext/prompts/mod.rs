@@ -0,0 +1,235 @@
+use andromeda_core::{
+    extension_op, ErrorClass, Extension, HostData, OpError, OpsStorage, ResourceTable, Rid,
+};
+use cliclack::ProgressBar;
+use nova_vm::{
+    ecmascript::{
+        builtins::ArgumentsList,
+        execution::{Agent, JsResult},
+        types::Value,
+    },
+    SmallInteger,
+};
+use serde::Deserialize;
+
+use crate::RuntimeMacroTask;
+
+struct PromptsExtResources {
+    spinners: ResourceTable<ProgressBar>,
+}
+
+/// Choice of a select or multiselect prompt, sent from JavaScript as JSON.
+#[derive(Deserialize)]
+struct PromptItem {
+    label: String,
+    #[serde(default)]
+    hint: String,
+}
+
+/// Prompts extension for Andromeda.
+/// This extension provides interactive terminal prompts backed by cliclack. Choices are passed by
+/// index, so JavaScript can map them back to values of any type.
+#[derive(Default)]
+pub struct PromptsExt;
+
+impl PromptsExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "prompts",
+            ops: vec![
+                extension_op!("internal_prompt_select", Self::internal_prompt_select, 3),
+                extension_op!(
+                    "internal_prompt_multiselect",
+                    Self::internal_prompt_multiselect,
+                    4
+                ),
+                extension_op!(
+                    "internal_prompt_password",
+                    Self::internal_prompt_password,
+                    1
+                ),
+                extension_op!("internal_prompt_confirm", Self::internal_prompt_confirm, 2),
+                extension_op!("internal_spinner_start", Self::internal_spinner_start, 1),
+                extension_op!(
+                    "internal_spinner_message",
+                    Self::internal_spinner_message,
+                    2
+                ),
+                extension_op!("internal_spinner_stop", Self::internal_spinner_stop, 3),
+            ],
+            storage: Some(Box::new(|storage: &mut OpsStorage| {
+                storage.insert(PromptsExtResources {
+                    spinners: ResourceTable::<ProgressBar>::new(),
+                });
+            })),
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Ask to pick one of the items, given as JSON, and return the index of the chosen one.
+    fn internal_prompt_select(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let message = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let items = Self::items(agent, args.get(1))?;
+        let initial = args.get(2).to_uint32(agent)? as usize;
+
+        let mut prompt = cliclack::select(message);
+        for (index, item) in items.iter().enumerate() {
+            prompt = prompt.item(index, &item.label, &item.hint);
+        }
+        let index = prompt
+            .initial_value(initial.min(items.len().saturating_sub(1)))
+            .interact()
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+
+        Ok(Value::Integer(SmallInteger::from(index as u32)))
+    }
+
+    /// Ask to pick any of the items, given as JSON, and return the indexes of the chosen ones as JSON.
+    fn internal_prompt_multiselect(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let message = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let items = Self::items(agent, args.get(1))?;
+        let required = args.get(2) == Value::Boolean(true);
+        let initial = args.get(3).to_string(agent)?.as_str(agent).to_string();
+        let initial: Vec<usize> = serde_json::from_str(&initial)
+            .map_err(|e| invalid_data(e).throw::<RuntimeMacroTask>(agent))?;
+
+        let mut prompt = cliclack::multiselect(message);
+        for (index, item) in items.iter().enumerate() {
+            prompt = prompt.item(index, &item.label, &item.hint);
+        }
+        let indexes = prompt
+            .initial_values(initial)
+            .required(required)
+            .interact()
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+
+        Ok(Value::from_string(
+            agent,
+            serde_json::to_string(&indexes).unwrap(),
+        ))
+    }
+
+    /// Ask for a password without echoing it.
+    fn internal_prompt_password(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let message = args.get(0).to_string(agent)?.as_str(agent).to_string();
+
+        let password = cliclack::password(message)
+            .mask('▪')
+            .interact()
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+
+        Ok(Value::from_string(agent, password))
+    }
+
+    /// Ask a yes or no question.
+    fn internal_prompt_confirm(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let message = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let initial = args.get(1) == Value::Boolean(true);
+
+        let confirmed = cliclack::confirm(message)
+            .initial_value(initial)
+            .interact()
+            .map_err(|e| OpError::from(e).throw::<RuntimeMacroTask>(agent))?;
+
+        Ok(Value::Boolean(confirmed))
+    }
+
+    /// Start a spinner with a message and return its Rid.
+    fn internal_spinner_start(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let message = args.get(0).to_string(agent)?.as_str(agent).to_string();
+
+        let mut spinner = cliclack::spinner();
+        spinner.start(message);
+
+        let rid = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .with_storage(|resources: &PromptsExtResources| resources.spinners.push(spinner));
+
+        Ok(Value::Integer(SmallInteger::from(rid.index())))
+    }
+
+    /// Change the message of a running spinner.
+    fn internal_spinner_message(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let message = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        let found = HostData::<RuntimeMacroTask>::from_agent(agent).with_storage(
+            |resources: &PromptsExtResources| match resources.spinners.get_mut(rid) {
+                Some(mut spinner) => {
+                    spinner.set_message(message);
+                    true
+                }
+                None => false,
+            },
+        );
+
+        if !found {
+            return Err(OpError::bad_resource().throw::<RuntimeMacroTask>(agent));
+        }
+        Ok(Value::Undefined)
+    }
+
+    /// Stop a spinner with a final message, shown as an error if the third argument is `true`.
+    fn internal_spinner_stop(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+        let message = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let error = args.get(2) == Value::Boolean(true);
+
+        let spinner = HostData::<RuntimeMacroTask>::from_agent(agent)
+            .with_storage(|resources: &PromptsExtResources| resources.spinners.remove(rid));
+
+        match spinner {
+            Some(mut spinner) if error => spinner.error(message),
+            Some(mut spinner) => spinner.stop(message),
+            None => return Err(OpError::bad_resource().throw::<RuntimeMacroTask>(agent)),
+        }
+        Ok(Value::Undefined)
+    }
+
+    /// Parse the JSON items of a select or multiselect prompt.
+    fn items(agent: &mut Agent, items: Value) -> JsResult<Vec<PromptItem>> {
+        let items = items.to_string(agent)?.as_str(agent).to_string();
+        let items: Vec<PromptItem> = serde_json::from_str(&items)
+            .map_err(|e| invalid_data(e).throw::<RuntimeMacroTask>(agent))?;
+        if items.is_empty() {
+            return Err(
+                OpError::new(ErrorClass::InvalidData, "No items to choose from")
+                    .throw::<RuntimeMacroTask>(agent),
+            );
+        }
+        Ok(items)
+    }
+}
+
+fn invalid_data(error: impl std::fmt::Display) -> OpError {
+    OpError::new(ErrorClass::InvalidData, error.to_string())
+}
@@ -0,0 +1,242 @@
+use andromeda_core::{
+    extension_op, ErrorClass, Extension, HostData, OpError, OpsStorage, ResourceTable, Rid,
+};
+use nova_vm::{
+    ecmascript::{
+        builtins::ArgumentsList,
+        execution::{Agent, JsResult},
+        types::Value,
+    },
+    SmallInteger,
+};
+use regex::{Captures, Regex, RegexBuilder};
+use serde_json::json;
+
+use crate::RuntimeMacroTask;
+
+struct RegexExtResources {
+    patterns: ResourceTable<Regex>,
+}
+
+/// Rough bytes used per byte of pattern by the compiled automata and their matching caches,
+/// which the `regex` crate doesn't report.
+const REGEX_BYTES_PER_PATTERN_BYTE: usize = 256;
+
+/// Regex extension for Andromeda.
+/// This extension provides a linear-time matcher backed by the `regex` crate for large inputs.
+/// Matches are returned as JSON, with indexes in UTF-16 code units like JavaScript strings.
+#[derive(Default)]
+pub struct RegexExt;
+
+impl RegexExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "regex",
+            ops: vec![
+                extension_op!("internal_regex_compile", Self::internal_regex_compile, 2),
+                extension_op!("internal_regex_close", Self::internal_regex_close, 1),
+                extension_op!("internal_regex_find", Self::internal_regex_find, 2),
+                extension_op!("internal_regex_find_all", Self::internal_regex_find_all, 2),
+                extension_op!("internal_regex_replace", Self::internal_regex_replace, 4),
+                extension_op!("internal_regex_split", Self::internal_regex_split, 3),
+            ],
+            storage: Some(Box::new(|storage: &mut OpsStorage| {
+                storage.insert(RegexExtResources {
+                    patterns: ResourceTable::<Regex>::new(),
+                });
+            })),
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Compile a pattern with the given flags (`i`, `m`, `s`, `x`) and return its Rid.
+    fn internal_regex_compile(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let pattern = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let flags = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        let regex = compile(&pattern, &flags).map_err(|e| e.throw::<RuntimeMacroTask>(agent))?;
+
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        host_data.external_memory.allocate(regex_size(&regex));
+        let rid =
+            host_data.with_storage(|resources: &RegexExtResources| resources.patterns.push(regex));
+
+        Ok(Value::Integer(SmallInteger::from(rid.index())))
+    }
+
+    /// Free a compiled pattern given its Rid.
+    fn internal_regex_close(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let rid = Rid::from_index(args.get(0).to_uint32(agent)?);
+
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let regex =
+            host_data.with_storage(|resources: &RegexExtResources| resources.patterns.remove(rid));
+
+        match regex {
+            Some(regex) => {
+                host_data.external_memory.free(regex_size(&regex));
+                Ok(Value::Undefined)
+            }
+            None => Err(OpError::bad_resource().throw::<RuntimeMacroTask>(agent)),
+        }
+    }
+
+    /// Find the first match, returned as JSON or `null`.
+    fn internal_regex_find(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let json = Self::with_regex(agent, args.get(0), |regex| match regex.captures(&text) {
+            Some(captures) => {
+                let start = captures.get(0).unwrap().start();
+                let index = utf16_len(&text[..start]);
+                match_to_json(regex, &captures, index).to_string()
+            }
+            None => "null".to_string(),
+        })?;
+        Ok(Value::from_string(agent, json))
+    }
+
+    /// Find every non-overlapping match, returned as a JSON array.
+    fn internal_regex_find_all(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let json = Self::with_regex(agent, args.get(0), |regex| {
+            // Convert byte offsets incrementally so long inputs are only walked once.
+            let mut byte_offset = 0;
+            let mut utf16_offset = 0;
+            let matches = regex
+                .captures_iter(&text)
+                .map(|captures| {
+                    let start = captures.get(0).unwrap().start();
+                    utf16_offset += utf16_len(&text[byte_offset..start]);
+                    byte_offset = start;
+                    match_to_json(regex, &captures, utf16_offset)
+                })
+                .collect::<Vec<_>>();
+            serde_json::Value::Array(matches).to_string()
+        })?;
+        Ok(Value::from_string(agent, json))
+    }
+
+    /// Replace the first match, or every match if the fourth argument is `true`.
+    /// The replacement can refer to groups as `$1`, `$name` or `${name}`.
+    fn internal_regex_replace(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let replacement = args.get(2).to_string(agent)?.as_str(agent).to_string();
+        let all = args.get(3) == Value::Boolean(true);
+        let output = Self::with_regex(agent, args.get(0), |regex| {
+            let limit = if all { 0 } else { 1 };
+            regex
+                .replacen(&text, limit, replacement.as_str())
+                .into_owned()
+        })?;
+        Ok(Value::from_string(agent, output))
+    }
+
+    /// Split the text around the matches, into at most `limit` parts if given, as a JSON array.
+    fn internal_regex_split(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let text = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let limit = match args.get(2) {
+            Value::Null | Value::Undefined => None,
+            value => Some(value.to_uint32(agent)? as usize),
+        };
+        let json = Self::with_regex(agent, args.get(0), |regex| {
+            let parts: Vec<&str> = match limit {
+                Some(limit) => regex.splitn(&text, limit).collect(),
+                None => regex.split(&text).collect(),
+            };
+            serde_json::to_string(&parts).unwrap()
+        })?;
+        Ok(Value::from_string(agent, json))
+    }
+
+    /// Run a closure with the compiled pattern behind the Rid value, throwing if there is none.
+    fn with_regex<R>(agent: &mut Agent, rid: Value, run: impl FnOnce(&Regex) -> R) -> JsResult<R> {
+        let rid = Rid::from_index(rid.to_uint32(agent)?);
+
+        let result = HostData::<RuntimeMacroTask>::from_agent(agent).with_storage(
+            |resources: &RegexExtResources| resources.patterns.get(rid).map(|regex| run(&regex)),
+        );
+
+        result.ok_or_else(|| OpError::bad_resource().throw::<RuntimeMacroTask>(agent))
+    }
+}
+
+/// Estimate the memory held by a compiled pattern, to report it as external memory.
+fn regex_size(regex: &Regex) -> usize {
+    std::mem::size_of::<Regex>() + regex.as_str().len() * REGEX_BYTES_PER_PATTERN_BYTE
+}
+
+fn compile(pattern: &str, flags: &str) -> Result<Regex, OpError> {
+    let mut builder = RegexBuilder::new(pattern);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            flag => {
+                return Err(OpError::new(
+                    ErrorClass::InvalidData,
+                    format!("Invalid regex flag: {flag}"),
+                ))
+            }
+        };
+    }
+    builder
+        .build()
+        .map_err(|e| OpError::new(ErrorClass::InvalidData, e.to_string()))
+}
+
+/// Describe a match as `{ index, text, captures, groups }`, like the arrays of `RegExp.prototype.exec`.
+fn match_to_json(regex: &Regex, captures: &Captures, index: usize) -> serde_json::Value {
+    let groups = regex
+        .capture_names()
+        .flatten()
+        .map(|name| {
+            (
+                name.to_string(),
+                json!(captures.name(name).map(|m| m.as_str())),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    json!({
+        "index": index,
+        "text": &captures[0],
+        "captures": captures
+            .iter()
+            .skip(1)
+            .map(|capture| capture.map(|m| m.as_str()))
+            .collect::<Vec<_>>(),
+        "groups": groups,
+    })
+}
+
+/// Length of a string in UTF-16 code units, the unit of JavaScript string indexes.
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use andromeda_core::{
+    extension_op, ErrorClass, Extension, HostData, MacroTask, OpError, Permissions,
+};
+use lettre::{
+    message::{header::ContentType, Attachment, Mailbox, MultiPart, SinglePart},
+    transport::smtp::{
+        authentication::{Credentials, Mechanism},
+        SMTP_PORT, SUBMISSIONS_PORT, SUBMISSION_PORT,
+    },
+    Message, SmtpTransport, Transport,
+};
+use nova_vm::ecmascript::{
+    builtins::{
+        promise_objects::promise_abstract_operations::promise_capability_records::PromiseCapability,
+        ArgumentsList,
+    },
+    execution::{Agent, JsResult},
+    types::{Global, IntoValue, Value},
+};
+use serde::Deserialize;
+
+use crate::RuntimeMacroTask;
+
+/// Connection options of the SMTP server, sent from JavaScript as JSON.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SmtpOptions {
+    hostname: String,
+    port: Option<u16>,
+    /// `"implicit"`, `"starttls"` or `"none"`.
+    tls: String,
+    username: Option<String>,
+    password: Option<String>,
+    /// `"plain"` or `"login"`, both are tried if missing.
+    mechanism: Option<String>,
+    /// Timeout of the connection and of each command, in milliseconds.
+    timeout: Option<u64>,
+}
+
+impl SmtpOptions {
+    /// Port the transport connects to, defaulting to the usual one of the TLS mode.
+    fn port(&self) -> u16 {
+        self.port.unwrap_or(match self.tls.as_str() {
+            "implicit" => SUBMISSIONS_PORT,
+            "starttls" => SUBMISSION_PORT,
+            _ => SMTP_PORT,
+        })
+    }
+}
+
+/// A message to send, sent from JavaScript as JSON.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MailMessage {
+    from: String,
+    #[serde(default)]
+    to: Vec<String>,
+    #[serde(default)]
+    cc: Vec<String>,
+    #[serde(default)]
+    bcc: Vec<String>,
+    reply_to: Option<String>,
+    subject: String,
+    text: Option<String>,
+    html: Option<String>,
+    #[serde(default)]
+    attachments: Vec<MailAttachment>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MailAttachment {
+    filename: String,
+    content_type: String,
+    content: AttachmentContent,
+}
+
+/// Attachments are either text or bytes, the latter sent as an array of numbers.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AttachmentContent {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+/// Body of a message before attachments are added.
+enum Body {
+    Single(SinglePart),
+    Multi(MultiPart),
+}
+
+/// SMTP extension for Andromeda.
+/// This extension builds MIME messages and sends them through an SMTP server with implicit TLS,
+/// STARTTLS or in plain text, authenticating with AUTH PLAIN or LOGIN.
+#[derive(Default)]
+pub struct SmtpExt;
+
+impl SmtpExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "smtp",
+            ops: vec![extension_op!(async "internal_smtp_send", Self::internal_smtp_send, 2)],
+            storage: None,
+            files: vec![],
+            deps: &["errors"],
+            cfg: None,
+        }
+    }
+
+    /// Send a message, resolving to the response of the server.
+    fn internal_smtp_send(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let options = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let message = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        let options: SmtpOptions = serde_json::from_str(&options)
+            .map_err(|e| invalid_data(e).throw::<RuntimeMacroTask>(agent))?;
+        Permissions::check::<RuntimeMacroTask>(agent, |permissions| {
+            permissions.check_net(&options.hostname, options.port())
+        })?;
+
+        let promise_capability = PromiseCapability::new(agent);
+        let root_value = Global::new(agent, promise_capability.promise().into_value());
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+        let macro_task_tx = host_data.macro_task_tx();
+        // lettre's transport is blocking, so the whole exchange runs on the blocking thread pool.
+        let job = host_data
+            .blocking_pool
+            .spawn(move || send(options, &message));
+
+        host_data.spawn_macro_task(async move {
+            let macro_task = match job.await {
+                Ok(Ok(response)) => MacroTask::ResolvePromiseWithString(root_value, response),
+                Ok(Err(e)) => MacroTask::RejectPromise(root_value, e),
+                Err(e) => {
+                    MacroTask::RejectPromise(root_value, OpError::from(std::io::Error::from(e)))
+                }
+            };
+            macro_task_tx.send(macro_task).unwrap();
+        });
+
+        Ok(Value::Promise(promise_capability.promise()))
+    }
+}
+
+fn send(options: SmtpOptions, message: &str) -> Result<String, OpError> {
+    let message: MailMessage = serde_json::from_str(message).map_err(invalid_data)?;
+    let message = build_message(message)?;
+
+    let mut builder = match options.tls.as_str() {
+        "implicit" => SmtpTransport::relay(&options.hostname),
+        "starttls" => SmtpTransport::starttls_relay(&options.hostname),
+        "none" => Ok(SmtpTransport::builder_dangerous(&options.hostname)),
+        tls => {
+            return Err(OpError::new(
+                ErrorClass::NotSupported,
+                format!("Unsupported SMTP TLS mode: {tls}"),
+            ))
+        }
+    }
+    .map_err(smtp_error)?;
+    builder = builder.port(options.port());
+    if let Some(timeout) = options.timeout {
+        builder = builder.timeout(Some(Duration::from_millis(timeout)));
+    }
+    if let Some(username) = options.username {
+        builder = builder.credentials(Credentials::new(
+            username,
+            options.password.unwrap_or_default(),
+        ));
+        let mechanisms = match options.mechanism.as_deref() {
+            None => vec![Mechanism::Plain, Mechanism::Login],
+            Some("plain") => vec![Mechanism::Plain],
+            Some("login") => vec![Mechanism::Login],
+            Some(mechanism) => {
+                return Err(OpError::new(
+                    ErrorClass::NotSupported,
+                    format!("Unsupported SMTP authentication mechanism: {mechanism}"),
+                ))
+            }
+        };
+        builder = builder.authentication(mechanisms);
+    }
+
+    let response = builder.build().send(&message).map_err(smtp_error)?;
+    Ok(response.message().collect::<Vec<_>>().join("\n"))
+}
+
+/// Build the MIME message: text and HTML bodies are alternatives, attachments make it mixed.
+fn build_message(message: MailMessage) -> Result<Message, OpError> {
+    let mut builder = Message::builder()
+        .from(mailbox(&message.from)?)
+        .subject(message.subject);
+    for to in &message.to {
+        builder = builder.to(mailbox(to)?);
+    }
+    for cc in &message.cc {
+        builder = builder.cc(mailbox(cc)?);
+    }
+    for bcc in &message.bcc {
+        builder = builder.bcc(mailbox(bcc)?);
+    }
+    if let Some(reply_to) = &message.reply_to {
+        builder = builder.reply_to(mailbox(reply_to)?);
+    }
+
+    let body = match (message.text, message.html) {
+        (Some(text), Some(html)) => Body::Multi(MultiPart::alternative_plain_html(text, html)),
+        (Some(text), None) => Body::Single(SinglePart::plain(text)),
+        (None, Some(html)) => Body::Single(SinglePart::html(html)),
+        (None, None) => Body::Single(SinglePart::plain(String::new())),
+    };
+
+    let message = if message.attachments.is_empty() {
+        match body {
+            Body::Single(part) => builder.singlepart(part),
+            Body::Multi(parts) => builder.multipart(parts),
+        }
+    } else {
+        let mut parts = match body {
+            Body::Single(part) => MultiPart::mixed().singlepart(part),
+            Body::Multi(alternative) => MultiPart::mixed().multipart(alternative),
+        };
+        for attachment in message.attachments {
+            let content_type = ContentType::parse(&attachment.content_type).map_err(|_| {
+                OpError::new(
+                    ErrorClass::InvalidData,
+                    format!("Invalid content type: {}", attachment.content_type),
+                )
+            })?;
+            let content = match attachment.content {
+                AttachmentContent::Text(text) => text.into_bytes(),
+                AttachmentContent::Bytes(bytes) => bytes,
+            };
+            parts =
+                parts.singlepart(Attachment::new(attachment.filename).body(content, content_type));
+        }
+        builder.multipart(parts)
+    };
+    message.map_err(invalid_data)
+}
+
+fn mailbox(address: &str) -> Result<Mailbox, OpError> {
+    address.parse().map_err(|_| {
+        OpError::new(
+            ErrorClass::InvalidData,
+            format!("Invalid email address: {address}"),
+        )
+    })
+}
+
+fn smtp_error(error: lettre::transport::smtp::Error) -> OpError {
+    let class = if error.is_timeout() {
+        ErrorClass::TimedOut
+    } else {
+        ErrorClass::Error
+    };
+    OpError::new(class, format!("SMTP error: {error}"))
+}
+
+fn invalid_data(error: impl std::fmt::Display) -> OpError {
+    OpError::new(ErrorClass::InvalidData, error.to_string())
+}
@@ -0,0 +1,126 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use andromeda_core::{HostData, MacroTask, TaskId};
+use nova_vm::ecmascript::{
+    execution::{
+        agent::{GcAgent, RealmRoot},
+        JsResult,
+    },
+    types::{Function, Global, Value},
+};
+
+use crate::RuntimeMacroTask;
+
+/// Frame rate used when the runtime doesn't configure one.
+pub const DEFAULT_FRAME_RATE: u32 = 60;
+
+pub struct AnimationFramesStorage {
+    callbacks: BTreeMap<AnimationFrameId, Global<Value>>,
+    count: u32,
+    /// Time between two frames.
+    frame_interval: Duration,
+    /// Task waiting for the next frame, if any is scheduled.
+    task_id: Option<TaskId>,
+    /// Origin of the timestamps passed to the callbacks.
+    origin: Instant,
+}
+
+impl AnimationFramesStorage {
+    pub fn new(frame_rate: u32) -> Self {
+        Self {
+            callbacks: BTreeMap::new(),
+            count: 0,
+            frame_interval: Duration::from_secs(1) / frame_rate.max(1),
+            task_id: None,
+            origin: Instant::now(),
+        }
+    }
+}
+
+/// An Id representing an animation frame callback.
+#[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
+pub struct AnimationFrameId(u32);
+
+impl AnimationFrameId {
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+
+    pub fn from_index(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// Register a callback for the next frame, scheduling the frame if needed.
+    pub fn request(host_data: &HostData<RuntimeMacroTask>, callback: Global<Value>) -> Self {
+        host_data.with_storage_mut(|frames_storage: &mut AnimationFramesStorage| {
+            // Ids start at 1 so they are always truthy, like in browsers.
+            frames_storage.count += 1;
+            let frame_id = Self(frames_storage.count);
+            frames_storage.callbacks.insert(frame_id, callback);
+
+            if frames_storage.task_id.is_none() {
+                let frame_interval = frames_storage.frame_interval;
+                let macro_task_tx = host_data.macro_task_tx();
+                frames_storage.task_id = Some(host_data.spawn_macro_task(async move {
+                    tokio::time::sleep(frame_interval).await;
+                    macro_task_tx
+                        .send(MacroTask::User(RuntimeMacroTask::RunAnimationFrames))
+                        .unwrap();
+                }));
+            }
+
+            frame_id
+        })
+    }
+
+    /// Remove the callback so it doesn't run in the next frame.
+    /// The pending frame is aborted when no callbacks are left, so it doesn't keep the event loop alive.
+    pub fn cancel(self, host_data: &HostData<RuntimeMacroTask>) {
+        host_data.with_storage_mut(|frames_storage: &mut AnimationFramesStorage| {
+            frames_storage.callbacks.remove(&self);
+            if frames_storage.callbacks.is_empty() {
+                if let Some(task_id) = frames_storage.task_id.take() {
+                    host_data.abort_macro_task(task_id);
+                    host_data.clear_macro_task(task_id);
+                }
+            }
+        })
+    }
+}
+
+/// Run every callback registered before this frame with the frame timestamp in milliseconds.
+/// Callbacks registered while running them are scheduled for the following frame.
+/// The first exception thrown is returned and the callbacks after it are dropped.
+pub fn run_animation_frames(
+    agent: &mut GcAgent,
+    host_data: &HostData<RuntimeMacroTask>,
+    realm_root: &RealmRoot,
+) -> JsResult<()> {
+    let (callbacks, timestamp) =
+        host_data.with_storage_mut(|frames_storage: &mut AnimationFramesStorage| {
+            if let Some(task_id) = frames_storage.task_id.take() {
+                host_data.clear_macro_task(task_id);
+            }
+            let timestamp = frames_storage.origin.elapsed().as_secs_f64() * 1000.0;
+            (std::mem::take(&mut frames_storage.callbacks), timestamp)
+        });
+
+    agent.run_in_realm(realm_root, |agent| {
+        let mut result = Ok(());
+        for (_, global_callback) in callbacks {
+            let callback = global_callback.take(agent);
+            if result.is_err() {
+                continue;
+            }
+            let callback_function: Function = callback.try_into().unwrap();
+            let timestamp = Value::from_f64(agent, timestamp);
+            result = callback_function
+                .call(agent, Value::Undefined, &[timestamp])
+                .map(|_| ());
+        }
+        result
+    })
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use andromeda_core::HostData;
+use nova_vm::ecmascript::{
+    execution::{
+        agent::{GcAgent, RealmRoot},
+        Agent, JsResult,
+    },
+    types::{Function, Global, Value},
+};
+
+use crate::RuntimeMacroTask;
+
+#[derive(Default)]
+pub struct ImmediatesStorage {
+    callbacks: BTreeMap<ImmediateId, Global<Value>>,
+    count: u32,
+}
+
+/// An Id representing an immediate.
+#[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
+pub struct ImmediateId(u32);
+
+impl ImmediateId {
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+
+    pub fn from_index(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// Register the callback and queue it for the next immediates phase of the event loop.
+    pub fn create(host_data: &HostData<RuntimeMacroTask>, callback: Global<Value>) -> Self {
+        let immediate_id = host_data.with_storage_mut(|immediates: &mut ImmediatesStorage| {
+            // Ids start at 1 so they are always truthy, like the ones of the timers.
+            immediates.count += 1;
+            let immediate_id = Self(immediates.count);
+            immediates.callbacks.insert(immediate_id, callback);
+            immediate_id
+        });
+        host_data.queue_immediate(RuntimeMacroTask::RunImmediate(immediate_id));
+        immediate_id
+    }
+
+    /// Remove the callback so it doesn't run. The queued task finds nothing to run and is skipped.
+    pub fn clear(self, agent: &mut Agent) {
+        let callback = HostData::<RuntimeMacroTask>::from_agent(agent).with_storage_mut(
+            |immediates: &mut ImmediatesStorage| immediates.callbacks.remove(&self),
+        );
+        if let Some(callback) = callback {
+            callback.take(agent);
+        }
+    }
+
+    /// Run the callback unless it was cleared, returning the exception it throws.
+    pub fn run(
+        self,
+        agent: &mut GcAgent,
+        host_data: &HostData<RuntimeMacroTask>,
+        realm_root: &RealmRoot,
+    ) -> JsResult<()> {
+        let Some(global_callback) =
+            host_data.with_storage_mut(|immediates: &mut ImmediatesStorage| {
+                immediates.callbacks.remove(&self)
+            })
+        else {
+            return Ok(());
+        };
+
+        agent.run_in_realm(realm_root, |agent| {
+            let callback = global_callback.take(agent);
+            let callback_function: Function = callback.try_into().unwrap();
+            callback_function.call(agent, Value::Undefined, &[])?;
+            Ok(())
+        })
+    }
+}
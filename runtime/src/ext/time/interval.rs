@@ -9,7 +9,10 @@ use std::{
 
 use andromeda_core::{HostData, TaskId};
 use nova_vm::ecmascript::{
-    execution::agent::{GcAgent, RealmRoot},
+    execution::{
+        agent::{GcAgent, RealmRoot},
+        JsResult,
+    },
     types::{Function, Global, Value},
 };
 
@@ -34,32 +37,36 @@ impl IntervalId {
         Self(index)
     }
 
-    /// Remove and abort the interval.
+    /// Remove and abort the interval. Unknown or already cleared intervals are ignored.
     pub fn clear_and_abort(self, host_data: &HostData<RuntimeMacroTask>) {
-        let mut host_data_storage = host_data.storage.borrow_mut();
-        let intervals_storage: &mut IntervalsStorage = host_data_storage.get_mut().unwrap();
-        let interval = intervals_storage.intervals.remove(&self).unwrap();
+        let interval = host_data.with_storage_mut(|intervals_storage: &mut IntervalsStorage| {
+            intervals_storage.intervals.remove(&self)
+        });
+        let Some(interval) = interval else {
+            return;
+        };
         host_data.abort_macro_task(interval.task_id);
         host_data.clear_macro_task(interval.task_id);
     }
 
-    /// Execute the Interval callback.
+    /// Execute the Interval callback, returning the exception it throws.
     pub fn run(
         self,
         agent: &mut GcAgent,
         host_data: &HostData<RuntimeMacroTask>,
         realm_root: &RealmRoot,
-    ) {
-        Interval::with(host_data, &self, |interval| {
-            let global_callback = &interval.callback;
-            agent.run_in_realm(realm_root, |agent| {
-                let callback = global_callback.get(agent);
-                let callback_function: Function = callback.try_into().unwrap();
-                callback_function
-                    .call(agent, Value::Undefined, &[])
-                    .unwrap();
-            });
-        });
+    ) -> JsResult<()> {
+        agent.run_in_realm(realm_root, |agent| {
+            // The storage must not stay borrowed while the callback runs, it may set other timers.
+            let Some(callback) =
+                Interval::with(host_data, &self, |interval| interval.callback.get(agent))
+            else {
+                return Ok(());
+            };
+            let callback_function: Function = callback.try_into().unwrap();
+            callback_function.call(agent, Value::Undefined, &[])?;
+            Ok(())
+        })
     }
 }
 
@@ -78,31 +85,30 @@ impl Interval {
         callback: Global<Value>,
         task_id: impl FnOnce(IntervalId) -> TaskId,
     ) -> IntervalId {
-        let mut host_data_storage = host_data.storage.borrow_mut();
-        let intervals_storage: &mut IntervalsStorage = host_data_storage.get_mut().unwrap();
-        let id = intervals_storage.count.fetch_add(1, Ordering::Relaxed);
-        let interval_id = IntervalId(id);
-        let task_id = task_id(interval_id);
-        let interval = Self {
-            period,
-            callback,
-            task_id,
-        };
+        host_data.with_storage_mut(|intervals_storage: &mut IntervalsStorage| {
+            let id = intervals_storage.count.fetch_add(1, Ordering::Relaxed);
+            let interval_id = IntervalId(id);
+            let task_id = task_id(interval_id);
+            let interval = Self {
+                period,
+                callback,
+                task_id,
+            };
 
-        intervals_storage.intervals.insert(interval_id, interval);
+            intervals_storage.intervals.insert(interval_id, interval);
 
-        interval_id
+            interval_id
+        })
     }
 
-    /// Run a closure with a reference to the [Interval].
-    pub fn with(
+    /// Run a closure with a reference to the [Interval], if it is still registered.
+    pub fn with<R>(
         host_data: &HostData<RuntimeMacroTask>,
         interval_id: &IntervalId,
-        run: impl FnOnce(&Self),
-    ) {
-        let host_data_storage = host_data.storage.borrow();
-        let intervals_storage: &IntervalsStorage = host_data_storage.get().unwrap();
-        let interval = intervals_storage.intervals.get(interval_id).unwrap();
-        run(interval);
+        run: impl FnOnce(&Self) -> R,
+    ) -> Option<R> {
+        host_data.with_storage(|intervals_storage: &IntervalsStorage| {
+            intervals_storage.intervals.get(interval_id).map(run)
+        })
     }
 }
@@ -1,3 +1,5 @@
+pub mod animation_frame;
+pub mod immediate;
 pub mod interval;
 pub mod timeout;
 
@@ -13,9 +15,15 @@ use nova_vm::ecmascript::{
 };
 use tokio::time::interval;
 
-use andromeda_core::{Extension, ExtensionOp, HostData, MacroTask, OpsStorage};
+use andromeda_core::{
+    extension_op,
+    webidl::{to_callback_function, to_long, IntegerConversion},
+    Extension, HostData, MacroTask, OpsStorage,
+};
 
 use crate::RuntimeMacroTask;
+use animation_frame::{AnimationFrameId, AnimationFramesStorage, DEFAULT_FRAME_RATE};
+use immediate::{ImmediateId, ImmediatesStorage};
 use interval::{Interval, IntervalId, IntervalsStorage};
 use timeout::{Timeout, TimeoutId, TimeoutsStorage};
 
@@ -24,31 +32,49 @@ pub struct TimeExt;
 
 impl TimeExt {
     pub fn new_extension() -> Extension {
+        Self::new_extension_with_frame_rate(DEFAULT_FRAME_RATE)
+    }
+
+    /// Same as [TimeExt::new_extension] but running `requestAnimationFrame` callbacks
+    /// `frame_rate` times per second.
+    pub fn new_extension_with_frame_rate(frame_rate: u32) -> Extension {
         Extension {
             name: "time",
             ops: vec![
-                ExtensionOp::new("internal_sleep", Self::internal_sleep, 1),
-                ExtensionOp::new("setInterval", Self::set_interval, 2),
-                ExtensionOp::new("clearInterval", Self::clear_interval, 1),
-                ExtensionOp::new("setTimeout", Self::set_timeout, 2),
-                ExtensionOp::new("clearTimeout", Self::clear_timeout, 1),
+                extension_op!(async "internal_sleep", Self::internal_sleep, 1),
+                extension_op!("setInterval", Self::set_interval, 2),
+                extension_op!("clearInterval", Self::clear_interval, 1),
+                extension_op!("setTimeout", Self::set_timeout, 2),
+                extension_op!("clearTimeout", Self::clear_timeout, 1),
+                extension_op!(
+                    "internal_set_unref_timeout",
+                    Self::internal_set_unref_timeout,
+                    2
+                ),
+                extension_op!("setImmediate", Self::set_immediate, 1),
+                extension_op!("clearImmediate", Self::clear_immediate, 1),
+                extension_op!("requestAnimationFrame", Self::request_animation_frame, 1),
+                extension_op!("cancelAnimationFrame", Self::cancel_animation_frame, 1),
             ],
-            storage: Some(Box::new(|storage: &mut OpsStorage| {
+            storage: Some(Box::new(move |storage: &mut OpsStorage| {
                 storage.insert(IntervalsStorage::default());
                 storage.insert(TimeoutsStorage::default());
+                storage.insert(ImmediatesStorage::default());
+                storage.insert(AnimationFramesStorage::new(frame_rate));
             })),
             files: vec![],
+            deps: &[],
+            cfg: None,
         }
     }
 
     pub fn internal_sleep(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let time_ms = Self::delay(agent, args.get(0), "Argument 1 of sleep")?;
+        let duration = Duration::from_millis(time_ms);
         let promise_capability = PromiseCapability::new(agent);
-        let time_ms = args[0].to_uint32(agent).unwrap();
-        let duration = Duration::from_millis(time_ms as u64);
 
         let root_value = Global::new(agent, promise_capability.promise().into_value());
-        let host_data = agent.get_host_data();
-        let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
         let macro_task_tx = host_data.macro_task_tx();
 
         host_data.spawn_macro_task(async move {
@@ -60,13 +86,13 @@ impl TimeExt {
     }
 
     pub fn set_interval(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        let callback = args[0];
-        let time_ms = args[1].to_uint32(agent).unwrap();
-        let period = Duration::from_millis(time_ms as u64);
+        let callback = to_callback_function(agent, args.get(0), "Argument 1 of setInterval")?;
+        let time_ms = Self::delay(agent, args.get(1), "Argument 2 of setInterval")?;
+        // Tokio intervals panic on a zero period.
+        let period = Duration::from_millis(time_ms.max(1));
 
-        let root_callback = Global::new(agent, callback);
-        let host_data = agent.get_host_data();
-        let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+        let root_callback = Global::new(agent, callback.into_value());
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
         let macro_task_tx = host_data.macro_task_tx();
 
         let interval_id = Interval::create(host_data, period, root_callback, |interval_id| {
@@ -87,12 +113,10 @@ impl TimeExt {
     }
 
     pub fn clear_interval(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        let interval_id_value = args[0];
-        let interval_id_u32 = interval_id_value.to_uint32(agent).unwrap();
+        let interval_id_u32 = args.get(0).to_uint32(agent)?;
         let interval_id = IntervalId::from_index(interval_id_u32);
 
-        let host_data = agent.get_host_data();
-        let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
 
         host_data
             .macro_task_tx
@@ -105,38 +129,66 @@ impl TimeExt {
     }
 
     pub fn set_timeout(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        let callback = args[0];
-        let time_ms = args[1].to_uint32(agent).unwrap();
-        let duration = Duration::from_millis(time_ms as u64);
+        let callback = to_callback_function(agent, args.get(0), "Argument 1 of setTimeout")?;
+        let time_ms = Self::delay(agent, args.get(1), "Argument 2 of setTimeout")?;
+        Ok(Self::create_timeout(
+            agent,
+            callback.into_value(),
+            time_ms,
+            true,
+        ))
+    }
+
+    /// Same as `setTimeout`, but the timeout doesn't keep the event loop alive, e.g. for
+    /// `AbortSignal.timeout`.
+    pub fn internal_set_unref_timeout(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let context = "Argument 1 of internal_set_unref_timeout";
+        let callback = to_callback_function(agent, args.get(0), context)?;
+        let context = "Argument 2 of internal_set_unref_timeout";
+        let time_ms = Self::delay(agent, args.get(1), context)?;
+        Ok(Self::create_timeout(
+            agent,
+            callback.into_value(),
+            time_ms,
+            false,
+        ))
+    }
+
+    /// Schedule the callback after `time_ms` and return the id of the timeout.
+    fn create_timeout(agent: &mut Agent, callback: Value, time_ms: u64, keep_alive: bool) -> Value {
+        let duration = Duration::from_millis(time_ms);
 
         let root_callback = Global::new(agent, callback);
-        let host_data = agent.get_host_data();
-        let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
         let macro_task_tx = host_data.macro_task_tx();
 
         let timeout_id = Timeout::create(host_data, duration, root_callback, |timeout_id| {
-            host_data.spawn_macro_task(async move {
+            let timer = async move {
                 tokio::time::sleep(duration).await;
-                macro_task_tx
-                    .send(MacroTask::User(RuntimeMacroTask::RunAndClearTimeout(
-                        timeout_id,
-                    )))
-                    .unwrap();
-            })
+                // A timeout that doesn't keep the event loop alive may fire after it's gone.
+                let _ = macro_task_tx.send(MacroTask::User(RuntimeMacroTask::RunAndClearTimeout(
+                    timeout_id,
+                )));
+            };
+            if keep_alive {
+                host_data.spawn_macro_task(timer)
+            } else {
+                host_data.spawn_unref_macro_task(timer)
+            }
         });
 
-        let timeout_id_value = Value::from_f64(agent, timeout_id.index() as f64);
-
-        Ok(timeout_id_value)
+        Value::from_f64(agent, timeout_id.index() as f64)
     }
 
     pub fn clear_timeout(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
-        let timeout_id_value = args[0];
-        let timeout_id_u32 = timeout_id_value.to_uint32(agent).unwrap();
+        let timeout_id_u32 = args.get(0).to_uint32(agent)?;
         let timeout_id = TimeoutId::from_index(timeout_id_u32);
 
-        let host_data = agent.get_host_data();
-        let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
 
         host_data
             .macro_task_tx
@@ -145,4 +197,69 @@ impl TimeExt {
 
         Ok(Value::Undefined)
     }
+
+    pub fn set_immediate(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let callback = to_callback_function(agent, args.get(0), "Argument 1 of setImmediate")?;
+
+        let root_callback = Global::new(agent, callback.into_value());
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+
+        let immediate_id = ImmediateId::create(host_data, root_callback);
+
+        let immediate_id_value = Value::from_f64(agent, immediate_id.index() as f64);
+
+        Ok(immediate_id_value)
+    }
+
+    pub fn clear_immediate(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let immediate_id_u32 = args.get(0).to_uint32(agent)?;
+        let immediate_id = ImmediateId::from_index(immediate_id_u32);
+
+        immediate_id.clear(agent);
+
+        Ok(Value::Undefined)
+    }
+
+    pub fn request_animation_frame(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let callback =
+            to_callback_function(agent, args.get(0), "Argument 1 of requestAnimationFrame")?;
+
+        let root_callback = Global::new(agent, callback.into_value());
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+
+        let frame_id = AnimationFrameId::request(host_data, root_callback);
+
+        let frame_id_value = Value::from_f64(agent, frame_id.index() as f64);
+
+        Ok(frame_id_value)
+    }
+
+    pub fn cancel_animation_frame(
+        agent: &mut Agent,
+        _this: Value,
+        args: ArgumentsList,
+    ) -> JsResult<Value> {
+        let frame_id_u32 = args.get(0).to_uint32(agent)?;
+        let frame_id = AnimationFrameId::from_index(frame_id_u32);
+
+        let host_data = HostData::<RuntimeMacroTask>::from_agent(agent);
+
+        frame_id.cancel(host_data);
+
+        Ok(Value::Undefined)
+    }
+
+    /// Convert a timer delay like HTML does: a WebIDL `long` where negative values mean 0.
+    fn delay(agent: &mut Agent, value: Value, context: &str) -> JsResult<u64> {
+        let time_ms = to_long(agent, value, IntegerConversion::Modulo, context)?;
+        Ok(time_ms.max(0) as u64)
+    }
 }
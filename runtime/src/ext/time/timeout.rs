@@ -9,7 +9,10 @@ use std::{
 
 use andromeda_core::{HostData, TaskId};
 use nova_vm::ecmascript::{
-    execution::agent::{GcAgent, RealmRoot},
+    execution::{
+        agent::{GcAgent, RealmRoot},
+        JsResult,
+    },
     types::{Function, Global, Value},
 };
 
@@ -34,41 +37,47 @@ impl TimeoutId {
         Self(index)
     }
 
-    /// Remove the timeout.
+    /// Remove the timeout, if it is still registered.
     pub fn clear(self, host_data: &HostData<RuntimeMacroTask>) {
-        let mut host_data_storage = host_data.storage.borrow_mut();
-        let timeouts_storage: &mut TimeoutsStorage = host_data_storage.get_mut().unwrap();
-        let timeout = timeouts_storage.timeouts.remove(&self).unwrap();
-        host_data.clear_macro_task(timeout.task_id);
+        let timeout = host_data.with_storage_mut(|timeouts_storage: &mut TimeoutsStorage| {
+            timeouts_storage.timeouts.remove(&self)
+        });
+        if let Some(timeout) = timeout {
+            host_data.clear_macro_task(timeout.task_id);
+        }
     }
 
-    /// Remove and abort the timeout.
+    /// Remove and abort the timeout. Unknown, already run or cleared timeouts are ignored.
     pub fn clear_and_abort(self, host_data: &HostData<RuntimeMacroTask>) {
-        let mut host_data_storage = host_data.storage.borrow_mut();
-        let timeouts_storage: &mut TimeoutsStorage = host_data_storage.get_mut().unwrap();
-        let timeout = timeouts_storage.timeouts.remove(&self).unwrap();
+        let timeout = host_data.with_storage_mut(|timeouts_storage: &mut TimeoutsStorage| {
+            timeouts_storage.timeouts.remove(&self)
+        });
+        let Some(timeout) = timeout else {
+            return;
+        };
         host_data.abort_macro_task(timeout.task_id);
         host_data.clear_macro_task(timeout.task_id);
     }
 
-    /// Execute the Timeout callback.
+    /// Execute the Timeout callback, returning the exception it throws.
     pub fn run_and_clear(
         self,
         agent: &mut GcAgent,
         host_data: &HostData<RuntimeMacroTask>,
         realm_root: &RealmRoot,
-    ) {
-        Timeout::with(host_data, &self, |timeout| {
-            let global_callback = &timeout.callback;
-            agent.run_in_realm(realm_root, |agent| {
-                let callback = global_callback.get(agent);
-                let callback_function: Function = callback.try_into().unwrap();
-                callback_function
-                    .call(agent, Value::Undefined, &[])
-                    .unwrap();
-            });
-        });
-        self.clear(host_data);
+    ) -> JsResult<()> {
+        agent.run_in_realm(realm_root, |agent| {
+            // The storage must not stay borrowed while the callback runs, it may set other timers.
+            let Some(callback) =
+                Timeout::with(host_data, &self, |timeout| timeout.callback.get(agent))
+            else {
+                return Ok(());
+            };
+            self.clear(host_data);
+            let callback_function: Function = callback.try_into().unwrap();
+            callback_function.call(agent, Value::Undefined, &[])?;
+            Ok(())
+        })
     }
 }
 
@@ -87,31 +96,30 @@ impl Timeout {
         callback: Global<Value>,
         task_id: impl FnOnce(TimeoutId) -> TaskId,
     ) -> TimeoutId {
-        let mut host_data_storage = host_data.storage.borrow_mut();
-        let timeouts_storage: &mut TimeoutsStorage = host_data_storage.get_mut().unwrap();
-        let id = timeouts_storage.count.fetch_add(1, Ordering::Relaxed);
-        let timeout_id = TimeoutId(id);
-        let task_id = task_id(timeout_id);
-        let timeout = Self {
-            period,
-            callback,
-            task_id,
-        };
+        host_data.with_storage_mut(|timeouts_storage: &mut TimeoutsStorage| {
+            let id = timeouts_storage.count.fetch_add(1, Ordering::Relaxed);
+            let timeout_id = TimeoutId(id);
+            let task_id = task_id(timeout_id);
+            let timeout = Self {
+                period,
+                callback,
+                task_id,
+            };
 
-        timeouts_storage.timeouts.insert(timeout_id, timeout);
+            timeouts_storage.timeouts.insert(timeout_id, timeout);
 
-        timeout_id
+            timeout_id
+        })
     }
 
-    /// Run a closure with a reference to the [Timeout].
-    pub fn with(
+    /// Run a closure with a reference to the [Timeout], if it is still registered.
+    pub fn with<R>(
         host_data: &HostData<RuntimeMacroTask>,
         timeout_id: &TimeoutId,
-        run: impl FnOnce(&Self),
-    ) {
-        let host_data_storage = host_data.storage.borrow();
-        let timeouts_storage: &TimeoutsStorage = host_data_storage.get().unwrap();
-        let timeout = timeouts_storage.timeouts.get(timeout_id).unwrap();
-        run(timeout);
+        run: impl FnOnce(&Self) -> R,
+    ) -> Option<R> {
+        host_data.with_storage(|timeouts_storage: &TimeoutsStorage| {
+            timeouts_storage.timeouts.get(timeout_id).map(run)
+        })
     }
 }
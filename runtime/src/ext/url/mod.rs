@@ -1,10 +1,9 @@
-use andromeda_core::{Extension, ExtensionOp};
+use andromeda_core::{extension_op, Extension};
 
 use nova_vm::ecmascript::{
     builtins::ArgumentsList,
-    execution::{Agent, JsResult},
+    execution::{agent::ExceptionType, Agent, JsResult},
     types::Value,
-    // types::Value,
 };
 use url::Url;
 
@@ -16,15 +15,17 @@ impl URLExt {
         Extension {
             name: "url",
             ops: vec![
-                ExtensionOp::new("internal_url_parse", Self::internal_parse, 2),
-                ExtensionOp::new(
+                extension_op!("internal_url_parse", Self::internal_parse, 2),
+                extension_op!(
                     "internal_url_parse_no_base",
                     Self::internal_parse_no_base,
-                    1,
+                    1
                 ),
             ],
             storage: None,
             files: vec![include_str!("./mod.ts")],
+            deps: &[],
+            cfg: None,
         }
     }
 
@@ -35,14 +36,14 @@ impl URLExt {
         let base_url = match Url::parse(base_href.as_str(agent)) {
             Ok(url) => url,
             Err(e) => {
-                return Ok(Value::from_string(agent, format!("Error: {}", e)));
+                return Err(agent.throw_exception(ExceptionType::TypeError, e.to_string()));
             }
         };
 
         let url = match base_url.join(url.as_str(agent)) {
             Ok(url) => url,
             Err(e) => {
-                return Ok(Value::from_string(agent, format!("Error: {}", e)));
+                return Err(agent.throw_exception(ExceptionType::TypeError, e.to_string()));
             }
         };
 
@@ -59,7 +60,7 @@ impl URLExt {
         let url = match Url::parse(url.as_str(agent)) {
             Ok(url) => url,
             Err(e) => {
-                return Ok(Value::from_string(agent, format!("Error: {}", e)));
+                return Err(agent.throw_exception(ExceptionType::TypeError, e.to_string()));
             }
         };
 
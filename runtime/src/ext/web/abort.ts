@@ -0,0 +1,120 @@
+// deno-lint-ignore-file no-unused-vars no-explicit-any
+
+const abortSignalToken = Symbol("[[abortSignalToken]]");
+const abortSignalAbort = Symbol("[[abort]]");
+
+/**
+ * The `AbortSignal` interface represents a signal object that allows you to communicate with an asynchronous
+ * operation and abort it if required via an `AbortController`.
+ *
+ * @example
+ * ```ts
+ * const signal = AbortSignal.timeout(1000);
+ * await Andromeda.sleep(5000, { signal }); // Rejects with a "TimeoutError" DOMException
+ * ```
+ */
+class AbortSignal extends EventTarget {
+  aborted = false;
+  reason: any = undefined;
+  onabort: ((event: Event) => void) | null = null;
+
+  constructor(token?: symbol) {
+    if (token !== abortSignalToken) {
+      throw new TypeError("Illegal constructor.");
+    }
+    super();
+  }
+
+  /**
+   * Throw the abort reason if the signal has been aborted.
+   */
+  throwIfAborted() {
+    if (this.aborted) {
+      throw this.reason;
+    }
+  }
+
+  [abortSignalAbort](reason?: any) {
+    if (this.aborted) return;
+    this.aborted = true;
+    this.reason = reason === undefined
+      ? new DOMException("signal is aborted without reason", "AbortError")
+      : reason;
+
+    const event = new Event("abort");
+    if (typeof this.onabort === "function") {
+      try {
+        this.onabort.call(this, event);
+      } catch (error) {
+        reportError(error);
+      }
+    }
+    this.dispatchEvent(event);
+  }
+
+  /**
+   * Create an already aborted signal, with `reason` or an "AbortError" `DOMException` as its reason.
+   */
+  static abort(reason?: any): AbortSignal {
+    const signal = new AbortSignal(abortSignalToken);
+    signal[abortSignalAbort](reason);
+    return signal;
+  }
+
+  /**
+   * Create a signal that aborts with a "TimeoutError" `DOMException` after `milliseconds`.
+   * The timer doesn't keep the event loop alive.
+   */
+  static timeout(milliseconds: number): AbortSignal {
+    const signal = new AbortSignal(abortSignalToken);
+    internal_set_unref_timeout(() => {
+      signal[abortSignalAbort](
+        new DOMException("signal timed out", "TimeoutError"),
+      );
+    }, milliseconds);
+    return signal;
+  }
+
+  /**
+   * Create a signal that aborts as soon as any of the given signals aborts, with the same reason.
+   */
+  static any(signals: Iterable<AbortSignal>): AbortSignal {
+    const signal = new AbortSignal(abortSignalToken);
+    const sources = Array.from(signals);
+    const aborted = sources.find((source) => source.aborted);
+    if (aborted) {
+      signal[abortSignalAbort](aborted.reason);
+      return signal;
+    }
+    for (const source of sources) {
+      source.addEventListener(
+        "abort",
+        () => signal[abortSignalAbort](source.reason),
+        { once: true, signal },
+      );
+    }
+    return signal;
+  }
+}
+
+/**
+ * The `AbortController` interface represents a controller object that allows you to abort one or more
+ * asynchronous operations as and when desired.
+ *
+ * @example
+ * ```ts
+ * const controller = new AbortController();
+ * Andromeda.sleep(1000, { signal: controller.signal }).catch((e) => console.log(e.name));
+ * controller.abort();
+ * ```
+ */
+class AbortController {
+  readonly signal: AbortSignal = new AbortSignal(abortSignalToken);
+
+  /**
+   * Abort the signal of this controller, with `reason` or an "AbortError" `DOMException` as its reason.
+   */
+  abort(reason?: any) {
+    this.signal[abortSignalAbort](reason);
+  }
+}
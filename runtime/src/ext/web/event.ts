@@ -0,0 +1,202 @@
+// deno-lint-ignore-file no-unused-vars no-explicit-any
+
+/**
+ * The `reportError` function reports an exception nobody can catch, e.g. thrown by an event listener,
+ * without interrupting the caller.
+ *
+ * @example
+ * ```ts
+ * reportError(new Error("Something went wrong"));
+ * ```
+ */
+function reportError(error: any) {
+  console.error("Uncaught", error);
+}
+
+/**
+ * The `DOMException` interface represents an abnormal event that occurs as a result of calling a web API.
+ *
+ * @example
+ * ```ts
+ * throw new DOMException("The operation was aborted.", "AbortError");
+ * ```
+ */
+class DOMException extends Error {
+  static readonly INDEX_SIZE_ERR = 1;
+  static readonly DOMSTRING_SIZE_ERR = 2;
+  static readonly HIERARCHY_REQUEST_ERR = 3;
+  static readonly WRONG_DOCUMENT_ERR = 4;
+  static readonly INVALID_CHARACTER_ERR = 5;
+  static readonly NO_DATA_ALLOWED_ERR = 6;
+  static readonly NO_MODIFICATION_ALLOWED_ERR = 7;
+  static readonly NOT_FOUND_ERR = 8;
+  static readonly NOT_SUPPORTED_ERR = 9;
+  static readonly INUSE_ATTRIBUTE_ERR = 10;
+  static readonly INVALID_STATE_ERR = 11;
+  static readonly SYNTAX_ERR = 12;
+  static readonly INVALID_MODIFICATION_ERR = 13;
+  static readonly NAMESPACE_ERR = 14;
+  static readonly INVALID_ACCESS_ERR = 15;
+  static readonly VALIDATION_ERR = 16;
+  static readonly TYPE_MISMATCH_ERR = 17;
+  static readonly SECURITY_ERR = 18;
+  static readonly NETWORK_ERR = 19;
+  static readonly ABORT_ERR = 20;
+  static readonly URL_MISMATCH_ERR = 21;
+  static readonly QUOTA_EXCEEDED_ERR = 22;
+  static readonly TIMEOUT_ERR = 23;
+  static readonly INVALID_NODE_TYPE_ERR = 24;
+  static readonly DATA_CLONE_ERR = 25;
+
+  /**
+   * The legacy error code of the exception name, or 0 for names introduced after error codes were deprecated.
+   */
+  readonly code: number;
+
+  constructor(message = "", name = "Error") {
+    super(String(message));
+    this.name = String(name);
+    this.code = domExceptionCodes[this.name] || 0;
+  }
+}
+
+const domExceptionCodes: Record<string, number> = {
+  IndexSizeError: 1,
+  HierarchyRequestError: 3,
+  WrongDocumentError: 4,
+  InvalidCharacterError: 5,
+  NoModificationAllowedError: 7,
+  NotFoundError: 8,
+  NotSupportedError: 9,
+  InvalidStateError: 11,
+  SyntaxError: 12,
+  InvalidModificationError: 13,
+  NamespaceError: 14,
+  InvalidAccessError: 15,
+  TypeMismatchError: 17,
+  SecurityError: 18,
+  NetworkError: 19,
+  AbortError: 20,
+  URLMismatchError: 21,
+  QuotaExceededError: 22,
+  TimeoutError: 23,
+  InvalidNodeTypeError: 24,
+  DataCloneError: 25,
+};
+
+/**
+ * The `Event` interface represents an event which takes place on an `EventTarget`.
+ */
+class Event {
+  readonly type: string;
+  readonly bubbles: boolean;
+  readonly cancelable: boolean;
+  defaultPrevented = false;
+  target: EventTarget | null = null;
+  currentTarget: EventTarget | null = null;
+  readonly timeStamp: number = Date.now();
+
+  constructor(
+    type: string,
+    init: { bubbles?: boolean; cancelable?: boolean } = {},
+  ) {
+    this.type = String(type);
+    this.bubbles = !!init.bubbles;
+    this.cancelable = !!init.cancelable;
+  }
+
+  /**
+   * Cancel the event if it is cancelable.
+   */
+  preventDefault() {
+    if (this.cancelable) {
+      this.defaultPrevented = true;
+    }
+  }
+}
+
+type EventListenerEntry = {
+  listener: any;
+  once: boolean;
+};
+
+const eventTargetListeners = Symbol("[[listeners]]");
+
+/**
+ * The `EventTarget` interface is implemented by objects that can receive events and may have listeners for them.
+ *
+ * @example
+ * ```ts
+ * const target = new EventTarget();
+ * target.addEventListener("ping", () => console.log("pong"));
+ * target.dispatchEvent(new Event("ping"));
+ * ```
+ */
+class EventTarget {
+  [eventTargetListeners]: Record<string, EventListenerEntry[]> = {};
+
+  /**
+   * Register a listener for events of the given type.
+   * Supports the `once` and `signal` options.
+   */
+  addEventListener(
+    type: string,
+    listener: any,
+    options: boolean | { once?: boolean; signal?: AbortSignal } = {},
+  ) {
+    if (listener === null || listener === undefined) return;
+    const { once = false, signal = undefined } = typeof options === "object"
+      ? options
+      : {};
+    if (signal && signal.aborted) return;
+
+    const listeners = this[eventTargetListeners][type] || [];
+    this[eventTargetListeners][type] = listeners;
+    if (listeners.some((entry) => entry.listener === listener)) return;
+    listeners.push({ listener, once });
+
+    if (signal) {
+      signal.addEventListener(
+        "abort",
+        () => this.removeEventListener(type, listener),
+        { once: true },
+      );
+    }
+  }
+
+  /**
+   * Remove a listener previously registered with `addEventListener`.
+   */
+  removeEventListener(type: string, listener: any) {
+    const listeners = this[eventTargetListeners][type];
+    if (!listeners) return;
+    this[eventTargetListeners][type] = listeners.filter((entry) =>
+      entry.listener !== listener
+    );
+  }
+
+  /**
+   * Dispatch an event to this target, calling its listeners in registration order.
+   * Returns `false` if the event was canceled.
+   */
+  dispatchEvent(event: Event): boolean {
+    event.target = this;
+    event.currentTarget = this;
+    const listeners = (this[eventTargetListeners][event.type] || []).slice();
+    for (const entry of listeners) {
+      if (entry.once) this.removeEventListener(event.type, entry.listener);
+      // A throwing listener doesn't stop the others nor throw to whoever dispatched the event.
+      try {
+        if (typeof entry.listener === "function") {
+          entry.listener.call(this, event);
+        } else {
+          entry.listener.handleEvent(event);
+        }
+      } catch (error) {
+        reportError(error);
+      }
+    }
+    event.currentTarget = null;
+    return !event.defaultPrevented;
+  }
+}
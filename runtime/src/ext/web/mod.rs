@@ -0,0 +1,19 @@
+use andromeda_core::Extension;
+
+/// Web extension for Andromeda.
+/// This extension provides web platform classes such as `EventTarget`, `DOMException` and `AbortController`.
+#[derive(Default)]
+pub struct WebExt;
+
+impl WebExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "web",
+            ops: vec![],
+            storage: None,
+            files: vec![include_str!("./event.ts"), include_str!("./abort.ts")],
+            deps: &[],
+            cfg: None,
+        }
+    }
+}
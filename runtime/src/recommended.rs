@@ -1,15 +1,35 @@
 use andromeda_core::{Extension, HostData};
-use nova_vm::ecmascript::execution::agent::{GcAgent, RealmRoot};
+use nova_vm::ecmascript::execution::{
+    agent::{GcAgent, RealmRoot},
+    JsResult,
+};
 
-use crate::{ConsoleExt, FsExt, ProcessExt, RuntimeMacroTask, TimeExt, URLExt};
+use crate::{
+    ext::animation_frame::run_animation_frames, run_shutdown_listener, CacheExt, ConsoleExt,
+    CsvExt, DiffExt, DomExt, ErrorsExt, FormatsExt, FsExt, HtmlExt, InternalsExt, PasswordsExt,
+    ProcessExt, PromptsExt, RegexExt, RuntimeMacroTask, SmtpExt, TimeExt, URLExt, WebExt,
+};
 
 pub fn recommended_extensions() -> Vec<Extension> {
     vec![
+        ErrorsExt::new_extension(),
+        WebExt::new_extension(),
         FsExt::new_extension(),
         ConsoleExt::new_extension(),
         TimeExt::new_extension(),
         ProcessExt::new_extension(),
         URLExt::new_extension(),
+        DomExt::new_extension(),
+        PasswordsExt::new_extension(),
+        CsvExt::new_extension(),
+        FormatsExt::new_extension(),
+        RegexExt::new_extension(),
+        CacheExt::new_extension(),
+        DiffExt::new_extension(),
+        HtmlExt::new_extension(),
+        PromptsExt::new_extension(),
+        SmtpExt::new_extension(),
+        InternalsExt::new_extension(),
     ]
 }
 
@@ -22,17 +42,26 @@ pub fn recommended_eventloop_handler(
     agent: &mut GcAgent,
     realm_root: &RealmRoot,
     host_data: &HostData<RuntimeMacroTask>,
-) {
+) -> JsResult<()> {
     match macro_task {
         RuntimeMacroTask::RunInterval(interval_id) => interval_id.run(agent, host_data, realm_root),
         RuntimeMacroTask::ClearInterval(interval_id) => {
             interval_id.clear_and_abort(host_data);
+            Ok(())
         }
         RuntimeMacroTask::RunAndClearTimeout(timeout_id) => {
             timeout_id.run_and_clear(agent, host_data, realm_root)
         }
         RuntimeMacroTask::ClearTimeout(timeout_id) => {
             timeout_id.clear_and_abort(host_data);
+            Ok(())
+        }
+        RuntimeMacroTask::RunImmediate(immediate_id) => {
+            immediate_id.run(agent, host_data, realm_root)
+        }
+        RuntimeMacroTask::RunAnimationFrames => run_animation_frames(agent, host_data, realm_root),
+        RuntimeMacroTask::RunShutdownListener(shutdown_signal) => {
+            run_shutdown_listener(agent, host_data, realm_root, shutdown_signal)
         }
     }
 }
@@ -0,0 +1,84 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn abort_notifies_listeners_with_an_abort_error() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const calls = [];
+        const controller = new AbortController();
+        controller.signal.onabort = () => calls.push("onabort");
+        controller.signal.addEventListener("abort", () => calls.push("listener"));
+        controller.abort();
+        controller.abort();
+        [calls.join(), controller.signal.aborted, controller.signal.reason.name].join();
+        "#,
+    );
+    assert_eq!(result.unwrap(), "onabort,listener,true,AbortError");
+}
+
+#[test]
+fn throwing_listeners_do_not_stop_the_others() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const calls = [];
+        const controller = new AbortController();
+        controller.signal.onabort = () => { throw new Error("onabort failed"); };
+        controller.signal.addEventListener("abort", () => { throw new Error("first failed"); });
+        controller.signal.addEventListener("abort", () => calls.push("second"));
+        controller.abort("reason");
+        [calls.join(), controller.signal.reason].join();
+        "#,
+    );
+    assert_eq!(result.unwrap(), "second,reason");
+}
+
+#[test]
+fn timeout_does_not_keep_the_event_loop_alive() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval("const signal = AbortSignal.timeout(60000);")
+        .unwrap();
+    assert_eq!(runtime.pending_macro_tasks(), 0);
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("signal.aborted").unwrap(), "false");
+}
+
+#[test]
+fn timeout_aborts_with_a_timeout_error() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            const signal = AbortSignal.timeout(1);
+            setTimeout(() => {}, 50);
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(
+        runtime
+            .eval("`${signal.aborted} ${signal.reason.name}`")
+            .unwrap(),
+        "true TimeoutError"
+    );
+}
+
+#[test]
+fn any_aborts_with_the_first_reason() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const first = new AbortController();
+        const second = new AbortController();
+        const signal = AbortSignal.any([first.signal, second.signal]);
+        second.abort("second");
+        first.abort("first");
+        [signal.aborted, signal.reason, AbortSignal.any([AbortSignal.abort("early")]).reason].join();
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true,second,early");
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn animation_frames_get_a_timestamp() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            const timestamps = [];
+            requestAnimationFrame((timestamp) => timestamps.push(typeof timestamp));
+            requestAnimationFrame((timestamp) => timestamps.push(typeof timestamp));
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("timestamps.join()").unwrap(), "number,number");
+}
+
+#[test]
+fn frames_requested_by_a_callback_run_in_the_next_frame() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let later;
+            requestAnimationFrame((first) => {
+              requestAnimationFrame((second) => later = second > first);
+            });
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("later").unwrap(), "true");
+}
+
+#[test]
+fn cancelled_animation_frame_does_not_run() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval("let ran = false; cancelAnimationFrame(requestAnimationFrame(() => ran = true));")
+        .unwrap();
+    // The frame is aborted with its last callback, so it doesn't keep the event loop alive.
+    assert_eq!(runtime.pending_macro_tasks(), 0);
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("ran").unwrap(), "false");
+}
+
+#[test]
+fn throwing_animation_frame_callback_is_returned_by_the_event_loop() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let second = false;
+            requestAnimationFrame(() => { throw new Error("frame failed"); });
+            requestAnimationFrame(() => second = true);
+            "#,
+        )
+        .unwrap();
+    let error = runtime.run_event_loop().unwrap_err();
+    assert!(error.contains("frame failed"), "{error}");
+    assert_eq!(runtime.eval("second").unwrap(), "false");
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn deep_equality_ignores_set_order() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        assertEquals(
+          { name: "andromeda", tags: new Set(["js", "ts"]), sizes: new Map([["a", [1, 2]]]) },
+          { name: "andromeda", tags: new Set(["ts", "js"]), sizes: new Map([["a", [1, 2]]]) },
+        );
+        assertNotEquals([1, 2, 3], [1, 2]);
+        "passed";
+        "#,
+    );
+    assert_eq!(result.unwrap(), "passed");
+}
+
+#[test]
+fn failed_assertions_throw_assertion_errors_with_a_diff() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        try {
+          assertEquals({ a: 1, b: [1, 2] }, { a: 1, b: [1, 3] }, "numbers differ");
+          "passed";
+        } catch (error) {
+          [
+            error instanceof AssertionError,
+            error.message.startsWith("numbers differ"),
+            error.message.includes("3"),
+          ].join();
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true,true,true");
+}
+
+#[test]
+fn assert_throws_checks_the_class_and_message() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const error = assertThrows(() => {
+          throw new TypeError("Hello, World!");
+        }, TypeError, "Hello");
+        let mismatch;
+        try {
+          assertThrows(() => {
+            throw new TypeError("Hello, World!");
+          }, RangeError);
+        } catch (error) {
+          mismatch = error instanceof AssertionError;
+        }
+        [error.name, mismatch].join();
+        "#,
+    );
+    assert_eq!(result.unwrap(), "TypeError,true");
+}
+
+#[test]
+fn assert_rejects_resolves_to_the_error() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let rejected;
+            assertRejects(() => Promise.reject(new RangeError("out")), RangeError)
+              .then((error) => rejected = error.message);
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("rejected").unwrap(), "out");
+}
+
+#[test]
+fn assert_match_tests_the_pattern() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        assertMatch("andromeda v0.1.0", /v\d+\.\d+\.\d+/);
+        try {
+          assertMatch("andromeda", /\d/);
+          "matched";
+        } catch (error) {
+          error instanceof AssertionError;
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true");
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn memory_cache_evicts_least_recently_used() {
+    let mut runtime = test_runtime();
+    let state = runtime.eval(
+        r#"
+        const sessions = Andromeda.caches.memory({ capacity: 2 });
+        sessions.set("a", "ada");
+        sessions.set("b", "grace");
+        sessions.get("a");
+        sessions.set("c", "linus");
+        const state = [sessions.has("a"), sessions.has("b"), sessions.size].join();
+        sessions.close();
+        state;
+        "#,
+    );
+    assert_eq!(state.unwrap(), "true,false,2");
+}
+
+#[test]
+fn memoize_shares_concurrent_calls() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let calls = 0;
+            const square = Andromeda.memoize(
+              (n) => new Promise((resolve) => {
+                calls++;
+                setTimeout(() => resolve(n * n), 1);
+              }),
+              { capacity: 10 },
+            );
+            let results;
+            Promise.all([square(12), square(12)]).then((values) => results = values.join());
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("results").unwrap(), "144,144");
+    assert_eq!(runtime.eval("calls").unwrap(), "1");
+}
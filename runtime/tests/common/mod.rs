@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use andromeda_core::{Permissions, TestRuntime};
+use andromeda_runtime::{
+    recommended_builtins, recommended_eventloop_handler, recommended_extensions, RuntimeMacroTask,
+};
+
+/// A [TestRuntime] with the extensions and builtins of the CLI.
+pub fn test_runtime() -> TestRuntime<RuntimeMacroTask> {
+    TestRuntime::new(
+        recommended_extensions(),
+        recommended_builtins(),
+        recommended_eventloop_handler,
+    )
+}
+
+/// A [TestRuntime] like [test_runtime], but with only the given permissions.
+// Only the permission tests restrict the access.
+#[allow(dead_code)]
+pub fn test_runtime_with_permissions(permissions: Permissions) -> TestRuntime<RuntimeMacroTask> {
+    TestRuntime::with_permissions(
+        recommended_extensions(),
+        recommended_builtins(),
+        recommended_eventloop_handler,
+        permissions,
+    )
+}
+
+/// A path in the temporary directory unique to this test process, along with the JavaScript
+/// string literal of the path.
+// Not every test crate including this module touches the file system.
+#[allow(dead_code)]
+pub fn temp_path(name: &str) -> (PathBuf, String) {
+    let path = std::env::temp_dir().join(format!("andromeda-{}-{name}", std::process::id()));
+    let literal = path_literal(&path);
+    (path, literal)
+}
+
+/// The JavaScript string literal of a path.
+#[allow(dead_code)]
+pub fn path_literal(path: &Path) -> String {
+    // The escapes of `Debug` are valid in JavaScript strings too.
+    format!("{:?}", path.to_str().unwrap())
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn parses_records_with_quoted_fields_and_casts() {
+    let mut runtime = test_runtime();
+    let people = runtime.eval(
+        r#"
+        JSON.stringify(Andromeda.csv.parse(
+          'name,language,year\nAda,"Analytical Engine, notes",1843',
+          { header: true, cast: true },
+        ))
+        "#,
+    );
+    assert_eq!(
+        people.unwrap(),
+        r#"[{"name":"Ada","language":"Analytical Engine, notes","year":1843}]"#
+    );
+}
+
+#[test]
+fn parses_rows_with_a_delimiter() {
+    let mut runtime = test_runtime();
+    let rows = runtime.eval(r#"JSON.stringify(Andromeda.csv.parse("a\tb\n1\t2", { delimiter: "\t" }))"#);
+    assert_eq!(rows.unwrap(), r#"[["a","b"],["1","2"]]"#);
+}
+
+#[test]
+fn stringified_records_parse_back() {
+    let mut runtime = test_runtime();
+    let records = runtime.eval(
+        r#"
+        const records = [{ name: "Grace", quote: 'She said "hi", twice' }];
+        const text = Andromeda.csv.stringify(records);
+        JSON.stringify(Andromeda.csv.parse(text, { header: true })) === JSON.stringify(records);
+        "#,
+    );
+    assert_eq!(records.unwrap(), "true");
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn line_changes_rebuild_both_texts() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const before = "one\ntwo\nthree\n";
+        const after = "one\n2\nthree\nfour\n";
+        const changes = Andromeda.diff.lines(before, after);
+        const side = (skipped) =>
+          changes.filter((change) => change.type !== skipped).map((change) => change.value).join("");
+        [side("insert") === before, side("delete") === after, changes[0].type].join();
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true,true,equal");
+}
+
+#[test]
+fn char_changes_rebuild_both_texts() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const changes = Andromeda.diff.chars("kitten", "sitting");
+        const side = (skipped) =>
+          changes.filter((change) => change.type !== skipped).map((change) => change.value).join("");
+        [side("insert"), side("delete")].join();
+        "#,
+    );
+    assert_eq!(result.unwrap(), "kitten,sitting");
+}
+
+#[test]
+fn unified_patches_apply() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const before = "one\ntwo\nthree\nfour\n";
+        const after = "one\n2\nthree\nfour\nfive\n";
+        const patch = Andromeda.diff.unified(before, after, {
+          oldName: "numbers.txt",
+          newName: "numbers.txt",
+        });
+        [patch.startsWith("--- numbers.txt"), Andromeda.diff.apply(before, patch) === after].join();
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true,true");
+}
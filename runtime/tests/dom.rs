@@ -0,0 +1,76 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn matches_selectors() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            const document = new DOMParser().parseFromString(
+              "<ul><li class='done'>Write</li><li>Ship</li></ul>",
+              "text/html",
+            );
+            "#,
+        )
+        .unwrap();
+    assert_eq!(
+        runtime
+            .eval(r#"document.querySelector("li:not(.done)").textContent"#)
+            .unwrap(),
+        "Ship"
+    );
+    assert_eq!(
+        runtime
+            .eval(r#"document.querySelectorAll("ul > li:nth-child(odd)").length"#)
+            .unwrap(),
+        "1"
+    );
+}
+
+#[test]
+fn unsupported_selectors_throw_syntax_error() {
+    let mut runtime = test_runtime();
+    let error = runtime.eval(
+        r#"
+        try {
+          new DOMParser().parseFromString("<p>hi</p>", "text/html").querySelector("p:hover");
+          "matched";
+        } catch (error) {
+          error.name;
+        }
+        "#,
+    );
+    assert_eq!(error.unwrap(), "SyntaxError");
+}
+
+#[test]
+fn parses_malformed_xml_to_parsererror() {
+    let mut runtime = test_runtime();
+    let root = runtime.eval(
+        r#"
+        new DOMParser()
+          .parseFromString("<feed><entry></feed>", "application/xml")
+          .documentElement.nodeName
+        "#,
+    );
+    assert_eq!(root.unwrap(), "parsererror");
+}
+
+#[test]
+fn caps_the_nesting_depth() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const deep = new DOMParser().parseFromString("<div>".repeat(2000), "text/html");
+        let depth = 0;
+        for (let node = deep.documentElement; node.firstElementChild; depth++) {
+          node = node.firstElementChild;
+        }
+        [depth, deep.getElementsByTagName("div").length].join();
+        "#,
+    );
+    // The html element is at depth 1, so 512 steps reach the elements flattened below depth 512.
+    assert_eq!(result.unwrap(), "512,2000");
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn parses_toml() {
+    let mut runtime = test_runtime();
+    let config = runtime.eval(
+        r#"
+        const config = Andromeda.toml.parse('title = "andromeda"\n\n[server]\nport = 8080\n');
+        [config.title, config.server.port].join();
+        "#,
+    );
+    assert_eq!(config.unwrap(), "andromeda,8080");
+}
+
+#[test]
+fn stringifies_toml_tables() {
+    let mut runtime = test_runtime();
+    let text = runtime.eval(r#"Andromeda.toml.stringify({ dependencies: { nova: "main" } })"#);
+    assert_eq!(text.unwrap().trim(), "[dependencies]\nnova = \"main\"");
+}
+
+#[test]
+fn yaml_round_trips() {
+    let mut runtime = test_runtime();
+    let value = runtime.eval(
+        r#"
+        const value = { name: "andromeda", tags: ["js", "ts"], nested: { port: 8080 } };
+        JSON.stringify(Andromeda.yaml.parse(Andromeda.yaml.stringify(value))) === JSON.stringify(value);
+        "#,
+    );
+    assert_eq!(value.unwrap(), "true");
+}
+
+#[test]
+fn invalid_documents_throw() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        try {
+          Andromeda.toml.parse("title = ");
+          "parsed";
+        } catch (error) {
+          error instanceof Error;
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true");
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use std::fs;
+
+use common::{temp_path, test_runtime};
+
+#[test]
+fn writes_and_reads_text_files() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("text.txt");
+    runtime
+        .eval(&format!(
+            r#"Andromeda.writeTextFileSync({literal}, "Hello, World!");"#
+        ))
+        .unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Hello, World!");
+    assert_eq!(
+        runtime
+            .eval(&format!("Andromeda.readTextFileSync({literal})"))
+            .unwrap(),
+        "Hello, World!"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn missing_files_throw_not_found() {
+    let mut runtime = test_runtime();
+    let (_, literal) = temp_path("missing.txt");
+    let result = runtime.eval(&format!(
+        r#"
+        try {{
+          Andromeda.readTextFileSync({literal});
+          "read";
+        }} catch (error) {{
+          error instanceof Andromeda.errors.NotFound;
+        }}
+        "#
+    ));
+    assert_eq!(result.unwrap(), "true");
+}
+
+#[test]
+fn truncates_asynchronously() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("truncate.txt");
+    fs::write(&path, "0123456789").unwrap();
+    runtime
+        .eval(&format!(
+            r#"
+            let truncated = false;
+            Andromeda.truncate({literal}, 4).then(() => truncated = true);
+            "#
+        ))
+        .unwrap();
+    assert_eq!(runtime.eval("truncated").unwrap(), "false");
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("truncated").unwrap(), "true");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "0123");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn truncate_rejects_lengths_out_of_range() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("truncate-range.txt");
+    fs::write(&path, "0123456789").unwrap();
+    let result = runtime.eval(&format!(
+        r#"
+        [-1, NaN, Infinity, 2 ** 53].map((length) => {{
+          try {{
+            Andromeda.truncate({literal}, length);
+            return "truncated";
+          }} catch (error) {{
+            return error.name;
+          }}
+        }}).join();
+        "#
+    ));
+    assert_eq!(result.unwrap(), "TypeError,TypeError,TypeError,TypeError");
+    runtime.run_event_loop().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "0123456789");
+    fs::remove_file(path).unwrap();
+}
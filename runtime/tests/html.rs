@@ -0,0 +1,75 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn escapes_text() {
+    let mut runtime = test_runtime();
+    assert_eq!(
+        runtime
+            .eval(r#"Andromeda.html.escape(`<a href="/?q=1&r=2">Tom & Jerry's</a>`)"#)
+            .unwrap(),
+        "&lt;a href=&quot;/?q=1&amp;r=2&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn sanitizes_to_the_allowed_tags_and_attributes() {
+    let mut runtime = test_runtime();
+    let sanitized = runtime.eval(
+        r#"
+        Andromeda.html.sanitize(
+          `<p onclick="steal()">Hi <b>there</b> <i>you</i></p><script>steal()</script>`,
+          { tags: ["p", "b"] },
+        )
+        "#,
+    );
+    assert_eq!(sanitized.unwrap(), "<p>Hi <b>there</b> you</p>");
+}
+
+#[test]
+fn sanitizes_javascript_urls() {
+    let mut runtime = test_runtime();
+    let sanitized = runtime.eval(
+        r#"
+        Andromeda.html.sanitize(`<a href="javascript:alert(1)">me</a>`, {
+          tags: ["a"],
+          attributes: { a: ["href"] },
+        })
+        "#,
+    );
+    let sanitized = sanitized.unwrap();
+    assert!(!sanitized.contains("javascript"), "{sanitized}");
+}
+
+#[test]
+fn templates_escape_interpolated_values() {
+    let mut runtime = test_runtime();
+    let html = runtime.eval(
+        r#"
+        const { template: html, raw } = Andromeda.html;
+        const items = ["<apple>", "pear"].map((item) => html`<li>${item}</li>`);
+        String(html`<ul>${items}</ul>${raw("<hr>")}`);
+        "#,
+    );
+    assert_eq!(
+        html.unwrap(),
+        "<ul><li>&lt;apple&gt;</li><li>pear</li></ul><hr>"
+    );
+}
+
+#[test]
+fn rejects_allowing_link_rel() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        try {
+          Andromeda.html.sanitize("<a rel='me'>me</a>", { attributes: { a: ["rel"] } });
+          "sanitized";
+        } catch (error) {
+          error instanceof Andromeda.errors.InvalidData;
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true");
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::fs;
+
+use common::{temp_path, test_runtime};
+
+#[test]
+fn reads_json_lines_skipping_blank_ones() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("lines.ndjson");
+    fs::write(&path, "{\"n\":1}\n\n{\"n\":2}\n").unwrap();
+    runtime
+        .eval(&format!(
+            r#"
+            const values = [];
+            (async () => {{
+              for await (const value of Andromeda.readJsonLines({literal})) {{
+                values.push(value.n);
+              }}
+            }})();
+            "#
+        ))
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("values.join()").unwrap(), "1,2");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn invalid_lines_reject_with_their_line_number() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("invalid.ndjson");
+    fs::write(&path, "{\"n\":1}\n{\"n\":\n").unwrap();
+    runtime
+        .eval(&format!(
+            r#"
+            let failure;
+            (async () => {{
+              for await (const _ of Andromeda.readJsonLines({literal})) {{}}
+            }})().catch((error) => failure = [error.name, error.message.includes(":2: ")].join());
+            "#
+        ))
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("failure").unwrap(), "SyntaxError,true");
+    fs::remove_file(path).unwrap();
+}
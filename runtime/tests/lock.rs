@@ -0,0 +1,130 @@
+mod common;
+
+use std::fs;
+
+use common::{temp_path, test_runtime};
+
+#[test]
+fn whole_file_locks_conflict_between_resources() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("lock-file.txt");
+    fs::write(&path, "0").unwrap();
+    let result = runtime.eval(&format!(
+        r#"
+        const first = Andromeda.openFileSync({literal});
+        const second = Andromeda.openFileSync({literal});
+        Andromeda.lockSync(first, true);
+        const contended = Andromeda.tryLockSync(second, true);
+        Andromeda.unlockSync(first);
+        const released = Andromeda.tryLockSync(second, true);
+        Andromeda.closeSync(first);
+        Andromeda.closeSync(second);
+        [contended, released].join();
+        "#
+    ));
+    assert_eq!(result.unwrap(), "false,true");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn try_lock_range_acquires_free_ranges() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("lock-free-range.txt");
+    fs::write(&path, "0123456789").unwrap();
+    let result = runtime.eval(&format!(
+        r#"
+        const rid = Andromeda.openFileSync({literal}, {{ write: true }});
+        const locked = Andromeda.tryLockRangeSync(rid, 0, 4, true);
+        Andromeda.unlockRangeSync(rid, 0, 4);
+        Andromeda.closeSync(rid);
+        locked;
+        "#
+    ));
+    assert_eq!(result.unwrap(), "true");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn lock_resolves_once_acquired() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("lock-async.txt");
+    fs::write(&path, "0").unwrap();
+    runtime
+        .eval(&format!(
+            r#"
+            let locked = false;
+            const rid = Andromeda.openFileSync({literal});
+            Andromeda.lock(rid, true).then(() => locked = true);
+            "#
+        ))
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("locked").unwrap(), "true");
+    runtime.eval("Andromeda.closeSync(rid)").unwrap();
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn range_locks_conflict_between_resources() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("lock-range.txt");
+    fs::write(&path, "0123456789").unwrap();
+    let result = runtime.eval(&format!(
+        r#"
+        const first = Andromeda.openFileSync({literal}, {{ write: true }});
+        const second = Andromeda.openFileSync({literal}, {{ write: true }});
+        Andromeda.lockRangeSync(first, 0, 4, true);
+        const overlapping = Andromeda.tryLockRangeSync(second, 2, 4, true);
+        const disjoint = Andromeda.tryLockRangeSync(second, 4, 4, true);
+        [overlapping, disjoint].join();
+        "#
+    ));
+    assert_eq!(result.unwrap(), "false,true");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn range_locks_survive_reading_the_file() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("lock-range-read.txt");
+    fs::write(&path, "0123456789").unwrap();
+    let result = runtime.eval(&format!(
+        r#"
+        const first = Andromeda.openFileSync({literal}, {{ write: true }});
+        const second = Andromeda.openFileSync({literal}, {{ write: true }});
+        Andromeda.lockRangeSync(first, 0, 0, true);
+        Andromeda.readTextFileSync({literal});
+        Andromeda.tryLockRangeSync(second, 0, 0, true);
+        "#
+    ));
+    assert_eq!(result.unwrap(), "false");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn lock_range_waits_without_blocking_the_event_loop() {
+    let mut runtime = test_runtime();
+    let (path, literal) = temp_path("lock-range-async.txt");
+    fs::write(&path, "0123456789").unwrap();
+    runtime
+        .eval(&format!(
+            r#"
+            const order = [];
+            const first = Andromeda.openFileSync({literal}, {{ write: true }});
+            const second = Andromeda.openFileSync({literal}, {{ write: true }});
+            Andromeda.lockRangeSync(first, 0, 4, true);
+            Andromeda.lockRange(second, 0, 4, true).then(() => order.push("locked"));
+            setTimeout(() => {{
+              order.push("unlocking");
+              Andromeda.unlockRangeSync(first, 0, 4);
+            }}, 10);
+            "#
+        ))
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("order.join()").unwrap(), "unlocking,locked");
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,74 @@
+use std::time::Instant;
+
+use andromeda_core::{extension_op, Extension, HostData, TestRuntime};
+use andromeda_runtime::{
+    recommended_builtins, recommended_eventloop_handler, recommended_extensions, RuntimeMacroTask,
+};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{Agent, JsResult},
+    types::Value,
+};
+
+fn test_extension() -> Extension {
+    Extension {
+        name: "test",
+        ops: vec![
+            extension_op!("test_panic", test_panic, 0),
+            extension_op!("test_missing_storage", test_missing_storage, 0),
+        ],
+        storage: None,
+        files: vec![],
+        deps: &["errors"],
+        cfg: None,
+    }
+}
+
+fn test_panic(_agent: &mut Agent, _this: Value, _args: ArgumentsList) -> JsResult<Value> {
+    panic!("boom")
+}
+
+fn test_missing_storage(agent: &mut Agent, _this: Value, _args: ArgumentsList) -> JsResult<Value> {
+    // No extension keeps a bare `Instant` in the storage.
+    HostData::<RuntimeMacroTask>::from_agent(agent).with_storage(|_: &Instant| ());
+    Ok(Value::Undefined)
+}
+
+fn runtime_with_test_ops() -> TestRuntime<RuntimeMacroTask> {
+    let mut extensions = recommended_extensions();
+    extensions.push(test_extension());
+    TestRuntime::new(
+        extensions,
+        recommended_builtins(),
+        recommended_eventloop_handler,
+    )
+}
+
+#[test]
+fn panicking_ops_throw_errors() {
+    let mut runtime = runtime_with_test_ops();
+    let result = runtime.eval(
+        r#"
+        try {
+          test_panic();
+        } catch (error) {
+          `${error instanceof Error}: ${error.message}`;
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true: The test_panic op panicked: boom");
+    // The runtime is still usable after the panic.
+    assert_eq!(runtime.eval("1 + 1").unwrap(), "2");
+}
+
+#[test]
+fn storage_panics_name_the_running_op() {
+    let mut runtime = runtime_with_test_ops();
+    let error = runtime
+        .eval("try { test_missing_storage(); } catch (error) { error.message; }")
+        .unwrap();
+    assert!(
+        error.contains("(accessed by the test_missing_storage op)"),
+        "{error}"
+    );
+}
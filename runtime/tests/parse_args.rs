@@ -0,0 +1,61 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn parses_options_and_positionals() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        const { values, positionals } = Andromeda.parseArgs({
+          args: ["-vv", "--port=8080", "--no-color", "--tag", "a", "--tag", "b", "src"],
+          options: {
+            verbose: { type: "boolean", short: "v", multiple: true },
+            port: { type: "number", default: 3000 },
+            color: { type: "boolean", default: true },
+            tag: { type: "string", multiple: true },
+            host: { type: "string", default: "localhost" },
+          },
+          allowPositionals: true,
+          allowNegative: true,
+        });
+        JSON.stringify({ values, positionals });
+        "#,
+    );
+    assert_eq!(
+        result.unwrap(),
+        r#"{"values":{"verbose":[true,true],"port":8080,"color":false,"tag":["a","b"],"host":"localhost"},"positionals":["src"]}"#
+    );
+}
+
+#[test]
+fn unknown_options_throw() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        try {
+          Andromeda.parseArgs({ args: ["--unknown"] });
+          "parsed";
+        } catch (error) {
+          [error instanceof TypeError, error.message.includes("--unknown")].join();
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true,true");
+}
+
+#[test]
+fn invalid_numbers_throw() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        try {
+          Andromeda.parseArgs({ args: ["--port", "http"], options: { port: { type: "number" } } });
+          "parsed";
+        } catch (error) {
+          error instanceof TypeError;
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true");
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::test_runtime;
+
+/// Hash the password with the algorithm, then verify the right and a wrong password.
+fn hash_and_verify(algorithm: &str) -> String {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(&format!(
+            r#"
+            let results;
+            (async () => {{
+              const hash = await Andromeda.passwords.hash("hunter2", {{ algorithm: "{algorithm}" }});
+              results = [
+                hash.includes("hunter2"),
+                await Andromeda.passwords.verify("hunter2", hash),
+                await Andromeda.passwords.verify("hunter3", hash),
+              ].join();
+            }})().catch((error) => results = String(error));
+            "#
+        ))
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    runtime.eval("results").unwrap()
+}
+
+#[test]
+fn argon2id_hashes_verify() {
+    assert_eq!(hash_and_verify("argon2id"), "false,true,false");
+}
+
+#[test]
+fn bcrypt_hashes_verify() {
+    assert_eq!(hash_and_verify("bcrypt"), "false,true,false");
+}
+
+#[test]
+fn unknown_algorithms_reject() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let rejected;
+            Andromeda.passwords
+              .hash("hunter2", { algorithm: "md5" })
+              .catch((error) => rejected = error instanceof TypeError);
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("rejected").unwrap(), "true");
+}
+
+#[test]
+fn argon2id_is_the_default() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(r#"let hash; Andromeda.passwords.hash("hunter2").then((value) => hash = value);"#)
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    let hash = runtime.eval("hash").unwrap();
+    assert!(hash.starts_with("$argon2id$"), "{hash}");
+}
+
+#[test]
+fn malformed_hashes_reject_with_invalid_data() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let rejected;
+            Andromeda.passwords
+              .verify("hunter2", "not a hash")
+              .catch((error) => rejected = error instanceof Andromeda.errors.InvalidData);
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("rejected").unwrap(), "true");
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use andromeda_core::{Permission, Permissions};
+use common::{path_literal, temp_path, test_runtime_with_permissions};
+
+/// Evaluate the expression with the permissions, returning `"denied"` if it threw
+/// `Andromeda.errors.PermissionDenied` and `"allowed"` otherwise.
+fn access(permissions: Permissions, expression: &str) -> String {
+    let mut runtime = test_runtime_with_permissions(permissions);
+    runtime
+        .eval(&format!(
+            r#"
+            try {{
+              {expression};
+              "allowed";
+            }} catch (error) {{
+              error instanceof Andromeda.errors.PermissionDenied ? "denied" : String(error);
+            }}
+            "#
+        ))
+        .unwrap()
+}
+
+fn read_only(paths: Vec<PathBuf>) -> Permissions {
+    Permissions {
+        read: Permission::Only(paths),
+        ..Permissions::deny_all()
+    }
+    .resolve_paths()
+}
+
+#[test]
+fn denies_reading_files() {
+    let (path, literal) = temp_path("denied-read.txt");
+    fs::write(&path, "secret").unwrap();
+    assert_eq!(
+        access(
+            Permissions::deny_all(),
+            &format!("Andromeda.readTextFileSync({literal})")
+        ),
+        "denied"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn denies_writing_files() {
+    let (path, literal) = temp_path("denied-write.txt");
+    assert_eq!(
+        access(
+            Permissions::deny_all(),
+            &format!(r#"Andromeda.writeTextFileSync({literal}, "data")"#)
+        ),
+        "denied"
+    );
+    assert!(!path.exists());
+}
+
+#[test]
+fn denies_env_access() {
+    assert_eq!(
+        access(Permissions::deny_all(), r#"Andromeda.env.get("HOME")"#),
+        "denied"
+    );
+    assert_eq!(
+        access(Permissions::deny_all(), "Andromeda.env.keys()"),
+        "denied"
+    );
+}
+
+#[test]
+fn denies_net_access() {
+    let mut runtime = test_runtime_with_permissions(Permissions::deny_all());
+    runtime
+        .eval(
+            r#"
+            let result;
+            (async () => {
+              await Andromeda.smtp.send(
+                { hostname: "localhost", port: 2525, tls: "none" },
+                { from: "a@example.com", to: "b@example.com", subject: "Hi", text: "Hi" },
+              );
+            })().then(
+              () => result = "allowed",
+              (error) => result = error instanceof Andromeda.errors.PermissionDenied,
+            );
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("result").unwrap(), "true");
+}
+
+#[test]
+fn denies_traversing_out_of_an_allowed_directory() {
+    let (dir, _) = temp_path("allowed-traversal");
+    let (outside, _) = temp_path("outside-traversal.txt");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("inside.txt"), "inside").unwrap();
+    fs::write(&outside, "outside").unwrap();
+    let permissions = read_only(vec![dir.clone()]);
+
+    let inside = path_literal(&dir.join("inside.txt"));
+    assert_eq!(
+        access(
+            permissions.clone(),
+            &format!("Andromeda.readTextFileSync({inside})")
+        ),
+        "allowed"
+    );
+    let traversal = path_literal(&dir.join("..").join(outside.file_name().unwrap()));
+    assert_eq!(
+        access(
+            permissions,
+            &format!("Andromeda.readTextFileSync({traversal})")
+        ),
+        "denied"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+    fs::remove_file(outside).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn denies_following_a_symlink_out_of_an_allowed_directory() {
+    let (dir, _) = temp_path("allowed-symlink");
+    let (outside, _) = temp_path("outside-symlink.txt");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&outside, "outside").unwrap();
+    let link = dir.join("link.txt");
+    std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+    assert_eq!(
+        access(
+            read_only(vec![dir.clone()]),
+            &format!("Andromeda.readTextFileSync({})", path_literal(&link))
+        ),
+        "denied"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+    fs::remove_file(outside).unwrap();
+}
+
+#[test]
+fn empty_allowed_paths_allow_nothing() {
+    // `--allow-read=` parses to a single empty path, which used to resolve to the current directory.
+    assert_eq!(
+        access(
+            read_only(vec![PathBuf::new()]),
+            r#"Andromeda.readTextFileSync("Cargo.toml")"#
+        ),
+        "denied"
+    );
+}
+
+#[test]
+fn assertion_diffs_do_not_need_env_access() {
+    let mut runtime = test_runtime_with_permissions(Permissions::deny_all());
+    let error = runtime.eval(
+        r#"
+        try {
+          assertEquals({ answer: 41 }, { answer: 42 });
+        } catch (error) {
+          [error.name, error.message.includes("42")].join();
+        }
+        "#,
+    );
+    assert_eq!(error.unwrap(), "AssertionError,true");
+}
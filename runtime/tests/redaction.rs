@@ -0,0 +1,48 @@
+use andromeda_runtime::Redactor;
+
+fn redactor(patterns: &[&str], vars: &[(&str, &str)]) -> Redactor {
+    let patterns: Vec<String> = patterns.iter().map(|pattern| pattern.to_string()).collect();
+    let vars = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+    Redactor::new(&patterns, vars)
+}
+
+#[test]
+fn masks_values_of_matching_variables() {
+    let redactor = redactor(
+        &["TOKEN", "SECRET"],
+        &[("GITHUB_TOKEN", "ghp_abcdef"), ("HOME", "/home/ada")],
+    );
+    assert_eq!(
+        redactor.redact("token ghp_abcdef in /home/ada"),
+        "token [REDACTED] in /home/ada"
+    );
+}
+
+#[test]
+fn matches_names_case_insensitively() {
+    let redactor = redactor(&["SECRET"], &[("client_secret", "s3cr3t-value")]);
+    assert_eq!(redactor.redact("s3cr3t-value"), "[REDACTED]");
+}
+
+#[test]
+fn ignores_short_values() {
+    let redactor = redactor(&["KEY"], &[("API_KEY", "abc")]);
+    assert_eq!(redactor.redact("abc"), "abc");
+}
+
+#[test]
+fn masks_the_longest_overlapping_secret_first() {
+    let redactor = redactor(
+        &["TOKEN"],
+        &[("SHORT_TOKEN", "abcd"), ("LONG_TOKEN", "abcdefgh")],
+    );
+    assert_eq!(redactor.redact("abcdefgh abcd"), "[REDACTED] [REDACTED]");
+}
+
+#[test]
+fn is_disabled_without_patterns() {
+    let redactor = redactor(&[], &[("GITHUB_TOKEN", "ghp_abcdef")]);
+    assert_eq!(redactor.redact("ghp_abcdef"), "ghp_abcdef");
+}
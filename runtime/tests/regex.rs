@@ -0,0 +1,76 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn finds_named_groups() {
+    let mut runtime = test_runtime();
+    let pairs = runtime.eval(
+        r#"
+        const pattern = Andromeda.regex.compile("(?P<key>\\w+)=(?P<value>\\d+)");
+        const pairs = pattern
+          .findAll("a=1, b=22")
+          .map((match) => `${match.groups.key}:${match.groups.value}`)
+          .join();
+        pattern.close();
+        pairs;
+        "#,
+    );
+    assert_eq!(pairs.unwrap(), "a:1,b:22");
+}
+
+#[test]
+fn replaces_with_named_groups() {
+    let mut runtime = test_runtime();
+    let replaced = runtime.eval(
+        r#"
+        const pattern = Andromeda.regex.compile("(?P<key>\\w+)=(?P<value>\\d+)");
+        const replaced = pattern.replaceAll("a=1, b=22", "$value=$key");
+        pattern.close();
+        replaced;
+        "#,
+    );
+    assert_eq!(replaced.unwrap(), "1=a, 22=b");
+}
+
+#[test]
+fn splits_with_a_limit() {
+    let mut runtime = test_runtime();
+    let parts = runtime.eval(
+        r#"
+        const separators = Andromeda.regex.compile("\\s*[,;]\\s*");
+        const parts = JSON.stringify(separators.split("a, b;c ,d", 3));
+        separators.close();
+        parts;
+        "#,
+    );
+    assert_eq!(parts.unwrap(), r#"["a","b","c ,d"]"#);
+}
+
+#[test]
+fn invalid_patterns_throw() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        try {
+          Andromeda.regex.compile("(unclosed");
+          "compiled";
+        } catch (error) {
+          error instanceof Error;
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true");
+}
+
+#[test]
+fn reports_external_memory_until_closed() {
+    let mut runtime = test_runtime();
+    let before = runtime.host_data().external_memory.total();
+    runtime
+        .eval(r#"const pattern = Andromeda.regex.compile("[a-z]+\\d*");"#)
+        .unwrap();
+    assert!(runtime.host_data().external_memory.total() > before);
+    runtime.eval("pattern.close();").unwrap();
+    assert_eq!(runtime.host_data().external_memory.total(), before);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn listening_for_signals_does_not_keep_the_event_loop_alive() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let ran = false;
+            Andromeda.shutdown.on(() => ran = true);
+            setTimeout(() => {}, 1);
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.pending_macro_tasks(), 0);
+    assert_eq!(runtime.eval("ran").unwrap(), "false");
+}
+
+#[test]
+fn shutdown_listener_must_be_a_function() {
+    let mut runtime = test_runtime();
+    let result = runtime.eval(
+        r#"
+        try {
+          internal_shutdown_listen(42);
+          "listening";
+        } catch (error) {
+          error instanceof TypeError;
+        }
+        "#,
+    );
+    assert_eq!(result.unwrap(), "true");
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use andromeda_core::Permissions;
+use common::test_runtime;
+
+#[test]
+fn eval_returns_the_completion_value() {
+    let mut runtime = test_runtime();
+    assert_eq!(runtime.eval("const answer = 6 * 7; answer").unwrap(), "42");
+    // Later snippets see the declarations of the previous ones.
+    assert_eq!(runtime.eval("answer + 1").unwrap(), "43");
+}
+
+#[test]
+fn eval_returns_thrown_values() {
+    let mut runtime = test_runtime();
+    let error = runtime.eval(r#"throw new TypeError("boom");"#).unwrap_err();
+    assert!(error.contains("boom"), "{error}");
+}
+
+#[test]
+fn tick_without_tasks_does_not_block() {
+    let mut runtime = test_runtime();
+    assert_eq!(runtime.tick(), Ok(false));
+    assert_eq!(runtime.pending_macro_tasks(), 0);
+}
+
+#[test]
+fn eval_returns_parse_errors() {
+    let mut runtime = test_runtime();
+    let error = runtime.eval("const = 1;").unwrap_err();
+    assert!(error.contains("<test>"), "{error}");
+    // The runtime is still usable after a snippet that didn't parse.
+    assert_eq!(runtime.eval("1 + 1").unwrap(), "2");
+}
+
+#[test]
+fn insert_storage_replaces_the_previous_value() {
+    let runtime = test_runtime();
+    let host_data = runtime.host_data();
+    let previous = host_data.insert_storage(Permissions::allow_all());
+    assert!(previous.is_some());
+    assert!(host_data.remove_storage::<Permissions>().is_some());
+    assert!(host_data.remove_storage::<Permissions>().is_none());
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn insert_storage_reports_conflicting_borrows() {
+    let runtime = test_runtime();
+    let host_data = runtime.host_data();
+    host_data.with_storage(|_: &Permissions| host_data.insert_storage(Permissions::allow_all()));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn measures_terminal_columns() {
+    let mut runtime = test_runtime();
+    assert_eq!(
+        runtime.eval(r#"Andromeda.text.width("日本語")"#).unwrap(),
+        "6"
+    );
+    // Escape sequences take no columns, and the widest line counts.
+    assert_eq!(
+        runtime
+            .eval(r#"Andromeda.text.width("\x1b[31mred\x1b[0m\nlonger")"#)
+            .unwrap(),
+        "6"
+    );
+}
+
+#[test]
+fn truncates_to_the_width() {
+    let mut runtime = test_runtime();
+    let truncated = runtime.eval(
+        r#"
+        const truncated = Andromeda.text.truncate("こんにちは世界", 9);
+        [Andromeda.text.width(truncated) <= 9, truncated.endsWith("…")].join();
+        "#,
+    );
+    assert_eq!(truncated.unwrap(), "true,true");
+    assert_eq!(
+        runtime
+            .eval(r#"Andromeda.text.truncate("short", 9)"#)
+            .unwrap(),
+        "short"
+    );
+}
+
+#[test]
+fn wraps_at_whitespace() {
+    let mut runtime = test_runtime();
+    assert_eq!(
+        runtime
+            .eval(r#"JSON.stringify(Andromeda.text.wrap("the quick brown fox jumps over the lazy dog", 10))"#)
+            .unwrap(),
+        r#"["the quick","brown fox","jumps over","the lazy","dog"]"#
+    );
+}
+
+#[test]
+fn wraps_wide_characters_without_splitting_them() {
+    let mut runtime = test_runtime();
+    assert_eq!(
+        runtime
+            .eval(r#"JSON.stringify(Andromeda.text.wrap("日本語テキスト", 5))"#)
+            .unwrap(),
+        r#"["日本","語テ","キス","ト"]"#
+    );
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::test_runtime;
+
+#[test]
+fn event_loop_phases_run_in_order() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            const order = [];
+            setTimeout(() => order.push("timeout"), 0);
+            setImmediate(() => order.push("immediate"));
+            Promise.resolve().then(() => order.push("microtask"));
+            order.push("script");
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(
+        runtime.eval("order.join()").unwrap(),
+        "script,microtask,immediate,timeout"
+    );
+}
+
+#[test]
+fn timeout_runs_once() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval("let runs = 0; setTimeout(() => runs++, 5);")
+        .unwrap();
+    assert_eq!(runtime.pending_macro_tasks(), 1);
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("runs").unwrap(), "1");
+    assert_eq!(runtime.pending_macro_tasks(), 0);
+}
+
+#[test]
+fn cleared_timeout_does_not_run() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval("let fired = false; clearTimeout(setTimeout(() => fired = true, 5));")
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("fired").unwrap(), "false");
+    assert_eq!(runtime.pending_macro_tasks(), 0);
+}
+
+#[test]
+fn interval_runs_until_cleared() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let runs = 0;
+            const id = setInterval(() => {
+              if (++runs === 3) clearInterval(id);
+            }, 1);
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("runs").unwrap(), "3");
+    assert_eq!(runtime.pending_macro_tasks(), 0);
+}
+
+#[test]
+fn cleared_immediate_does_not_run() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            const ran = [];
+            setImmediate(() => ran.push("kept"));
+            clearImmediate(setImmediate(() => ran.push("cleared")));
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("ran.join()").unwrap(), "kept");
+}
+
+#[test]
+fn sleep_resolves_and_aborts() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            const settled = [];
+            Andromeda.sleep(5).then(() => settled.push("slept"));
+            const controller = new AbortController();
+            Andromeda.sleep(10000, { signal: controller.signal })
+              .catch((reason) => settled.push(reason));
+            controller.abort("aborted");
+            "#,
+        )
+        .unwrap();
+    runtime.run_event_loop().unwrap();
+    assert_eq!(runtime.eval("settled.join()").unwrap(), "aborted,slept");
+}
+
+#[test]
+fn throwing_timer_callback_stops_the_event_loop() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(
+            r#"
+            let later = false;
+            setTimeout(() => { throw new Error("timer failed"); }, 0);
+            setTimeout(() => later = true, 10);
+            "#,
+        )
+        .unwrap();
+    let error = runtime.run_event_loop().unwrap_err();
+    assert!(error.contains("timer failed"), "{error}");
+    assert_eq!(runtime.eval("later").unwrap(), "false");
+}
+
+#[test]
+fn throwing_immediate_is_returned_by_the_event_loop() {
+    let mut runtime = test_runtime();
+    runtime
+        .eval(r#"setImmediate(() => { throw new Error("immediate failed"); });"#)
+        .unwrap();
+    let error = runtime.run_event_loop().unwrap_err();
+    assert!(error.contains("immediate failed"), "{error}");
+}
@@ -0,0 +1,46 @@
+/**
+ * The subset of the `Deno` namespace defined when running with `--deno-compat`.
+ * Paths can also be given as `file:` URLs.
+ */
+declare namespace Deno {
+  const args: string[];
+  const mainModule: string;
+  const errors: typeof Andromeda.errors;
+
+  const env: {
+    get(key: string): string | undefined;
+    set(key: string, value: string): void;
+    delete(key: string): void;
+    has(key: string): boolean;
+    toObject(): Record<string, string>;
+  };
+
+  function exit(code?: number): never;
+
+  function readTextFileSync(path: string | URL): string;
+  function readTextFile(path: string | URL): Promise<string>;
+  function writeTextFileSync(path: string | URL, data: string): void;
+  function writeTextFile(path: string | URL, data: string): Promise<void>;
+  function copyFileSync(source: string | URL, destination: string | URL): void;
+  function copyFile(
+    source: string | URL,
+    destination: string | URL,
+  ): Promise<void>;
+  function mkdirSync(path: string | URL): void;
+  function mkdir(path: string | URL): Promise<void>;
+  function chmod(path: string | URL, mode: number): Promise<void>;
+  function chown(
+    path: string | URL,
+    uid: number | null,
+    gid: number | null,
+  ): Promise<void>;
+  function utime(
+    path: string | URL,
+    atime: number | Date,
+    mtime: number | Date,
+  ): Promise<void>;
+  function link(oldpath: string | URL, newpath: string | URL): Promise<void>;
+  function readLink(path: string | URL): Promise<string>;
+  function realPath(path: string | URL): Promise<string>;
+  function truncate(name: string | URL, len?: number): Promise<void>;
+}
@@ -1,25 +1,79 @@
 /**
- * The `assert` function tests if a condition is true.
+ * A frame of a stack trace, passed to `Error.prepareStackTrace`.
+ */
+interface CallSite {
+  getThis(): unknown;
+  getTypeName(): string | null;
+  getFunction(): ((...args: unknown[]) => unknown) | undefined;
+  getFunctionName(): string | null;
+  getMethodName(): string | null;
+  getFileName(): string | undefined;
+  getLineNumber(): number | null;
+  getColumnNumber(): number | null;
+  isNative(): boolean;
+  isToplevel(): boolean;
+  isEval(): boolean;
+  isConstructor(): boolean;
+}
+
+interface ErrorConstructor {
+  /**
+   * The `captureStackTrace` function sets the `stack` property of the target object. The
+   * engine doesn't record call sites, so the stack is only the `name: message` header, or
+   * whatever `prepareStackTrace` returns for an empty list of frames.
+   *
+   * @example
+   * ```ts
+   * const target: { stack?: string } = {};
+   * Error.captureStackTrace(target);
+   * ```
+   */
+  captureStackTrace(
+    targetObject: object,
+    constructorOpt?: (...args: any[]) => unknown,
+  ): void;
+
+  /**
+   * Hook formatting the `stack` property from the error and its frames.
+   */
+  prepareStackTrace?: (error: Error, stackTraces: CallSite[]) => unknown;
+
+  /**
+   * Maximum number of frames in a stack trace, 10 by default.
+   */
+  stackTraceLimit: number;
+}
+
+/**
+ * The `AssertionError` class is the error thrown by the assertion functions when an assertion fails.
+ */
+declare class AssertionError extends Error {
+  constructor(message: string);
+}
+
+/**
+ * The `assert` function tests if a condition is truthy, throwing an `AssertionError` otherwise.
  *
  * @example
  * ```ts
  * assert(1 === 1, "The condition is true!");
  * ```
  */
-declare function assert(condition: boolean, message: string): void;
+declare function assert(condition: unknown, message?: string): asserts condition;
 
 /**
- * The `assertEquals` function tests if two values are equal.
+ * The `assertEquals` function tests if two values are deeply equal, throwing an `AssertionError`
+ * with a diff of the values otherwise.
  *
  * @example
  * ```ts
- * assertEquals(1, 1, "The values are equal!");
+ * assertEquals({ a: [1, 2] }, { a: [1, 2] }, "The values are equal!");
  * ```
  */
-declare function assertEquals<T>(value1: T, value2: T, message: string): void;
+declare function assertEquals<T>(actual: T, expected: T, message?: string): void;
 
 /**
- * The `assertNotEquals` function tests if two values are not equal.
+ * The `assertNotEquals` function tests if two values are not deeply equal.
  *
  * @example
  * ```ts
@@ -27,21 +81,63 @@ declare function assertEquals<T>(value1: T, value2: T, message: string): void;
  * ```
  */
 declare function assertNotEquals<T>(
-  value1: T,
-  value2: T,
-  message: string,
+  actual: T,
+  expected: T,
+  message?: string,
+): void;
+
+/**
+ * The `assertMatch` function tests if a string matches a regular expression.
+ *
+ * @example
+ * ```ts
+ * assertMatch("Hello, World!", /world/i);
+ * ```
+ */
+declare function assertMatch(
+  actual: string,
+  expected: RegExp,
+  message?: string,
 ): void;
 
 /**
- * The `assertThrows` function tests if a function throws an error.
+ * The `assertThrows` function tests if a function throws an error, optionally of the given class
+ * and with a message including the given text. It returns the thrown error.
  *
  * @example
  * ```ts
  * assertThrows(() => {
- *  throw new Error("Hello, World!");
- * }, "An error occurred!");
+ *  throw new TypeError("Hello, World!");
+ * }, TypeError, "Hello");
+ * ```
+ */
+declare function assertThrows(fn: () => unknown, message?: string): Error;
+declare function assertThrows<E extends Error>(
+  fn: () => unknown,
+  ErrorClass: new (...args: any[]) => E,
+  messageIncludes?: string,
+  message?: string,
+): E;
+
+/**
+ * The `assertRejects` function tests if an async function returns a Promise that rejects, optionally
+ * with an error of the given class and with a message including the given text. It resolves to the error.
+ *
+ * @example
+ * ```ts
+ * await assertRejects(() => Andromeda.readLink("missing"), Andromeda.errors.NotFound);
+ * ```
  */
-declare function assertThrows(fn: () => void, message: string): void;
+declare function assertRejects(
+  fn: () => PromiseLike<unknown>,
+  message?: string,
+): Promise<Error>;
+declare function assertRejects<E extends Error>(
+  fn: () => PromiseLike<unknown>,
+  ErrorClass: new (...args: any[]) => E,
+  messageIncludes?: string,
+  message?: string,
+): Promise<E>;
 
 /**
  * The Andromeda namespace for the Andromeda runtime.
@@ -51,6 +147,104 @@ declare namespace Andromeda {
    * The `args` property contains the command-line arguments.
    */
   const args: string[];
+
+  /**
+   * mainModule contains the `file:` URL of the script the runtime was started with.
+   *
+   * @example
+   * ```ts
+   * const data = new URL("./data.json", Andromeda.mainModule);
+   * ```
+   */
+  const mainModule: string;
+
+  /**
+   * internals namespace for introspecting the runtime, only available with `--expose-internals`.
+   */
+  namespace internals {
+    /**
+     * Metadata of an op registered by a runtime extension.
+     */
+    interface OpInfo {
+      /** Name of the global function. */
+      name: string;
+      /** Number of arguments the op expects. */
+      args: number;
+      /** Whether the op returns a Promise. */
+      async: boolean;
+      /** Name of the extension that registered the op. */
+      extension: string;
+    }
+
+    /**
+     * ops lists the ops registered by the runtime extensions.
+     *
+     * @example
+     * ```ts
+     * for (const op of Andromeda.internals.ops()) {
+     *   console.log(`${op.extension}: ${op.name}/${op.args}`);
+     * }
+     * ```
+     */
+    function ops(): OpInfo[];
+
+    /**
+     * Activity of the thread pool running the blocking work of async ops.
+     */
+    interface BlockingPoolStats {
+      /** Jobs waiting for a free thread. */
+      queued: number;
+      /** Jobs running right now. */
+      running: number;
+      /** Jobs that finished. */
+      completed: number;
+    }
+
+    /**
+     * blockingPool reports the activity of the thread pool running the blocking work of async ops.
+     *
+     * @example
+     * ```ts
+     * console.log(Andromeda.internals.blockingPool().completed);
+     * ```
+     */
+    function blockingPool(): BlockingPoolStats;
+  }
+
+  /**
+   * errors contains the error classes thrown by the runtime, so they can be caught by type.
+   *
+   * @example
+   * ```ts
+   * try {
+   *   Andromeda.readTextFileSync("missing.txt");
+   * } catch (error) {
+   *   if (error instanceof Andromeda.errors.NotFound) {
+   *     console.log("The file does not exist");
+   *   }
+   * }
+   * ```
+   */
+  namespace errors {
+    class NotFound extends Error {}
+    class PermissionDenied extends Error {}
+    class ConnectionRefused extends Error {}
+    class ConnectionReset extends Error {}
+    class ConnectionAborted extends Error {}
+    class NotConnected extends Error {}
+    class AddrInUse extends Error {}
+    class AddrNotAvailable extends Error {}
+    class BrokenPipe extends Error {}
+    class AlreadyExists extends Error {}
+    class InvalidData extends Error {}
+    class TimedOut extends Error {}
+    class Interrupted extends Error {}
+    class WriteZero extends Error {}
+    class UnexpectedEof extends Error {}
+    class Busy extends Error {}
+    class NotSupported extends Error {}
+    class BadResource extends Error {}
+  }
   /**
    * readFileSync reads a file from the file system.
    *
@@ -72,6 +266,283 @@ declare namespace Andromeda {
    */
   function writeTextFileSync(path: string, data: string): void;
 
+  /**
+   * openFileSync opens a file and returns its resource ID.
+   * The file is opened for reading, and also for writing with `{ write: true }`.
+   *
+   * @example
+   * ```ts
+   * const rid = Andromeda.openFileSync("hello.txt", { write: true });
+   * ```
+   */
+  function openFileSync(path: string, options?: { write?: boolean }): number;
+
+  /**
+   * closeSync closes a file given its resource ID.
+   *
+   * @example
+   * ```ts
+   * Andromeda.closeSync(rid);
+   * ```
+   */
+  function closeSync(rid: number): void;
+
+  /**
+   * lockSync acquires an advisory lock on the whole file, shared unless `exclusive` is `true`.
+   *
+   * @example
+   * ```ts
+   * Andromeda.lockSync(rid, true);
+   * ```
+   */
+  function lockSync(rid: number, exclusive?: boolean): void;
+
+  /**
+   * lock acquires an advisory lock on the whole file without blocking the event loop.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.lock(rid, true);
+   * ```
+   */
+  function lock(rid: number, exclusive?: boolean): Promise<void>;
+
+  /**
+   * tryLockSync tries to acquire an advisory lock on the whole file without waiting.
+   *
+   * @example
+   * ```ts
+   * const locked = Andromeda.tryLockSync(rid, true);
+   * ```
+   */
+  function tryLockSync(rid: number, exclusive?: boolean): boolean;
+
+  /**
+   * unlockSync releases the advisory lock held on the whole file.
+   *
+   * @example
+   * ```ts
+   * Andromeda.unlockSync(rid);
+   * ```
+   */
+  function unlockSync(rid: number): void;
+
+  /**
+   * lockRangeSync acquires an advisory lock on a byte range of the file.
+   * A `length` of 0 locks until the end of the file.
+   * Exclusive locks need the file opened with `{ write: true }`.
+   * Outside of Linux, closing any resource of the file releases the range locks of the process.
+   *
+   * @example
+   * ```ts
+   * Andromeda.lockRangeSync(rid, 0, 512, true);
+   * ```
+   */
+  function lockRangeSync(
+    rid: number,
+    start: number,
+    length: number,
+    exclusive?: boolean,
+  ): void;
+
+  /**
+   * tryLockRangeSync tries to acquire an advisory lock on a byte range of the file without waiting.
+   *
+   * @example
+   * ```ts
+   * const locked = Andromeda.tryLockRangeSync(rid, 0, 512, true);
+   * ```
+   */
+  function tryLockRangeSync(
+    rid: number,
+    start: number,
+    length: number,
+    exclusive?: boolean,
+  ): boolean;
+
+  /**
+   * lockRange acquires an advisory lock on a byte range of the file without blocking the event loop.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.lockRange(rid, 0, 512, true);
+   * ```
+   */
+  function lockRange(
+    rid: number,
+    start: number,
+    length: number,
+    exclusive?: boolean,
+  ): Promise<void>;
+
+  /**
+   * unlockRangeSync releases an advisory lock on a byte range of the file.
+   *
+   * @example
+   * ```ts
+   * Andromeda.unlockRangeSync(rid, 0, 512);
+   * ```
+   */
+  function unlockRangeSync(rid: number, start: number, length: number): void;
+
+  /**
+   * chmod changes the permissions of a file using a unix mode.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.chmod("script.sh", 0o755);
+   * ```
+   */
+  function chmod(path: string, mode: number): Promise<void>;
+
+  /**
+   * chown changes the owner user and group of a file. `null` leaves an id unchanged.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.chown("hello.txt", 1000, null);
+   * ```
+   */
+  function chown(
+    path: string,
+    uid: number | null,
+    gid: number | null,
+  ): Promise<void>;
+
+  /**
+   * utime changes the access and modification times of a file or a directory.
+   * Times the system can't represent throw a `RangeError`.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.utime("hello.txt", new Date(), new Date());
+   * ```
+   */
+  function utime(
+    path: string,
+    atime: number | Date,
+    mtime: number | Date,
+  ): Promise<void>;
+
+  /**
+   * link creates a hard link to a file.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.link("hello.txt", "hello-link.txt");
+   * ```
+   */
+  function link(oldpath: string, newpath: string): Promise<void>;
+
+  /**
+   * readLink returns the target of a symbolic link.
+   *
+   * @example
+   * ```ts
+   * const target = await Andromeda.readLink("current");
+   * ```
+   */
+  function readLink(path: string): Promise<string>;
+
+  /**
+   * realPath resolves a path to its absolute form with every symbolic link resolved.
+   *
+   * @example
+   * ```ts
+   * const path = await Andromeda.realPath("./hello.txt");
+   * ```
+   */
+  function realPath(path: string): Promise<string>;
+
+  /**
+   * truncate truncates or extends a file to the given length in bytes.
+   * Negative or non-finite lengths throw a `TypeError`.
+   *
+   * @example
+   * ```ts
+   * await Andromeda.truncate("hello.txt", 5);
+   * ```
+   */
+  function truncate(path: string, len?: number): Promise<void>;
+
+  /**
+   * readJsonLines reads a newline-delimited JSON (NDJSON) file one line at a time,
+   * yielding the parsed value of each non-empty line.
+   *
+   * @example
+   * ```ts
+   * for await (const entry of Andromeda.readJsonLines("logs.ndjson")) {
+   *  console.log(entry);
+   * }
+   * ```
+   */
+  function readJsonLines(
+    path: string,
+  ): AsyncIteratorHelpers<unknown> & AsyncDisposable;
+
+  /**
+   * Configuration of an option of `parseArgs`.
+   */
+  interface ParseArgsOptionConfig {
+    type: "boolean" | "string" | "number";
+    short?: string;
+    multiple?: boolean;
+    default?: ParsedArgValue;
+  }
+
+  /**
+   * Configuration of `parseArgs`, following Node's `util.parseArgs`.
+   */
+  interface ParseArgsConfig {
+    /** Arguments to parse, the arguments after the script path by default. */
+    args?: string[];
+    options?: Record<string, ParseArgsOptionConfig>;
+    /** Throw on unknown options and, unless `allowPositionals`, on positionals. `true` by default. */
+    strict?: boolean;
+    allowPositionals?: boolean;
+    /** Accept `--no-<name>` to set boolean options to `false`. */
+    allowNegative?: boolean;
+    /** Also return the parsed tokens. */
+    tokens?: boolean;
+  }
+
+  type ParsedArgValue =
+    | string
+    | boolean
+    | number
+    | (string | boolean | number)[];
+
+  type ParseArgsToken =
+    | {
+      kind: "option";
+      index: number;
+      name: string;
+      rawName: string;
+      value: string | undefined;
+      inlineValue: boolean | undefined;
+    }
+    | { kind: "positional"; index: number; value: string }
+    | { kind: "option-terminator"; index: number };
+
+  interface ParsedArgs {
+    values: Record<string, ParsedArgValue | undefined>;
+    positionals: string[];
+    tokens?: ParseArgsToken[];
+  }
+
+  /**
+   * parseArgs parses command-line arguments, following the signature of Node's `util.parseArgs`,
+   * with an additional `number` option type and `--no-<name>` negation with `allowNegative`.
+   *
+   * @example
+   * ```ts
+   * const { values } = Andromeda.parseArgs({
+   *   options: { verbose: { type: "boolean", short: "v" }, port: { type: "number", default: 3000 } },
+   * });
+   * ```
+   */
+  function parseArgs(config?: ParseArgsConfig): ParsedArgs;
+
   /**
    * exit exits the program with an optional exit code.
    *
@@ -82,8 +553,59 @@ declare namespace Andromeda {
    */
   function exit(code?: number): void;
 
+  /**
+   * The memory used by the runtime, in bytes.
+   */
+  interface MemoryUsage {
+    /**
+     * Memory held by the runtime outside of the JavaScript heap on behalf of
+     * JavaScript objects, such as the buffers of open resources.
+     */
+    external: number;
+  }
+
+  /**
+   * memoryUsage reports the memory used by the runtime. The runtime collects
+   * garbage as the external memory grows, since the garbage collector can't see it.
+   *
+   * @example
+   * ```ts
+   * console.log(`${Andromeda.memoryUsage().external} bytes held by resources`);
+   * ```
+   */
+  function memoryUsage(): MemoryUsage;
+
+  /**
+   * shutdown coordinates a graceful shutdown on SIGINT or SIGTERM, or Ctrl+C
+   * on Windows.
+   */
+  namespace shutdown {
+    /**
+     * Milliseconds the hooks get to settle before the process exits anyway,
+     * 10 seconds by default.
+     */
+    let timeout: number;
+
+    /**
+     * Register a hook to run with the name of the signal, e.g. to stop
+     * accepting connections and wait for the in-flight requests. The process
+     * exits with the code of the signal once every hook settled, or after
+     * `timeout`. A second signal exits right away.
+     *
+     * @example
+     * ```ts
+     * Andromeda.shutdown.on(async (signal) => {
+     *   console.log(`Received ${signal}, closing the server`);
+     *   await server.close();
+     * });
+     * ```
+     */
+    function on(hook: (signal: string) => unknown): void;
+  }
+
   /**
    * Returns a Promise to be resolved after the specified time un milliseconds.
+   * If a `signal` is given the Promise rejects with its reason as soon as it is aborted.
    *
    * @example
    * ```ts
@@ -91,7 +613,515 @@ declare namespace Andromeda {
    *  console.log("Hello, World!");
    * });
    */
-  function sleep(duration: number): Promise<void>;
+  function sleep(
+    duration: number,
+    options?: { signal?: AbortSignal },
+  ): Promise<void>;
+
+  namespace csv {
+    /**
+     * parse parses CSV text into rows of fields, or into objects keyed by column names with `header`.
+     * With `cast` numbers and booleans are converted from their text. Pass `delimiter: "\t"` for TSV.
+     *
+     * @example
+     * ```ts
+     * const rows = Andromeda.csv.parse("name,age\nAda,36", { header: true, cast: true });
+     * ```
+     */
+    function parse(
+      text: string,
+      options?: {
+        delimiter?: string;
+        quote?: string;
+        escape?: string;
+        header?: boolean | string[];
+        cast?: boolean;
+      },
+    ): unknown[][] | Record<string, unknown>[];
+
+    /**
+     * stringify writes arrays or objects as CSV text. For objects a header row is written with the
+     * `columns`, which default to the keys of the first row, unless `header` is `false`.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.csv.stringify([{ name: "Ada", age: 36 }]);
+     * ```
+     */
+    function stringify(
+      data: unknown[][] | Record<string, unknown>[],
+      options?: {
+        delimiter?: string;
+        quote?: string;
+        escape?: string;
+        header?: boolean;
+        columns?: string[];
+      },
+    ): string;
+  }
+
+  namespace toml {
+    /**
+     * parse parses a TOML document. Dates and times are returned as RFC 3339 strings.
+     *
+     * @example
+     * ```ts
+     * const config = Andromeda.toml.parse(Andromeda.readTextFileSync("config.toml"));
+     * ```
+     */
+    function parse(text: string): Record<string, unknown>;
+
+    /**
+     * stringify writes an object as a TOML document.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.toml.stringify({ server: { port: 8080 } });
+     * ```
+     */
+    function stringify(value: Record<string, unknown>): string;
+  }
+
+  namespace yaml {
+    /**
+     * parse parses a YAML document.
+     *
+     * @example
+     * ```ts
+     * const config = Andromeda.yaml.parse("name: andromeda");
+     * ```
+     */
+    function parse(text: string): unknown;
+
+    /**
+     * stringify writes a value as a YAML document.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.yaml.stringify({ name: "andromeda" });
+     * ```
+     */
+    function stringify(value: unknown): string;
+  }
+
+  namespace diff {
+    /**
+     * A run of text that is the same in both texts, only in the old one, or only in the new one.
+     */
+    interface Change {
+      type: "equal" | "delete" | "insert";
+      value: string;
+    }
+
+    /**
+     * lines compares two texts line by line.
+     *
+     * @example
+     * ```ts
+     * const changes = Andromeda.diff.lines("a\nb\n", "a\nc\n");
+     * ```
+     */
+    function lines(a: string, b: string): Change[];
+
+    /**
+     * words compares two texts word by word.
+     */
+    function words(a: string, b: string): Change[];
+
+    /**
+     * chars compares two texts character by character.
+     */
+    function chars(a: string, b: string): Change[];
+
+    /**
+     * unified writes a unified diff between two texts, with 3 lines of context by default.
+     *
+     * @example
+     * ```ts
+     * const patch = Andromeda.diff.unified(before, after, { oldName: "a.txt", newName: "b.txt" });
+     * ```
+     */
+    function unified(
+      a: string,
+      b: string,
+      options?: { oldName?: string; newName?: string; context?: number },
+    ): string;
+
+    /**
+     * apply applies a unified diff to a text, throwing if the patch context doesn't match.
+     */
+    function apply(text: string, patch: string): string;
+  }
+
+  namespace text {
+    /**
+     * width measures the widest line of the text in terminal columns. East Asian wide characters
+     * and emoji take two columns, ANSI escape sequences none.
+     *
+     * @example
+     * ```ts
+     * const width = Andromeda.text.width("日本語"); // 6
+     * ```
+     */
+    function width(text: string): number;
+
+    /**
+     * truncate cuts the text to fit in `width` columns, ending it with `tail` (`"…"` by default)
+     * if anything was cut.
+     */
+    function truncate(
+      text: string,
+      width: number,
+      options?: { tail?: string },
+    ): string;
+
+    /**
+     * wrap wraps the text in lines of at most `width` columns, breaking at whitespace when possible.
+     */
+    function wrap(text: string, width: number): string[];
+
+    /**
+     * columns returns the width of the terminal, or `undefined` if the output isn't a terminal.
+     */
+    function columns(): number | undefined;
+  }
+
+  namespace html {
+    /**
+     * HTML that templates insert without escaping it.
+     */
+    interface SafeHtml {
+      readonly value: string;
+      toString(): string;
+    }
+
+    /**
+     * Allowlist of `sanitize`. Missing fields keep the default allowlist.
+     */
+    interface SanitizeOptions {
+      /** Tags to keep. `<script>` and `<style>` are always removed. */
+      tags?: string[];
+      /** Attributes to keep per tag, except `<script>` and `<style>`. */
+      attributes?: Record<string, string[]>;
+      /** Attributes to keep on every tag. `rel` needs `linkRel: null`. */
+      genericAttributes?: string[];
+      /** URL schemes allowed in links and sources, e.g. `["https", "mailto"]`. */
+      urlSchemes?: string[];
+      /** Whether to remove comments, `true` by default. */
+      stripComments?: boolean;
+      /**
+       * `rel` set on every link, `"noopener noreferrer"` by default, `null` to keep the original one.
+       * Allowing the `rel` attribute of links needs `null`.
+       */
+      linkRel?: string | null;
+    }
+
+    /**
+     * escape escapes `&`, `<`, `>`, quotes and backticks.
+     *
+     * @example
+     * ```ts
+     * const text = Andromeda.html.escape("<b>Tom & Jerry</b>");
+     * ```
+     */
+    function escape(text: string): string;
+
+    /**
+     * sanitize removes every tag, attribute and URL scheme of untrusted HTML that isn't in the allowlist.
+     *
+     * @example
+     * ```ts
+     * const clean = Andromeda.html.sanitize(comment, { tags: ["b", "i", "a"] });
+     * ```
+     */
+    function sanitize(html: string, options?: SanitizeOptions): string;
+
+    /**
+     * raw marks HTML as safe, so templates insert it without escaping it.
+     */
+    function raw(html: string): SafeHtml;
+
+    /**
+     * template builds HTML from a template literal, escaping every interpolation unless it is safe HTML.
+     *
+     * @example
+     * ```ts
+     * const page = Andromeda.html.template`<p>Hello, ${name}!</p>`;
+     * ```
+     */
+    function template(strings: TemplateStringsArray, ...values: unknown[]): SafeHtml;
+
+    /**
+     * stream builds HTML like template, as an async iterator of chunks. Interpolations can be
+     * promises and async iterables.
+     */
+    function stream(
+      strings: TemplateStringsArray,
+      ...values: unknown[]
+    ): AsyncIteratorHelpers<string>;
+  }
+
+  namespace regex {
+    /**
+     * A match found by a compiled pattern. `index` is in UTF-16 code units, like JavaScript string indexes.
+     */
+    interface Match {
+      index: number;
+      text: string;
+      captures: (string | null)[];
+      groups: Record<string, string | null>;
+    }
+
+    /**
+     * A pattern compiled by `Andromeda.regex.compile`.
+     */
+    interface Regex extends Disposable {
+      readonly pattern: string;
+      readonly flags: string;
+      /** Find the first match, or `null`. */
+      find(text: string): Match | null;
+      /** Find every non-overlapping match. */
+      findAll(text: string): Match[];
+      /** Replace the first match. The replacement can refer to groups as `$1`, `$name` or `${name}`. */
+      replace(text: string, replacement: string): string;
+      /** Replace every match. The replacement can refer to groups as `$1`, `$name` or `${name}`. */
+      replaceAll(text: string, replacement: string): string;
+      /** Split the text around the matches, into at most `limit` parts if given. */
+      split(text: string, limit?: number): string[];
+      /** Free the compiled pattern. Disposing the pattern closes it too. */
+      close(): void;
+    }
+
+    /**
+     * compile compiles a pattern for the linear-time regex engine, with optional flags
+     * `i`, `m`, `s` and `x`.
+     *
+     * @example
+     * ```ts
+     * const words = Andromeda.regex.compile("\\w+");
+     * console.log(words.findAll("hello world").length);
+     * ```
+     */
+    function compile(pattern: string, flags?: string): Regex;
+  }
+
+  namespace caches {
+    /**
+     * An in-memory cache created by `Andromeda.caches.memory`.
+     */
+    interface MemoryCache<V> extends Disposable {
+      /** Get the value of a key, or `undefined` if it's missing or expired. */
+      get(key: string): V | undefined;
+      /** Whether the key is in the cache and not expired. */
+      has(key: string): boolean;
+      /** Set the value of a key, with its own time to live in milliseconds if given. */
+      set(key: string, value: V, options?: { ttl?: number }): void;
+      /** Delete a key, returning whether it was in the cache. */
+      delete(key: string): boolean;
+      /** Delete every key. */
+      clear(): void;
+      /** Number of entries that haven't expired. */
+      readonly size: number;
+      /**
+       * Get the value of a key, computing and caching it if it's missing. Concurrent calls for the
+       * same key share one computation, and failures aren't cached.
+       */
+      getOrInsertWith(
+        key: string,
+        compute: (key: string) => V | Promise<V>,
+      ): Promise<V>;
+      /** Free the cache and its values. */
+      close(): void;
+    }
+
+    /**
+     * memory creates a cache keeping at most `capacity` least recently used entries for `ttl`
+     * milliseconds each. Both are unlimited by default.
+     *
+     * @example
+     * ```ts
+     * const sessions = Andromeda.caches.memory<string>({ capacity: 100, ttl: 30_000 });
+     * sessions.set("token", "ada");
+     * ```
+     */
+    function memory<V>(
+      options?: { capacity?: number; ttl?: number },
+    ): MemoryCache<V>;
+  }
+
+  /**
+   * memoize caches the results of an async function in a memory cache, sharing concurrent calls
+   * with the same key. The key is the JSON of the arguments unless a `key` function is given.
+   *
+   * @example
+   * ```ts
+   * const loadUser = Andromeda.memoize((id: number) => fetchUser(id), { ttl: 60_000 });
+   * ```
+   */
+  function memoize<A extends unknown[], V>(
+    fn: (...args: A) => V | Promise<V>,
+    options?: { capacity?: number; ttl?: number; key?: (...args: A) => string },
+  ): (...args: A) => Promise<V>;
+
+  namespace passwords {
+    /**
+     * hash hashes a password with a random salt, using argon2id unless another `algorithm` is given.
+     *
+     * @example
+     * ```ts
+     * Andromeda.passwords.hash("hunter2").then((hash) => console.log(hash));
+     * ```
+     */
+    function hash(
+      password: string,
+      options?: { algorithm?: "argon2id" | "bcrypt" | "scrypt" },
+    ): Promise<string>;
+
+    /**
+     * verify checks a password against a hash returned by `hash`.
+     *
+     * @example
+     * ```ts
+     * Andromeda.passwords.verify("hunter2", hash).then((ok) => console.log(ok));
+     * ```
+     */
+    function verify(password: string, hash: string): Promise<boolean>;
+  }
+
+  namespace smtp {
+    /**
+     * How to connect to the SMTP server.
+     */
+    interface TransportOptions {
+      hostname: string;
+      /** Defaults to 465 with implicit TLS, 587 with STARTTLS and 25 without TLS. */
+      port?: number;
+      /** `"implicit"` by default. */
+      tls?: "implicit" | "starttls" | "none";
+      username?: string;
+      password?: string;
+      /** Both mechanisms are tried if missing. */
+      mechanism?: "plain" | "login";
+      /** Timeout of the connection and of each command, in milliseconds. */
+      timeout?: number;
+    }
+
+    /**
+     * A file attached to a message.
+     */
+    interface Attachment {
+      filename: string;
+      /** Defaults to `text/plain; charset=utf-8` for strings and `application/octet-stream` for bytes. */
+      contentType?: string;
+      content: string | Uint8Array;
+    }
+
+    /**
+     * A message to send. Addresses are either `user@example.com` or `Name <user@example.com>`.
+     */
+    interface Message {
+      from: string;
+      to?: string | string[];
+      cc?: string | string[];
+      bcc?: string | string[];
+      replyTo?: string;
+      subject: string;
+      text?: string;
+      html?: string;
+      attachments?: Attachment[];
+    }
+
+    /**
+     * send sends a message through an SMTP server, resolving to the response of the server.
+     *
+     * @example
+     * ```ts
+     * await Andromeda.smtp.send(
+     *   { hostname: "smtp.example.com", username: "alerts", password },
+     *   { from: "alerts@example.com", to: "ops@example.com", subject: "Hi", text: "Hello!" },
+     * );
+     * ```
+     */
+    function send(transport: TransportOptions, message: Message): Promise<string>;
+  }
+
+  namespace prompts {
+    /**
+     * An option of a select or multiselect prompt, or a plain value used as its own label.
+     */
+    type Option<T> = T | { value: T; label?: string; hint?: string };
+
+    /**
+     * A running spinner returned by `spinner`.
+     */
+    interface Spinner extends Disposable {
+      /** Change the message shown next to the spinner. */
+      message(message: string): void;
+      /** Stop the spinner, showing a final message. Disposing a running spinner stops it. */
+      stop(message?: string): void;
+      /** Stop the spinner, showing the message as an error. */
+      error(message: string): void;
+    }
+
+    /**
+     * select asks to pick one of the options and returns its value.
+     *
+     * @example
+     * ```ts
+     * const color = Andromeda.prompts.select("Pick a color", ["red", "green", "blue"]);
+     * ```
+     */
+    function select<T>(
+      message: string,
+      options: Option<T>[],
+      config?: { initial?: T },
+    ): T;
+
+    /**
+     * multiselect asks to pick any of the options and returns their values.
+     *
+     * @example
+     * ```ts
+     * const toppings = Andromeda.prompts.multiselect("Toppings", ["cheese", "ham"]);
+     * ```
+     */
+    function multiselect<T>(
+      message: string,
+      options: Option<T>[],
+      config?: { initial?: T[]; required?: boolean },
+    ): T[];
+
+    /**
+     * password asks for a secret without echoing it.
+     *
+     * @example
+     * ```ts
+     * const token = Andromeda.prompts.password("API token");
+     * ```
+     */
+    function password(message: string): string;
+
+    /**
+     * confirm asks a yes or no question, defaulting to yes unless `initial` is false.
+     *
+     * @example
+     * ```ts
+     * const ok = Andromeda.prompts.confirm("Continue?");
+     * ```
+     */
+    function confirm(message: string, config?: { initial?: boolean }): boolean;
+
+    /**
+     * spinner shows a spinner with a message until it is stopped.
+     *
+     * @example
+     * ```ts
+     * const spinner = Andromeda.prompts.spinner("Downloading");
+     * spinner.stop("Downloaded");
+     * ```
+     */
+    function spinner(message: string): Spinner;
+  }
 
   namespace stdin {
     /**
@@ -183,3 +1213,78 @@ declare function prompt(message: string): string;
  * The `confirm` function prompts the user for confirmation.
  */
 declare function confirm(message: string): boolean;
+
+/**
+ * The `requestAnimationFrame` function schedules a callback for the next frame.
+ * Frames run 60 times per second unless the runtime configures another rate. The
+ * callback receives the frame timestamp in milliseconds.
+ *
+ * @example
+ * ```ts
+ * requestAnimationFrame((timestamp) => {
+ *   console.log(`frame at ${timestamp}ms`);
+ * });
+ * ```
+ */
+declare function requestAnimationFrame(
+  callback: (timestamp: number) => void,
+): number;
+
+/**
+ * The `cancelAnimationFrame` function cancels a callback scheduled with `requestAnimationFrame`.
+ */
+declare function cancelAnimationFrame(id: number): void;
+
+/**
+ * The `setImmediate` function runs a callback as soon as the current macro task and
+ * its microtasks are done, before any timer or I/O callback that is ready.
+ *
+ * Every turn of the event loop first runs the pending microtasks, then the
+ * immediates queued so far (each one followed by its microtasks), then a single
+ * macro task such as a timer. Immediates queued by an immediate run in the next turn,
+ * so they can't starve the timers.
+ *
+ * @example
+ * ```ts
+ * setTimeout(() => console.log("timeout"), 0);
+ * setImmediate(() => console.log("immediate"));
+ * Promise.resolve().then(() => console.log("microtask"));
+ * // microtask, immediate, timeout
+ * ```
+ */
+declare function setImmediate(callback: () => void): number;
+
+/**
+ * The `clearImmediate` function cancels a callback scheduled with `setImmediate`.
+ */
+declare function clearImmediate(id: number): void;
+
+/**
+ * The async iterator helpers, available on every async iterator of the runtime.
+ * They mirror the iterator helpers, returning promises for the consuming methods.
+ *
+ * @example
+ * ```ts
+ * const errors = await Andromeda.readJsonLines("logs.ndjson")
+ *   .filter((entry: any) => entry.level === "error")
+ *   .take(10)
+ *   .toArray();
+ * ```
+ */
+interface AsyncIteratorHelpers<T> extends AsyncIterableIterator<T> {
+  map<U>(mapper: (value: T, index: number) => U | Promise<U>): AsyncIteratorHelpers<U>;
+  filter(
+    predicate: (value: T, index: number) => unknown,
+  ): AsyncIteratorHelpers<T>;
+  take(limit: number): AsyncIteratorHelpers<T>;
+  drop(limit: number): AsyncIteratorHelpers<T>;
+  reduce<U>(
+    reducer: (accumulator: U, value: T, index: number) => U | Promise<U>,
+    initialValue: U,
+  ): Promise<U>;
+  toArray(): Promise<T[]>;
+  forEach(callback: (value: T, index: number) => unknown): Promise<void>;
+  some(predicate: (value: T, index: number) => unknown): Promise<boolean>;
+  every(predicate: (value: T, index: number) => unknown): Promise<boolean>;
+  find(predicate: (value: T, index: number) => unknown): Promise<T | undefined>;
+}
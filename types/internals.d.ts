@@ -46,18 +46,401 @@ declare function internal_write_line(message: string): void;
 /**
  * The `internal_file_open` function opens a File and returns a Rid.
  */
-declare function internal_open_file(path: string): void;
+declare function internal_open_file(path: string, write: boolean): number;
+
+/**
+ * The `internal_close_file` function closes a File given its Rid.
+ */
+declare function internal_close_file(rid: number): void;
+
+/**
+ * The `internal_lock_file` function locks a whole File given its Rid, waiting until the lock is available.
+ */
+declare function internal_lock_file(rid: number, exclusive: boolean): void;
+
+/**
+ * The `internal_lock_file_async` function locks a whole File given its Rid on the blocking pool.
+ */
+declare function internal_lock_file_async(
+  rid: number,
+  exclusive: boolean,
+): Promise<void>;
+
+/**
+ * The `internal_try_lock_file` function tries to lock a whole File given its Rid without waiting.
+ */
+declare function internal_try_lock_file(
+  rid: number,
+  exclusive: boolean,
+): boolean;
+
+/**
+ * The `internal_unlock_file` function unlocks a whole File given its Rid.
+ */
+declare function internal_unlock_file(rid: number): void;
+
+/**
+ * The `internal_lock_file_range` function locks a byte range of a File given its Rid, returning
+ * `false` if it doesn't `wait` and the range is locked by somebody else.
+ */
+declare function internal_lock_file_range(
+  rid: number,
+  exclusive: boolean,
+  start: number,
+  length: number,
+  wait: boolean,
+): boolean;
+
+/**
+ * The `internal_lock_file_range_async` function locks a byte range of a File given its Rid on the
+ * blocking pool.
+ */
+declare function internal_lock_file_range_async(
+  rid: number,
+  exclusive: boolean,
+  start: number,
+  length: number,
+): Promise<void>;
+
+/**
+ * The `internal_unlock_file_range` function unlocks a byte range of a File given its Rid.
+ */
+declare function internal_unlock_file_range(
+  rid: number,
+  start: number,
+  length: number,
+): void;
+
+/**
+ * The `internal_chmod` function changes the permissions of a file.
+ */
+declare function internal_chmod(path: string, mode: number): Promise<void>;
+
+/**
+ * The `internal_chown` function changes the owner user and group of a file.
+ */
+declare function internal_chown(
+  path: string,
+  uid: number | null,
+  gid: number | null,
+): Promise<void>;
+
+/**
+ * The `internal_utime` function changes the access and modification times of a file.
+ */
+declare function internal_utime(
+  path: string,
+  atime: number,
+  mtime: number,
+): Promise<void>;
+
+/**
+ * The `internal_link` function creates a hard link to a file.
+ */
+declare function internal_link(oldpath: string, newpath: string): Promise<void>;
+
+/**
+ * The `internal_read_link` function reads the target of a symbolic link.
+ */
+declare function internal_read_link(path: string): Promise<string>;
+
+/**
+ * The `internal_real_path` function resolves a path to its canonical absolute form.
+ */
+declare function internal_real_path(path: string): Promise<string>;
+
+/**
+ * The `internal_truncate` function truncates or extends a file to the given length.
+ */
+declare function internal_truncate(path: string, len: number): Promise<void>;
+
+/**
+ * The `internal_open_lines` function opens a file to read it line by line and returns the reader resource ID.
+ */
+declare function internal_open_lines(path: string): number;
+
+/**
+ * The `internal_next_line` function reads the next line of a reader, resolving with `undefined` at the end of the file.
+ */
+declare function internal_next_line(rid: number): Promise<string | undefined>;
+
+/**
+ * The `internal_close_lines` function closes a line reader given its resource ID.
+ */
+declare function internal_close_lines(rid: number): void;
 
 /**
  * The `internal_sleep` function returns a Promise to be resolved after the specified time un milliseconds.
  */
 declare function internal_sleep(duration: number): Promise<void>;
 
+/**
+ * The `internal_set_unref_timeout` function calls the callback after the specified time in milliseconds,
+ * like `setTimeout`, without keeping the event loop alive.
+ */
+declare function internal_set_unref_timeout(
+  callback: () => void,
+  delay: number,
+): number;
+
+/**
+ * The `internal_csv_parse` function parses CSV text into a JSON array of string arrays.
+ */
+declare function internal_csv_parse(text: string, options: string): string;
+
+/**
+ * The `internal_csv_stringify` function writes a JSON array of string arrays as CSV text.
+ */
+declare function internal_csv_stringify(rows: string, options: string): string;
+
+/**
+ * The `internal_toml_parse` function parses a TOML document into JSON.
+ */
+declare function internal_toml_parse(text: string): string;
+
+/**
+ * The `internal_toml_stringify` function writes JSON as a TOML document.
+ */
+declare function internal_toml_stringify(json: string): string;
+
+/**
+ * The `internal_yaml_parse` function parses a YAML document into JSON.
+ */
+declare function internal_yaml_parse(text: string): string;
+
+/**
+ * The `internal_yaml_stringify` function writes JSON as a YAML document.
+ */
+declare function internal_yaml_stringify(json: string): string;
+
+/**
+ * The `internal_diff` function compares two texts by `"lines"`, `"words"` or `"chars"` and returns the changes as JSON.
+ */
+declare function internal_diff(
+  a: string,
+  b: string,
+  granularity: string,
+): string;
+
+/**
+ * The `internal_diff_unified` function writes a unified diff between two texts.
+ */
+declare function internal_diff_unified(
+  a: string,
+  b: string,
+  oldName: string,
+  newName: string,
+  context: number,
+): string;
+
+/**
+ * The `internal_diff_apply` function applies a unified diff to a text.
+ */
+declare function internal_diff_apply(text: string, patch: string): string;
+
+/**
+ * The `internal_html_escape` function escapes the characters that are special in HTML.
+ */
+declare function internal_html_escape(text: string): string;
+
+/**
+ * The `internal_html_sanitize` function sanitizes HTML with the allowlist given as JSON.
+ */
+declare function internal_html_sanitize(html: string, options: string): string;
+
+/**
+ * The `internal_regex_compile` function compiles a pattern and returns its resource ID.
+ */
+declare function internal_regex_compile(pattern: string, flags: string): number;
+
+/**
+ * The `internal_regex_close` function frees a compiled pattern.
+ */
+declare function internal_regex_close(rid: number): void;
+
+/**
+ * The `internal_regex_find` function returns the first match as JSON, or `"null"`.
+ */
+declare function internal_regex_find(rid: number, text: string): string;
+
+/**
+ * The `internal_regex_find_all` function returns every match as a JSON array.
+ */
+declare function internal_regex_find_all(rid: number, text: string): string;
+
+/**
+ * The `internal_regex_replace` function replaces the first match, or all of them if `all` is `true`.
+ */
+declare function internal_regex_replace(
+  rid: number,
+  text: string,
+  replacement: string,
+  all: boolean,
+): string;
+
+/**
+ * The `internal_regex_split` function splits the text around the matches, as a JSON array.
+ */
+declare function internal_regex_split(
+  rid: number,
+  text: string,
+  limit?: number,
+): string;
+
+/**
+ * The `internal_cache_create` function creates a memory cache and returns its resource ID.
+ */
+declare function internal_cache_create(capacity: number, ttl: number): number;
+
+/**
+ * The `internal_cache_close` function frees a memory cache.
+ */
+declare function internal_cache_close(rid: number): void;
+
+/**
+ * The `internal_cache_get` function gets the value of a key, or `undefined` if it's missing or expired.
+ */
+declare function internal_cache_get(rid: number, key: string): any;
+
+/**
+ * The `internal_cache_set` function sets the value of a key, with its own time to live if given.
+ */
+declare function internal_cache_set(
+  rid: number,
+  key: string,
+  value: unknown,
+  ttl: number | undefined,
+): void;
+
+/**
+ * The `internal_cache_delete` function deletes a key and returns whether it was in the cache.
+ */
+declare function internal_cache_delete(rid: number, key: string): boolean;
+
+/**
+ * The `internal_cache_clear` function deletes every key of a memory cache.
+ */
+declare function internal_cache_clear(rid: number): void;
+
+/**
+ * The `internal_cache_size` function counts the entries of a memory cache that haven't expired.
+ */
+declare function internal_cache_size(rid: number): number;
+
+/**
+ * The `internal_password_hash` function hashes a password on the blocking thread pool.
+ */
+declare function internal_password_hash(
+  password: string,
+  algorithm: string,
+): Promise<string>;
+
+/**
+ * The `internal_password_verify` function checks a password against an encoded hash.
+ */
+declare function internal_password_verify(
+  password: string,
+  hash: string,
+): Promise<boolean>;
+
+/**
+ * The `internal_prompt_select` function asks to pick one of the JSON `{ label, hint }` items and
+ * returns its index.
+ */
+declare function internal_prompt_select(
+  message: string,
+  items: string,
+  initial: number,
+): number;
+
+/**
+ * The `internal_prompt_multiselect` function asks to pick any of the JSON `{ label, hint }` items
+ * and returns their indexes as JSON.
+ */
+declare function internal_prompt_multiselect(
+  message: string,
+  items: string,
+  required: boolean,
+  initial: string,
+): string;
+
+/**
+ * The `internal_prompt_password` function asks for a secret without echoing it.
+ */
+declare function internal_prompt_password(message: string): string;
+
+/**
+ * The `internal_prompt_confirm` function asks a yes or no question.
+ */
+declare function internal_prompt_confirm(
+  message: string,
+  initial: boolean,
+): boolean;
+
+/**
+ * The `internal_spinner_start` function starts a spinner and returns its Rid.
+ */
+declare function internal_spinner_start(message: string): number;
+
+/**
+ * The `internal_spinner_message` function changes the message of a spinner.
+ */
+declare function internal_spinner_message(rid: number, message: string): void;
+
+/**
+ * The `internal_spinner_stop` function stops a spinner, showing the message as an error if asked.
+ */
+declare function internal_spinner_stop(
+  rid: number,
+  message: string,
+  error: boolean,
+): void;
+
+/**
+ * The `internal_ops` function lists the registered ops as JSON, when internals are exposed.
+ */
+declare function internal_ops(): string;
+
+/**
+ * The `internal_blocking_pool_stats` function reports the blocking pool activity as JSON, when
+ * internals are exposed.
+ */
+declare function internal_blocking_pool_stats(): string;
+
 /**
  *  The `internal_print` function to log messages to the console.
  */
 declare function internal_print(message: string): void;
 
+/**
+ * The `internal_text_width` function measures the widest line of a text in terminal columns.
+ */
+declare function internal_text_width(text: string): number;
+
+/**
+ * The `internal_text_truncate` function cuts a text to fit in a number of terminal columns.
+ */
+declare function internal_text_truncate(
+  text: string,
+  width: number,
+  tail: string,
+): string;
+
+/**
+ * The `internal_text_wrap` function wraps a text to a number of terminal columns and returns the lines as JSON.
+ */
+declare function internal_text_wrap(text: string, width: number): string;
+
+/**
+ * The `internal_terminal_columns` function gets the width of the terminal, if the output is one.
+ */
+declare function internal_terminal_columns(): number | undefined;
+
+/**
+ * The `internal_no_color` function checks if colors are turned off with `NO_COLOR`, without the env permission.
+ */
+declare function internal_no_color(): boolean;
+
 /**
  * The `internal_get_cli_args` function to get the command line arguments.
  */
@@ -83,6 +466,38 @@ declare function internal_delete_env(key: string): void;
  */
 declare function internal_get_env_keys(): string[];
 
+/**
+ * The `internal_main_module` function gets the `file:` URL of the main script, if any.
+ */
+declare function internal_main_module(): string | undefined;
+
+/**
+ * The `internal_external_memory` function gets the bytes of memory held by the ops outside of the JavaScript heap.
+ */
+declare function internal_external_memory(): number;
+
+/**
+ * The `internal_shutdown_listen` function calls the listener with the name of the signal and its exit code on the first SIGINT or SIGTERM.
+ */
+declare function internal_shutdown_listen(
+  listener: (signal: string, exitCode: number) => void,
+): void;
+
+/**
+ * The `internal_set_error_thrower` function registers the function used by ops to throw the error classes.
+ */
+declare function internal_set_error_thrower(
+  thrower: (name: string, message: string, domExceptionName?: string) => never,
+): void;
+
+/**
+ * The `internal_smtp_send` function sends the message given as JSON with the transport options given as JSON.
+ */
+declare function internal_smtp_send(
+  options: string,
+  message: string,
+): Promise<string>;
+
 /**
  * The `internal_url_parse` function to parse a URL string.
  */
@@ -91,4 +506,8 @@ declare function internal_url_parse(url: string, base: string): string;
 /**
  * The `internal_url_parse_no_base` function to parse a URL string without a base URL.
  */
-declare function internal_url_parse_no_base(url: string): string;
\ No newline at end of file
+declare function internal_url_parse_no_base(url: string): string;
+/**
+ * The `internal_dom_parse` function parses HTML or XML and returns the nodes of the document as JSON.
+ */
+declare function internal_dom_parse(text: string, type: string): string;